@@ -0,0 +1,13 @@
+// Exercise: Make add_original_main Not Overflow
+// Lesson: original_main (see src/original_main.rs)
+//
+// A `u8` holds 0 to 255, so `200 + 100` does not fit: in a debug build the addition
+// panics with "attempt to add with overflow". Change the body so that the function
+// returns `None` when the sum does not fit, and `Some(sum)` when it does.
+// (Hint: the integer types have a few `checked_*` methods.)
+//
+// Check your answer with: cargo run -- exercise check add_without_overflow
+
+pub fn add_original_main(x: u8, y: u8) -> Option<u8> {
+    Some(x + y)
+}
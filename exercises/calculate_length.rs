@@ -0,0 +1,12 @@
+// Exercise: Implement calculate_length
+// Lesson: references_borrowing (see src/references_borrowing.rs)
+//
+// Return the length of `s` in bytes. The function only borrows the String,
+// so the caller can keep using it afterwards.
+//
+// Check your answer with: cargo run -- exercise check calculate_length
+
+#[allow(clippy::ptr_arg)]
+pub fn calculate_length(s: &String) -> usize {
+    todo!("return the length of {:?}", s)
+}
@@ -0,0 +1,17 @@
+// Exercise: Fix the Borrow Error
+// Lesson: more_borrowing_rules (see src/more_borrowing_rules.rs)
+//
+// `before_and_after` should return "hello / hello, world": the text before the change,
+// then the text after it. It does not compile yet: `before` still borrows `s` when
+// `after` tries to borrow it mutably.
+//
+// Fix the borrow error without changing what the function returns.
+// Check your answer with: cargo run -- exercise check fix_borrow_error
+
+pub fn before_and_after() -> String {
+    let mut s = String::from("hello");
+    let before = &s;
+    let after = &mut s;
+    after.push_str(", world");
+    format!("{} / {}", before, after)
+}
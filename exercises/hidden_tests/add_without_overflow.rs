@@ -0,0 +1,12 @@
+#[test]
+fn small_sums_fit() {
+    assert_eq!(add_original_main(12, 18), Some(30));
+    assert_eq!(add_original_main(0, 0), Some(0));
+    assert_eq!(add_original_main(200, 55), Some(255));
+}
+
+#[test]
+fn large_sums_do_not_overflow() {
+    assert_eq!(add_original_main(200, 100), None);
+    assert_eq!(add_original_main(255, 1), None);
+}
@@ -0,0 +1,14 @@
+#[test]
+fn counts_bytes() {
+    assert_eq!(calculate_length(&String::from("hello")), 5);
+    assert_eq!(calculate_length(&String::new()), 0);
+    // "é" takes two bytes in UTF-8.
+    assert_eq!(calculate_length(&String::from("café")), 5);
+}
+
+#[test]
+fn the_string_can_be_used_afterwards() {
+    let s = String::from("Hello, Blockchain!");
+    let len = calculate_length(&s);
+    assert_eq!(format!("{} has {} bytes", s, len), "Hello, Blockchain! has 18 bytes");
+}
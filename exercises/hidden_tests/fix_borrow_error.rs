@@ -0,0 +1,4 @@
+#[test]
+fn returns_the_text_before_and_after_the_change() {
+    assert_eq!(before_and_after(), "hello / hello, world");
+}
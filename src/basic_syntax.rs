@@ -1,38 +1,48 @@
 // Module: Basic Syntax, Variables, Functions, and Shadowing
 // This module demonstrates basic Rust syntax, variable declaration (including mutability and shadowing),
 // and function calls.
+//
+// Like every lesson, it prints with `writeln!(out, ...)` instead of `println!(...)`.
+// `out` can be any `io::Write` sink: `main` passes standard output, and tests pass a
+// `Vec<u8>` so they can check exactly what was printed. The `?` after each `writeln!`
+// passes a write error back to the caller instead of ignoring it.
 
-pub fn demonstrate_basics() {
-    println!("\n--- Basic Syntax, Variables, Functions, and Shadowing ---");
+use crate::lesson::{Chapter, Lesson};
+use std::io::{self, BufRead, Write};
+
+pub fn demonstrate_basics(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n--- Basic Syntax, Variables, Functions, and Shadowing ---")?;
 
     // --- Variables and Mutability ---
     // Variables are immutable by default.
     let immutable_var: u8 = 5;
-    println!("Immutable variable 'immutable_var': {}", immutable_var);
+    writeln!(out, "Immutable variable 'immutable_var': {}", immutable_var)?;
     // immutable_var = 10; // This would cause a compile error because immutable_var is not mutable.
 
     // To make a variable mutable, use the `mut` keyword.
     let mut mutable_var: i32 = 10;
-    println!("Initial value of 'mutable_var': {}", mutable_var);
+    writeln!(out, "Initial value of 'mutable_var': {}", mutable_var)?;
     mutable_var = 20;
-    println!("New value of 'mutable_var': {}", mutable_var);
+    writeln!(out, "New value of 'mutable_var': {}", mutable_var)?;
 
     // --- Basic Data Types (Implicit and Explicit) ---
     let an_integer = 15; // Type i32 inferred by default for integers
+    // (Clippy would suggest `std::f64::consts::PI`; any literal works for this example.)
+    #[allow(clippy::approx_constant)]
     let a_float = 3.14;  // Type f64 inferred by default for floats
     let a_boolean = true; // Type bool inferred
-    println!("Inferred types: integer={}, float={}, boolean={}", an_integer, a_float, a_boolean);
+    writeln!(out, "Inferred types: integer={}, float={}, boolean={}", an_integer, a_float, a_boolean)?;
 
     // --- Function Calls ---
     // Call a simple function that prints a value.
     // Primitive types like u8 implement the `Copy` trait, so `an_integer_param` is copied.
     let an_integer_param: u8 = 25;
-    print_value_passed(an_integer_param);
-    println!("'an_integer_param' is still valid here: {}", an_integer_param);
+    print_value_passed(out, an_integer_param)?;
+    writeln!(out, "'an_integer_param' is still valid here: {}", an_integer_param)?;
 
     // Call a function that returns a value.
     let sum_result = add_numbers(10, 20);
-    println!("The sum of 10 and 20 (from add_numbers function) is: {}", sum_result);
+    writeln!(out, "The sum of 10 and 20 (from add_numbers function) is: {}", sum_result)?;
 
     // --- Shadowing ---
     // You can declare a new variable with the same name as a previous variable.
@@ -40,11 +50,11 @@ pub fn demonstrate_basics() {
     // The previous variable is still there but inaccessible by its name in this scope.
 
     let shadow_example_num: i32 = 5;
-    println!("'shadow_example_num' initially (integer): {}", shadow_example_num);
+    writeln!(out, "'shadow_example_num' initially (integer): {}", shadow_example_num)?;
 
     // Shadowing `shadow_example_num` with a new variable of the same name but potentially different value.
     let shadow_example_num = shadow_example_num + 5;
-    println!("'shadow_example_num' after shadowing (integer + 5): {}", shadow_example_num);
+    writeln!(out, "'shadow_example_num' after shadowing (integer + 5): {}", shadow_example_num)?;
 
     // Shadowing is different from marking a variable as `mut` because:
     // 1. We are effectively creating a new variable.
@@ -52,29 +62,54 @@ pub fn demonstrate_basics() {
     // 3. It's useful if you want to perform some transformations on a value but keep it immutable afterwards.
 
     let spaces = "   "; // `spaces` is a string slice (&str)
-    println!("'spaces' initially (string slice): \"{}\"", spaces);
+    writeln!(out, "'spaces' initially (string slice): \"{}\"", spaces)?;
 
     let spaces = spaces.len(); // `spaces` is shadowed by a new variable, now of type usize (length of the string)
-    println!("'spaces' after shadowing (now length, type usize): {}", spaces);
+    writeln!(out, "'spaces' after shadowing (now length, type usize): {}", spaces)?;
     // This is allowed because the first `spaces` variable (the string slice) goes out of scope
     // (or rather, its name is reused), and a new `spaces` variable (the usize) is created.
 
     // Another example from original prac11:
     let x_shadow = 5;
-    println!("x_shadow (original): {}", x_shadow); // x_shadow is i32
+    writeln!(out, "x_shadow (original): {}", x_shadow)?; // x_shadow is i32
 
     let x_shadow = "Hello, Blockchain!"; // x_shadow is now &str
-    println!("x_shadow (shadowed as &str): \"{}\"", x_shadow);
+    writeln!(out, "x_shadow (shadowed as &str): \"{}\"", x_shadow)?;
 
     let x_shadow = x_shadow.len(); // x_shadow is now usize
-    println!("x_shadow (shadowed as usize - length of string): {}", x_shadow);
+    writeln!(out, "x_shadow (shadowed as usize - length of string): {}", x_shadow)?;
+    Ok(())
+}
+
+pub struct BasicSyntaxLesson;
+
+impl Lesson for BasicSyntaxLesson {
+    fn id(&self) -> &'static str {
+        "basic_syntax"
+    }
+
+    fn title(&self) -> &'static str {
+        "Basic Syntax, Variables, Functions, and Shadowing"
+    }
+
+    fn chapter(&self) -> Chapter {
+        Chapter::GettingStarted
+    }
+
+    fn run(&self, _input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        demonstrate_basics(out)
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("basic_syntax.rs")
+    }
 }
 
 // This function takes a u8 value as an argument and prints it.
 // 'value' is a copy of the argument passed to the function because u8 is a simple type
 // that implements the Copy trait.
-fn print_value_passed(value: u8) {
-    println!("Value received in print_value_passed function: {}", value);
+fn print_value_passed(out: &mut dyn Write, value: u8) -> io::Result<()> {
+    writeln!(out, "Value received in print_value_passed function: {}", value)
 }
 
 // Example of a function that returns a value.
@@ -116,15 +151,13 @@ mod tests {
         assert_eq!(add_numbers(0, -3), -3);
     }
 
-    // Example of a test that uses an internal (non-pub) function if it existed.
-    // Here, we'll test `print_value_passed` indirectly by checking compilation,
-    // but typically you'd test functions that return values or have side effects
-    // you can observe. Since `print_value_passed` only prints, a true unit test
-    // is harder without more complex testing setups (e.g., capturing stdout).
-    // For this tutorial, we'll just ensure it compiles as part of the module.
+    // Tests can also use internal (non-pub) functions like `print_value_passed`.
+    // It only prints, but because it writes to any `io::Write`, the test can hand it
+    // a `Vec<u8>` and check the captured text instead of looking at the console.
     #[test]
-    fn test_print_value_passed_compiles() {
-        print_value_passed(10); // Just checking it can be called
-        // No assert needed if we're just checking for compilation within the test context.
+    fn test_print_value_passed_output() {
+        let mut output: Vec<u8> = Vec::new();
+        print_value_passed(&mut output, 10).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "Value received in print_value_passed function: 10\n");
     }
 }
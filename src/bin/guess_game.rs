@@ -0,0 +1,216 @@
+// Rock-paper-scissors against the computer. The game itself is in the library
+// (src/guess_game.rs); this binary reads the command line and starts a match:
+//     cargo run --bin guess_game
+//     cargo run --bin guess_game -- --best-of 5
+//     cargo run --bin guess_game -- --first-to 3
+//     cargo run --bin guess_game -- --rules rpsls
+//     cargo run --bin guess_game -- --rules my_rules.json
+//     cargo run --bin guess_game -- --strategy markov
+//     cargo run --bin guess_game -- --seed 42      (or RUST_PRACTICE_SEED=42: the same computer moves every time)
+//     cargo run --bin guess_game -- --rng pcg32    (a random generator written from scratch, see src/rng/generators.rs)
+// Or full-screen, choosing the moves with the arrow keys:
+//     cargo run --bin guess_game -- tui --best-of 5
+// The name and moves can also be given up front, which is how the tests and scripts play:
+//     cargo run --bin guess_game -- --player alice --input "rock\n"
+//     cargo run --bin guess_game -- --answers moves.txt
+// Two players can also play each other over the network, each in their own terminal:
+//     cargo run --bin guess_game -- host --best-of 3
+//     cargo run --bin guess_game -- join 192.168.1.20
+// Or guess a number instead, alone or by watching the binary-search bot:
+//     cargo run --bin guess_game -- number --difficulty hard --hints
+//     cargo run --bin guess_game -- number --bot
+// A session can be saved and checked again later, e.g. for a bug report:
+//     cargo run --bin guess_game -- --seed 42 --log session.jsonl
+//     cargo run --bin guess_game -- replay session.jsonl
+// The computer strategies can also play each other, to see which one is strongest:
+//     cargo run --bin guess_game -- tournament --games 200 --seed 1 --csv results.csv
+// Every game against the computer is added to the player's statistics:
+//     cargo run --bin guess_game -- stats alice
+//     cargo run --bin guess_game -- leaderboard
+
+use rust_practice::guess_game::cli::{self, Command, NumberOptions, PlayOptions, TournamentOptions};
+use rust_practice::guess_game::lan::{self, Player};
+use rust_practice::guess_game::number::{self, NumberGame};
+use rust_practice::guess_game::replay::Log;
+use rust_practice::guess_game::{play, tournament, tui, Session};
+use rust_practice::guess_game::stats::{self, Stats};
+use rust_practice::rng;
+use rust_practice::storage;
+use std::env;
+use std::io::{self, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process;
+
+fn main(){
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            process::exit(2);
+        }
+    };
+    let result = match command {
+        Command::Play(options) => play_game(options),
+        Command::Tui(options) => tui_game(options),
+        Command::Host(port, options) => host_game(port, options),
+        Command::Join(address, options) => join_game(&address, options),
+        Command::Number(options) => guess_number(options),
+        Command::Tournament(options) => run_tournament(options),
+        Command::Replay(path) => replay(&path),
+        Command::Stats(player) => show_stats(player.as_deref()),
+        Command::Leaderboard => show_leaderboard(),
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+    };
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    }
+}
+
+fn play_game(options: PlayOptions) -> Result<(), String> {
+    let seed = rng::choose_seed(options.seed)?;
+    let mut input = options.source.open().map_err(|error| format!("cannot open the answers: {}", error))?;
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let player = player_name(options.player.clone(), &mut input, &mut out)?;
+    let mut rng = options.rng.seeded(seed);
+    let session = play(&mut input, &mut out, &mut rng, &options.settings).map_err(|error| error.to_string())?;
+    record(&player, seed, &options, session)
+}
+
+fn tui_game(options: PlayOptions) -> Result<(), String> {
+    let seed = rng::choose_seed(options.seed)?;
+    // The name is asked for before the screen is taken over.
+    let player = player_name(options.player.clone(), &mut io::stdin().lock(), &mut io::stdout())?;
+    let session = tui::play(options.settings.clone(), options.rng.seeded(seed))
+        .map_err(|error| format!("the terminal UI failed: {}", error))?;
+    println!(
+        "Thanks for playing! {} rounds; matches won: you {}, computer {}.",
+        session.rounds.len(),
+        session.matches.wins,
+        session.matches.losses
+    );
+    record(&player, seed, &options, session)
+}
+
+// Saves the log if `--log` was given, and adds the session to the player's statistics
+// if any round was played.
+fn record(player: &str, seed: u64, options: &PlayOptions, session: Session) -> Result<(), String> {
+    let settings = &options.settings;
+    let mut stats = if session.rounds.is_empty() { None } else { Some(load_stats()?) };
+    if let Some(stats) = stats.as_mut() {
+        stats.record_session(player, &settings.rules, &session, storage::unix_now());
+    }
+    if let Some(path) = &options.log {
+        let log =
+            Log { player: player.to_string(), seed, rng: options.rng, settings: settings.clone(), rounds: session.rounds };
+        log.save(path).map_err(|error| format!("cannot save the log to {}: {}", path.display(), error))?;
+    }
+    match stats {
+        Some(stats) => stats.save().map_err(|error| format!("cannot save the statistics: {}", error)),
+        None => Ok(()),
+    }
+}
+
+fn replay(path: &Path) -> Result<(), String> {
+    let log = Log::load(path)?;
+    log.render(&mut io::stdout().lock()).map_err(|error| error.to_string())?;
+    let moves = log.verify().map_err(|problem| format!("the log does not replay: {}", problem))?;
+    println!("Verified: seed {} gives the same {} computer move{}.", log.seed, moves, if moves == 1 { "" } else { "s" });
+    Ok(())
+}
+
+fn host_game(port: u16, options: PlayOptions) -> Result<(), String> {
+    // Listen on every network interface, so players on other computers can join.
+    let listener =
+        TcpListener::bind(("0.0.0.0", port)).map_err(|error| format!("cannot listen on port {}: {}", port, error))?;
+    let port = listener.local_addr().map_err(|error| error.to_string())?.port();
+    println!("Waiting for an opponent on port {} (they run `guess_game join <this computer's address>:{}`)", port, port);
+    let mut input = options.source.open().map_err(|error| format!("cannot open the answers: {}", error))?;
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let name = player_name(options.player, &mut input, &mut out)?;
+    let player = Player { name: &name, input: &mut input, out: &mut out, rng: &mut rand::thread_rng() };
+    lan::host(&listener, player, &options.settings.rules, options.settings.format)
+        .map(|_| ())
+        .map_err(|error| error.to_string())
+}
+
+fn join_game(address: &str, options: PlayOptions) -> Result<(), String> {
+    let mut input = options.source.open().map_err(|error| format!("cannot open the answers: {}", error))?;
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let name = player_name(options.player, &mut input, &mut out)?;
+    let player = Player { name: &name, input: &mut input, out: &mut out, rng: &mut rand::thread_rng() };
+    lan::join(address, player)
+        .map(|_| ())
+        .map_err(|error| format!("game with {} failed: {}", address, error))
+}
+
+fn guess_number(options: NumberOptions) -> Result<(), String> {
+    let seed = rng::choose_seed(options.seed)?;
+    let mut game = NumberGame::random(options.difficulty, &mut options.rng.seeded(seed));
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let result = if options.bot {
+        number::play_bot(&mut out, &mut game)
+    } else {
+        let mut input = options.source.open().map_err(|error| format!("cannot open the answers: {}", error))?;
+        number::play(&mut input, &mut out, &mut game, options.hints)
+    };
+    result.map_err(|error| error.to_string())
+}
+
+fn run_tournament(options: TournamentOptions) -> Result<(), String> {
+    let seed = rng::choose_seed(options.seed)?;
+    let results = tournament::run(&options.config, &mut options.rng.seeded(seed));
+    results.write_table(&mut io::stdout().lock()).map_err(|error| error.to_string())?;
+    let again = if options.rng.is_std() { format!("--seed {}", seed) } else { format!("--seed {} --rng {}", seed, options.rng) };
+    println!("\nSeed: {} (pass `{}` to play the same tournament again)", seed, again);
+    if let Some(path) = &options.csv {
+        let mut csv: Vec<u8> = Vec::new();
+        results.write_csv(&mut csv).map_err(|error| error.to_string())?;
+        std::fs::write(path, csv).map_err(|error| format!("cannot write {}: {}", path.display(), error))?;
+        println!("Saved the results to {}.", path.display());
+    }
+    Ok(())
+}
+
+// The name from `--player`, or asked for.
+fn player_name(given: Option<String>, input: &mut dyn io::BufRead, out: &mut dyn Write) -> Result<String, String> {
+    match given {
+        Some(name) => Ok(name),
+        None => stats::read_player_name(input, out).map_err(|error| error.to_string()),
+    }
+}
+
+fn show_stats(player: Option<&str>) -> Result<(), String> {
+    let stats = load_stats()?;
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let result = match player {
+        Some(player) => stats.write_player(player, &mut out),
+        None if stats.players.is_empty() => stats.write_leaderboard(&mut out),
+        None => stats.players.keys().enumerate().try_for_each(|(index, player)| {
+            if index > 0 {
+                writeln!(out)?;
+            }
+            stats.write_player(player, &mut out)
+        }),
+    };
+    result.map_err(|error| error.to_string())
+}
+
+fn show_leaderboard() -> Result<(), String> {
+    let stats = load_stats()?;
+    stats.write_leaderboard(&mut io::stdout().lock()).map_err(|error| error.to_string())
+}
+
+fn load_stats() -> Result<Stats, String> {
+    Stats::load().map_err(|error| format!("cannot read the statistics: {}", error))
+}
@@ -0,0 +1,107 @@
+// Prints random values. The helpers live in the library (src/random_number.rs).
+//     cargo run --bin random_number
+//     cargo run --bin random_number -- --seed 42   (or RUST_PRACTICE_SEED=42: the same values every time)
+//     cargo run --bin random_number -- --rng pcg32 --seed 42   (a generator written from scratch, see src/rng/generators.rs)
+// With options it generates values of one type, e.g. ten dice rolls or test data:
+//     cargo run --bin random_number -- --type u8 --range 1..=6 --count 10 --separator " "
+//     cargo run --bin random_number -- --type f64 --range -1..1 --count 5 --format json
+// Or from a distribution, summarised or drawn as a histogram instead of printed:
+//     cargo run --bin random_number -- --dist normal:10,2 --summary --histogram
+//     cargo run --bin random_number -- --dist poisson:4 --count 10
+// Or tabletop dice, showing every die:
+//     cargo run --bin random_number -- roll 4d6kh3+2
+// Or passwords and passphrases for test accounts, each with its entropy:
+//     cargo run --bin random_number -- password --length 20 --no-ambiguous --count 3
+//     cargo run --bin random_number -- password --words 6
+
+use rust_practice::random_number::cli::{self, Command, GenerateOptions, PasswordOptions, RollOptions};
+use rust_practice::random_number::dice;
+use rust_practice::random_number::report::{self, Summary};
+use rust_practice::random_number::{write_random_values, write_values, Sampler, Value};
+use rust_practice::rng::{self, RngKind};
+use std::env;
+use std::io::{self, Write};
+use std::process;
+
+fn main(){
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::usage());
+            process::exit(2);
+        }
+    };
+    let result = match command {
+        Command::Sample(seed, kind) => sample(seed, kind),
+        Command::Generate(options) => generate(options),
+        Command::Roll(options) => roll(options),
+        Command::Password(options) => password(options),
+        Command::Help => {
+            println!("{}", cli::usage());
+            Ok(())
+        }
+    };
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    }
+}
+
+fn sample(seed: Option<u64>, kind: RngKind) -> Result<(), String> {
+    let mut rng = kind.seeded(rng::choose_seed(seed)?);
+    write_random_values(&mut io::stdout().lock(), &mut rng).map_err(|error| error.to_string())
+}
+
+fn generate(options: GenerateOptions) -> Result<(), String> {
+    let mut rng = options.rng.seeded(rng::choose_seed(options.seed)?);
+    let values: Vec<Value> = (0..options.count).map(|_| options.sampler.sample(&mut rng)).collect();
+    let out = &mut io::stdout().lock();
+    if !options.summary && options.histogram.is_none() {
+        return write_values(out, &values, options.format, &options.separator).map_err(|error| error.to_string());
+    }
+    // The parser turns characters away, so every value is a number here.
+    let numbers: Vec<f64> = values.iter().filter_map(|value| value.as_f64()).collect();
+    describe(out, &options, &numbers).map_err(|error| error.to_string())
+}
+
+fn roll(options: RollOptions) -> Result<(), String> {
+    let mut rng = options.rng.seeded(rng::choose_seed(options.seed)?);
+    let roll = options.expr.roll(&mut rng)?;
+    dice::write_roll(&mut io::stdout().lock(), &options.expr, &roll).map_err(|error| error.to_string())
+}
+
+fn password(options: PasswordOptions) -> Result<(), String> {
+    let mut rng = rng::strong_rng(options.seed)?;
+    let bits = options.recipe.entropy_bits();
+    let out = &mut io::stdout().lock();
+    for _ in 0..options.count {
+        writeln!(out, "{}\t{:.1} bits", options.recipe.generate(&mut rng), bits).map_err(|error| error.to_string())?;
+    }
+    Ok(())
+}
+
+fn describe(out: &mut dyn Write, options: &GenerateOptions, numbers: &[f64]) -> io::Result<()> {
+    if let Sampler::Distribution(distribution) = &options.sampler {
+        writeln!(out, "Distribution: {}", distribution)?;
+    }
+    if options.summary {
+        match Summary::of(numbers) {
+            Some(summary) => {
+                let expected = match &options.sampler {
+                    Sampler::Distribution(distribution) => Some((distribution.mean(), distribution.variance())),
+                    Sampler::Values(_) => None,
+                };
+                summary.write(out, expected)?;
+            }
+            None => writeln!(out, "Count:     0")?,
+        }
+    }
+    if let Some(bins) = options.histogram {
+        if options.summary {
+            writeln!(out)?;
+        }
+        report::write_histogram(out, numbers, bins, options.sampler.whole_numbers())?;
+    }
+    Ok(())
+}
@@ -1,27 +1,30 @@
 // Module: Boolean Type
 // This module covers the boolean type in Rust.
 
-pub fn demonstrate_boolean_type() {
-    println!("\n--- Boolean Type ---");
+use crate::lesson::{Chapter, Lesson};
+use std::io::{self, BufRead, Write};
+
+pub fn demonstrate_boolean_type(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n--- Boolean Type ---")?;
 
     // Rust's boolean type is `bool`, which can have two possible values: `true` or `false`.
     let is_sunny: bool = true;
     let is_raining = false; // Type `bool` is inferred here
 
-    println!("Is it sunny? {}", is_sunny);
-    println!("Is it raining? {}", is_raining);
+    writeln!(out, "Is it sunny? {}", is_sunny)?;
+    writeln!(out, "Is it raining? {}", is_raining)?;
 
     // Booleans are commonly used in control flow statements (like if/else).
     if is_sunny {
-        println!("It's a sunny day! Don't forget your sunglasses.");
+        writeln!(out, "It's a sunny day! Don't forget your sunglasses.")?;
     } else {
-        println!("It's not sunny today.");
+        writeln!(out, "It's not sunny today.")?;
     }
 
     if is_raining {
-        println!("It's raining! Remember your umbrella.");
+        writeln!(out, "It's raining! Remember your umbrella.")?;
     } else {
-        println!("No rain for now.");
+        writeln!(out, "No rain for now.")?;
     }
 
     // Boolean logic operators:
@@ -33,16 +36,16 @@ pub fn demonstrate_boolean_type() {
     let have_umbrella = false;
 
     let go_to_beach = is_sunny && !is_raining;
-    println!("Should we go to the beach? {}", go_to_beach);
+    writeln!(out, "Should we go to the beach? {}", go_to_beach)?;
 
     let stay_inside = is_raining || !is_sunny;
-    println!("Should we stay inside? {}", stay_inside);
+    writeln!(out, "Should we stay inside? {}", stay_inside)?;
 
     let need_sunglasses_today = is_sunny && have_sunglasses;
     let need_umbrella_today = is_raining && have_umbrella; // This was `is_sunny && is_raining` in prac7, which is less logical
 
-    println!("Do I need sunglasses today? {}", need_sunglasses_today);
-    println!("Do I need an umbrella today? {}", need_umbrella_today); // Corrected logic from prac7
+    writeln!(out, "Do I need sunglasses today? {}", need_sunglasses_today)?;
+    writeln!(out, "Do I need an umbrella today? {}", need_umbrella_today)?; // Corrected logic from prac7
 
     // Original prac7.rs:
     // fn main(){
@@ -54,4 +57,33 @@ pub fn demonstrate_boolean_type() {
     //     println!("need_sunglasses: {}", need_sunglasses);
     // }
     // The refactored module provides clearer examples and slightly adjusted logic for clarity.
+    Ok(())
+}
+
+pub struct BooleanTypeLesson;
+
+impl Lesson for BooleanTypeLesson {
+    fn id(&self) -> &'static str {
+        "boolean_type"
+    }
+
+    fn title(&self) -> &'static str {
+        "Boolean Type"
+    }
+
+    fn chapter(&self) -> Chapter {
+        Chapter::DataTypes
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["basic_syntax"]
+    }
+
+    fn run(&self, _input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        demonstrate_boolean_type(out)
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("boolean_type.rs")
+    }
 }
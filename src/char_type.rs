@@ -1,8 +1,11 @@
 // Module: Character Type
 // This module explains Rust's character type `char`.
 
-pub fn demonstrate_char_type() {
-    println!("\n--- Character Type ---");
+use crate::lesson::{Chapter, Lesson};
+use std::io::{self, BufRead, Write};
+
+pub fn demonstrate_char_type(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n--- Character Type ---")?;
 
     // Rust's `char` type represents a single Unicode scalar value.
     // This means it can store much more than just ASCII characters.
@@ -13,31 +16,31 @@ pub fn demonstrate_char_type() {
     let c4: char = '😻'; // Emoji character
     let c5: char = 'Ω'; // Greek letter Omega
 
-    println!("c1 (letter): {}", c1);
-    println!("c2 (letter): {}", c2);
-    println!("c3 (digit): {}", c3);
-    println!("c4 (emoji): {}", c4);
-    println!("c5 (Greek letter): {}", c5);
+    writeln!(out, "c1 (letter): {}", c1)?;
+    writeln!(out, "c2 (letter): {}", c2)?;
+    writeln!(out, "c3 (digit): {}", c3)?;
+    writeln!(out, "c4 (emoji): {}", c4)?;
+    writeln!(out, "c5 (Greek letter): {}", c5)?;
 
     // `char` type is 4 bytes in size (UTF-32).
-    println!("Size of char: {} bytes", std::mem::size_of::<char>());
+    writeln!(out, "Size of char: {} bytes", std::mem::size_of::<char>())?;
 
     // A String is a collection of UTF-8 encoded characters.
     // While related, `char` and `String` are different.
     // A `String` is a growable, heap-allocated data structure, whereas `char` is a single character.
     let string_example: String = String::from("Hello, Rustaceans! 🦀");
-    println!("Example String: {}", string_example);
-    println!("Iterating through characters of the string:");
+    writeln!(out, "Example String: {}", string_example)?;
+    writeln!(out, "Iterating through characters of the string:")?;
     for character in string_example.chars() {
-        print!("'{}' ", character);
+        write!(out, "'{}' ", character)?;
     }
-    println!(); // Newline after loop
+    writeln!(out)?; // Newline after loop
 
     // You can check properties of characters:
-    println!("Is 'A' an alphabetic character? {}", 'A'.is_alphabetic());
-    println!("Is '7' a numeric digit? {}", '7'.is_numeric());
-    println!("Is '😻' an emoji (alphanumeric check)? {}", '😻'.is_alphanumeric()); // Emojis are often not alphanumeric
-    println!("Is ' ' a whitespace character? {}", ' '.is_whitespace());
+    writeln!(out, "Is 'A' an alphabetic character? {}", 'A'.is_alphabetic())?;
+    writeln!(out, "Is '7' a numeric digit? {}", '7'.is_numeric())?;
+    writeln!(out, "Is '😻' an emoji (alphanumeric check)? {}", '😻'.is_alphanumeric())?; // Emojis are often not alphanumeric
+    writeln!(out, "Is ' ' a whitespace character? {}", ' '.is_whitespace())?;
 
     // Original prac8.rs:
     // fn main(){
@@ -47,4 +50,33 @@ pub fn demonstrate_char_type() {
     //     println!("{} {} {}", char_var, emoji_var, string_var);
     // }
     // This refactored module provides more detail on the char type.
+    Ok(())
+}
+
+pub struct CharTypeLesson;
+
+impl Lesson for CharTypeLesson {
+    fn id(&self) -> &'static str {
+        "char_type"
+    }
+
+    fn title(&self) -> &'static str {
+        "Character Type"
+    }
+
+    fn chapter(&self) -> Chapter {
+        Chapter::DataTypes
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["basic_syntax"]
+    }
+
+    fn run(&self, _input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        demonstrate_char_type(out)
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("char_type.rs")
+    }
 }
@@ -0,0 +1,449 @@
+// Module: Command-Line Interface
+// Parses the arguments of the `RustPractice` binary into a `Command`.
+// Parsing is kept separate from running the lessons so it can be unit-tested.
+
+use crate::export::Format;
+use crate::input::InputSource;
+use crate::lesson::Chapter;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: RustPractice <command>
+
+Commands:
+  list                              List all lessons, grouped by chapter
+  search <keyword>                  Find lessons whose id, title or chapter mention <keyword>
+  run <topic>...                    Run one or more lessons by id (e.g. `run loops`)
+  run --chapter <chapter>           Run every lesson of a chapter (e.g. `run --chapter ownership`)
+  run --all [--skip-interactive]    Run every lesson in study order
+  quiz [<topic>]                    Take the quiz of a lesson (without <topic>: list the quizzes)
+  exercise [list]                   List the hands-on exercises in `exercises/`
+  exercise check [<name>...]        Compile and test your solutions (all exercises by default)
+  export [<topic>...]               Write the lessons as pages (all lessons by default)
+  serve [--port <port>]             Serve the lessons with a playground on http://127.0.0.1:8000/
+  progress [--user <name>]          Show completed lessons and quiz scores, per chapter
+  reset [--user <name> | --all]     Forget the recorded progress (asks first unless --yes)
+  help                              Show this message
+
+Options for `run` and `quiz`:
+  --input <text>                    Answer interactive lessons from <text>, e.g. \"Alice\\n30\\n\"
+  --answers <file>                  Answer interactive lessons from the lines of <file>
+  --user <name>                     Record completed lessons and quiz scores for <name>
+
+Options for `export`:
+  --format <format>                 markdown (default), html or mdbook
+  --out <dir>                       Where to write the pages (default: ./export)
+
+Options for `exercise`:
+  --dir <path>                      Where the exercise files are (default: ./exercises)
+  --user <name>                     Record solved exercises for <name>
+
+Progress is recorded per learner. Without --user, the name comes from RUST_PRACTICE_USER
+or your login name.";
+
+pub const DEFAULT_PORT: u16 = 8000;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    List,
+    Search(String),
+    Run(RunSelection, RunOptions),
+    // `None` lists the lessons that have a quiz.
+    Quiz(Option<String>, RunOptions),
+    Exercise(ExerciseCommand),
+    // An empty list of topics means every lesson.
+    Export(Vec<String>, ExportOptions),
+    Serve { port: u16 },
+    Progress { user: Option<String> },
+    Reset(ResetOptions),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RunSelection {
+    Topics(Vec<String>),
+    Chapter(Chapter),
+    All,
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct RunOptions {
+    // Skip lessons that wait for keyboard input (see `Lesson::is_interactive`).
+    pub skip_interactive: bool,
+    // Where interactive lessons read their answers from.
+    pub input: InputSource,
+    // The learner whose progress is recorded (`None` means the default learner).
+    pub user: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ExerciseCommand {
+    List(ExerciseOptions),
+    // An empty list means every exercise.
+    Check(Vec<String>, ExerciseOptions),
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct ExerciseOptions {
+    // `None` means the default `exercises/` directory.
+    pub dir: Option<PathBuf>,
+    pub user: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExportOptions {
+    pub format: Format,
+    pub out: PathBuf,
+}
+
+impl Default for ExportOptions {
+    fn default() -> ExportOptions {
+        ExportOptions { format: Format::Markdown, out: PathBuf::from("export") }
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct ResetOptions {
+    pub user: Option<String>,
+    // Forget every learner instead of just one.
+    pub all: bool,
+    // Do not ask for confirmation.
+    pub yes: bool,
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(Command::Help);
+    };
+
+    match command.as_str() {
+        "list" => {
+            expect_no_more(rest)?;
+            Ok(Command::List)
+        }
+        "search" => match rest {
+            [keyword] => Ok(Command::Search(keyword.clone())),
+            [] => Err(String::from("`search` needs a keyword")),
+            _ => Err(String::from("`search` takes a single keyword (use quotes for several words)")),
+        },
+        "run" => parse_run(rest),
+        "quiz" => parse_quiz(rest),
+        "progress" => match rest {
+            [] => Ok(Command::Progress { user: None }),
+            [flag, name] if flag == "--user" => Ok(Command::Progress { user: Some(name.clone()) }),
+            [flag] if flag == "--user" => Err(String::from("`--user` needs a name")),
+            [extra, ..] => Err(format!("unexpected argument `{}`", extra)),
+        },
+        "exercise" | "exercises" => parse_exercise(rest),
+        "export" => parse_export(rest),
+        "serve" => match rest {
+            [] => Ok(Command::Serve { port: DEFAULT_PORT }),
+            [flag, port] if flag == "--port" => match port.parse() {
+                Ok(port) => Ok(Command::Serve { port }),
+                Err(_) => Err(format!("`{}` is not a port number", port)),
+            },
+            [flag] if flag == "--port" => Err(String::from("`--port` needs a number")),
+            [extra, ..] => Err(format!("unexpected argument `{}` for `serve`", extra)),
+        },
+        "reset" => parse_reset(rest),
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        other => Err(format!("unknown command `{}`", other)),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<Command, String> {
+    let mut topics = Vec::new();
+    let mut chapter = None;
+    let mut all = false;
+    let mut options = RunOptions::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if parse_shared_option(arg, &mut args, &mut options)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--all" => all = true,
+            "--skip-interactive" => options.skip_interactive = true,
+            "--chapter" => {
+                let name = args.next().ok_or("`--chapter` needs a chapter name")?;
+                let found = Chapter::from_name(name).ok_or_else(|| unknown_chapter(name))?;
+                chapter = Some(found);
+            }
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{}` for `run`", flag)),
+            topic => topics.push(topic.to_string()),
+        }
+    }
+
+    // Exactly one way of choosing lessons must be used.
+    let selection = match (topics.is_empty(), chapter, all) {
+        (false, None, false) => RunSelection::Topics(topics),
+        (true, Some(chapter), false) => RunSelection::Chapter(chapter),
+        (true, None, true) => RunSelection::All,
+        (true, None, false) => return Err(String::from("`run` needs a topic, `--chapter <chapter>` or `--all`")),
+        _ => return Err(String::from("use only one of: topics, `--chapter` or `--all`")),
+    };
+    Ok(Command::Run(selection, options))
+}
+
+fn parse_quiz(args: &[String]) -> Result<Command, String> {
+    let mut topic = None;
+    let mut options = RunOptions::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if parse_shared_option(arg, &mut args, &mut options)? {
+            continue;
+        }
+        match arg.as_str() {
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{}` for `quiz`", flag)),
+            name if topic.is_none() => topic = Some(name.to_string()),
+            extra => return Err(format!("`quiz` takes a single topic, found `{}` as well", extra)),
+        }
+    }
+    Ok(Command::Quiz(topic, options))
+}
+
+// Handles the options that `run` and `quiz` have in common (--input, --answers and --user).
+// Returns false if `arg` is not one of them.
+fn parse_shared_option(arg: &str, args: &mut std::slice::Iter<String>, options: &mut RunOptions) -> Result<bool, String> {
+    match arg {
+        "--input" => {
+            let text = args.next().ok_or("`--input` needs the answers, e.g. \"Alice\\n30\\n\"")?;
+            options.input = InputSource::from_text(text);
+        }
+        "--answers" => {
+            let path = args.next().ok_or("`--answers` needs a file name")?;
+            options.input = InputSource::File(PathBuf::from(path));
+        }
+        "--user" => options.user = Some(args.next().ok_or("`--user` needs a name")?.clone()),
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn parse_exercise(args: &[String]) -> Result<Command, String> {
+    let (check, args) = match args.split_first() {
+        Some((action, rest)) if action == "check" => (true, rest),
+        Some((action, rest)) if action == "list" => (false, rest),
+        _ => (false, args),
+    };
+
+    let mut names = Vec::new();
+    let mut options = ExerciseOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dir" => options.dir = Some(PathBuf::from(args.next().ok_or("`--dir` needs a directory")?)),
+            "--user" => options.user = Some(args.next().ok_or("`--user` needs a name")?.clone()),
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{}` for `exercise`", flag)),
+            name if check => names.push(name.to_string()),
+            other => return Err(format!("unexpected argument `{}` (did you mean `exercise check {}`?)", other, other)),
+        }
+    }
+    Ok(Command::Exercise(if check { ExerciseCommand::Check(names, options) } else { ExerciseCommand::List(options) }))
+}
+
+fn parse_export(args: &[String]) -> Result<Command, String> {
+    let mut topics = Vec::new();
+    let mut options = ExportOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let name = args.next().ok_or("`--format` needs a format")?;
+                options.format = Format::from_name(name)
+                    .ok_or_else(|| format!("unknown format `{}` (use {})", name, Format::NAMES))?;
+            }
+            "--out" => options.out = PathBuf::from(args.next().ok_or("`--out` needs a directory")?),
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{}` for `export`", flag)),
+            topic => topics.push(topic.to_string()),
+        }
+    }
+    Ok(Command::Export(topics, options))
+}
+
+fn parse_reset(args: &[String]) -> Result<Command, String> {
+    let mut options = ResetOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--user" => options.user = Some(args.next().ok_or("`--user` needs a name")?.clone()),
+            "--all" => options.all = true,
+            "--yes" | "-y" => options.yes = true,
+            other => return Err(format!("unexpected argument `{}` for `reset`", other)),
+        }
+    }
+    if options.all && options.user.is_some() {
+        return Err(String::from("use either `--user <name>` or `--all`, not both"));
+    }
+    Ok(Command::Reset(options))
+}
+
+fn expect_no_more(args: &[String]) -> Result<(), String> {
+    match args.first() {
+        Some(extra) => Err(format!("unexpected argument `{}`", extra)),
+        None => Ok(()),
+    }
+}
+
+fn unknown_chapter(name: &str) -> String {
+    let known: Vec<&str> = Chapter::ALL.iter().map(|chapter| chapter.slug()).collect();
+    format!("unknown chapter `{}` (known chapters: {})", name, known.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        parse_args(&args)
+    }
+
+    #[test]
+    fn test_no_arguments_shows_help() {
+        assert_eq!(parse(&[]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn test_list_and_search() {
+        assert_eq!(parse(&["list"]), Ok(Command::List));
+        assert_eq!(parse(&["search", "borrow"]), Ok(Command::Search(String::from("borrow"))));
+        assert!(parse(&["search"]).is_err());
+        assert!(parse(&["list", "extra"]).is_err());
+    }
+
+    #[test]
+    fn test_run_topics() {
+        assert_eq!(
+            parse(&["run", "loops", "char_type"]),
+            Ok(Command::Run(
+                RunSelection::Topics(vec![String::from("loops"), String::from("char_type")]),
+                RunOptions::default()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_run_chapter() {
+        assert_eq!(
+            parse(&["run", "--chapter", "ownership"]),
+            Ok(Command::Run(RunSelection::Chapter(Chapter::OwnershipAndBorrowing), RunOptions::default()))
+        );
+        assert!(parse(&["run", "--chapter", "closures"]).is_err());
+        assert!(parse(&["run", "--chapter"]).is_err());
+    }
+
+    #[test]
+    fn test_run_all_skip_interactive() {
+        assert_eq!(
+            parse(&["run", "--all", "--skip-interactive"]),
+            Ok(Command::Run(RunSelection::All, RunOptions { skip_interactive: true, ..RunOptions::default() }))
+        );
+    }
+
+    #[test]
+    fn test_run_with_scripted_input() {
+        let expected_input = InputSource::Text(String::from("Alice\n30\n"));
+        assert_eq!(
+            parse(&["run", "user_input", "--input", "Alice\\n30\\n"]),
+            Ok(Command::Run(
+                RunSelection::Topics(vec![String::from("user_input")]),
+                RunOptions { input: expected_input, ..RunOptions::default() }
+            ))
+        );
+        match parse(&["run", "--all", "--answers", "answers.txt"]) {
+            Ok(Command::Run(_, options)) => assert_eq!(options.input, InputSource::File(PathBuf::from("answers.txt"))),
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+        assert!(parse(&["run", "user_input", "--input"]).is_err());
+    }
+
+    #[test]
+    fn test_run_for_a_named_learner() {
+        match parse(&["run", "loops", "--user", "alice"]) {
+            Ok(Command::Run(_, options)) => assert_eq!(options.user.as_deref(), Some("alice")),
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_quiz() {
+        assert_eq!(parse(&["quiz"]), Ok(Command::Quiz(None, RunOptions::default())));
+        match parse(&["quiz", "string_ownership", "--input", "1\\nno\\n", "--user", "alice"]) {
+            Ok(Command::Quiz(Some(topic), options)) => {
+                assert_eq!(topic, "string_ownership");
+                assert_eq!(options.input, InputSource::Text(String::from("1\nno\n")));
+                assert_eq!(options.user.as_deref(), Some("alice"));
+            }
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+        assert!(parse(&["quiz", "loops", "control_flow"]).is_err());
+        assert!(parse(&["quiz", "loops", "--skip-interactive"]).is_err());
+    }
+
+    #[test]
+    fn test_exercise() {
+        let list = Command::Exercise(ExerciseCommand::List(ExerciseOptions::default()));
+        assert_eq!(parse(&["exercise"]), Ok(list));
+        assert_eq!(
+            parse(&["exercise", "check", "calculate_length", "--dir", "mine", "--user", "bob"]),
+            Ok(Command::Exercise(ExerciseCommand::Check(
+                vec![String::from("calculate_length")],
+                ExerciseOptions { dir: Some(PathBuf::from("mine")), user: Some(String::from("bob")) }
+            )))
+        );
+        assert_eq!(
+            parse(&["exercise", "check"]),
+            Ok(Command::Exercise(ExerciseCommand::Check(Vec::new(), ExerciseOptions::default())))
+        );
+        assert!(parse(&["exercise", "calculate_length"]).is_err());
+        assert!(parse(&["exercise", "check", "--dir"]).is_err());
+    }
+
+    #[test]
+    fn test_export() {
+        assert_eq!(parse(&["export"]), Ok(Command::Export(Vec::new(), ExportOptions::default())));
+        assert_eq!(
+            parse(&["export", "loops", "--format", "mdbook", "--out", "book"]),
+            Ok(Command::Export(
+                vec![String::from("loops")],
+                ExportOptions { format: Format::MdBook, out: PathBuf::from("book") }
+            ))
+        );
+        assert_eq!(
+            parse(&["export", "--format", "pdf"]),
+            Err(String::from("unknown format `pdf` (use markdown, html or mdbook)"))
+        );
+    }
+
+    #[test]
+    fn test_serve() {
+        assert_eq!(parse(&["serve"]), Ok(Command::Serve { port: 8000 }));
+        assert_eq!(parse(&["serve", "--port", "0"]), Ok(Command::Serve { port: 0 }));
+        assert!(parse(&["serve", "--port", "http"]).is_err());
+        assert!(parse(&["serve", "--port", "70000"]).is_err());
+    }
+
+    #[test]
+    fn test_progress_and_reset() {
+        assert_eq!(parse(&["progress"]), Ok(Command::Progress { user: None }));
+        assert_eq!(parse(&["progress", "--user", "bob"]), Ok(Command::Progress { user: Some(String::from("bob")) }));
+        assert_eq!(
+            parse(&["reset", "--user", "bob", "--yes"]),
+            Ok(Command::Reset(ResetOptions { user: Some(String::from("bob")), all: false, yes: true }))
+        );
+        assert_eq!(parse(&["reset", "--all"]), Ok(Command::Reset(ResetOptions { user: None, all: true, yes: false })));
+        assert!(parse(&["reset", "--all", "--user", "bob"]).is_err());
+        assert!(parse(&["progress", "bob"]).is_err());
+    }
+
+    #[test]
+    fn test_run_rejects_missing_or_mixed_selection() {
+        assert!(parse(&["run"]).is_err());
+        assert!(parse(&["run", "--all", "loops"]).is_err());
+        assert!(parse(&["run", "--verbose"]).is_err());
+        assert!(parse(&["frobnicate"]).is_err());
+    }
+}
@@ -1,37 +1,34 @@
 // Module: Control Flow
 // This module explains `if/else if/else` expressions and `match` statements in Rust.
 
-pub fn demonstrate_control_flow() {
-    println!("\n--- Control Flow: if/else and match ---");
+use crate::lesson::{Chapter, Lesson};
+use std::io::{self, BufRead, Write};
+
+pub fn demonstrate_control_flow(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n--- Control Flow: if/else and match ---")?;
 
     // --- if/else if/else expressions ---
     // `if` expressions allow you to branch your code depending on conditions.
     // Conditions must be of type `bool`.
 
     let number: u8 = 10;
-    println!("Demonstrating if/else with number = {}", number);
+    writeln!(out, "Demonstrating if/else with number = {}", number)?;
 
     if number < 5 {
-        println!("Condition: number < 5 is true. Number is small.");
+        writeln!(out, "Condition: number < 5 is true. Number is small.")?;
     } else if number > 20 {
-        println!("Condition: number > 20 is true. Number is large.");
+        writeln!(out, "Condition: number > 20 is true. Number is large.")?;
     } else if number == 10 {
-        println!("Condition: number == 10 is true. Number is exactly 10!");
+        writeln!(out, "Condition: number == 10 is true. Number is exactly 10!")?;
     } else {
         // This `else` block would catch numbers >= 5, <= 20, and not equal to 10.
-        println!("Condition: number is between 5 and 20 (inclusive), but not 10.");
+        writeln!(out, "Condition: number is between 5 and 20 (inclusive), but not 10.")?;
     }
 
-    // `if` is an expression, meaning it can return a value.
-    // All blocks in an `if/else` expression must return the same type.
+    // `if` is an expression, meaning it can return a value (see `describe_condition` below).
     let condition = true;
-    let result_from_if = if condition {
-        "Condition was true" // This is a &str
-    } else {
-        "Condition was false" // This is also a &str
-        // 0 // This would cause a compile error: `if` and `else` have incompatible types
-    };
-    println!("Result from if expression: {}", result_from_if);
+    let result_from_if = describe_condition(condition);
+    writeln!(out, "Result from if expression: {}", result_from_if)?;
 
     // --- match statements ---
     // `match` allows you to compare a value against a series of patterns and execute code
@@ -39,45 +36,40 @@ pub fn demonstrate_control_flow() {
     // `match` is exhaustive: you must cover every possible case for the value being matched.
 
     let value_to_match: u8 = 3;
-    println!("\nDemonstrating match with value_to_match = {}", value_to_match);
+    writeln!(out, "\nDemonstrating match with value_to_match = {}", value_to_match)?;
 
     match value_to_match {
-        0 => println!("Match: value is Zero"),
-        1 => println!("Match: value is One"),
+        0 => writeln!(out, "Match: value is Zero")?,
+        1 => writeln!(out, "Match: value is One")?,
         2 | 3 => { // Multiple patterns can be combined with `|`
-            println!("Match: value is Two or Three");
+            writeln!(out, "Match: value is Two or Three")?;
             // Match arms can be blocks of code
         }
         4..=6 => { // `a..=b` is an inclusive range pattern (matches 4, 5, or 6)
-            println!("Match: value is between Four and Six (inclusive)");
+            writeln!(out, "Match: value is between Four and Six (inclusive)")?;
         }
-        _ => println!("Match: value is something else (default case using wildcard `_`)"),
+        _ => writeln!(out, "Match: value is something else (default case using wildcard `_`)")?,
     } // The `_` (underscore) is a wildcard pattern that matches any value and is often used for default cases.
 
-    // `match` is also an expression and can return a value.
+    // `match` is also an expression and can return a value (see `classify_food` below).
     let food = "apple";
-    let fruit_type = match food {
-        "apple" => "It's a crunchy fruit!",
-        "banana" => "It's a soft fruit!",
-        "orange" | "lemon" => "It's a citrus fruit!",
-        _ => "It's some other kind of food.",
-    };
-    println!("Matching food '{}': {}", food, fruit_type);
+    let fruit_type = classify_food(food);
+    writeln!(out, "Matching food '{}': {}", food, fruit_type)?;
 
     // Matching Option<T>
     let some_number: Option<i32> = Some(5);
     let no_number: Option<i32> = None;
 
-    println!("Matching an Option<i32> (Some(5)):");
+    writeln!(out, "Matching an Option<i32> (Some(5)):")?;
     match some_number {
-        Some(n) => println!("Got a number: {}", n),
-        None => println!("Got nothing!"),
+        Some(n) => writeln!(out, "Got a number: {}", n)?,
+        None => writeln!(out, "Got nothing!")?,
     }
 
-    println!("Matching an Option<i32> (None):");
+    writeln!(out, "Matching an Option<i32> (None):")?;
     match no_number {
-        Some(n) => println!("Got a number: {}", n), // This arm won't be hit
-        None => println!("Got nothing! (This is expected for no_number)"),
+        Some(n) => writeln!(out, "Got a number: {}", n)?, // This arm won't be hit
+        None => writeln!(out, "Got nothing! (This is expected for no_number)")?,
     }
 
     // Original prac12.rs:
@@ -107,6 +99,56 @@ pub fn demonstrate_control_flow() {
     //     }
     // }
     // The refactored module expands on these concepts.
+    Ok(())
+}
+
+// The `if/else` expression is the last expression in this function, so its value is returned.
+// All blocks in an `if/else` expression must return the same type.
+fn describe_condition(condition: bool) -> &'static str {
+    if condition {
+        "Condition was true" // This is a &str
+    } else {
+        "Condition was false" // This is also a &str
+        // 0 // This would cause a compile error: `if` and `else` have incompatible types
+    }
+}
+
+// A `match` on a string slice; each arm produces a value of the same type.
+fn classify_food(food: &str) -> &'static str {
+    match food {
+        "apple" => "It's a crunchy fruit!",
+        "banana" => "It's a soft fruit!",
+        "orange" | "lemon" => "It's a citrus fruit!",
+        _ => "It's some other kind of food.",
+    }
+}
+
+pub struct ControlFlowLesson;
+
+impl Lesson for ControlFlowLesson {
+    fn id(&self) -> &'static str {
+        "control_flow"
+    }
+
+    fn title(&self) -> &'static str {
+        "Control Flow: if/else and match"
+    }
+
+    fn chapter(&self) -> Chapter {
+        Chapter::ControlFlow
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["boolean_type"]
+    }
+
+    fn run(&self, _input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        demonstrate_control_flow(out)
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("control_flow.rs")
+    }
 }
 
 #[cfg(test)]
@@ -115,84 +157,49 @@ mod tests {
 
     #[test]
     fn test_if_expression_true_case() {
-        // Recreate the logic for the if expression that returns a value
-        let condition = true;
-        let result = if condition {
-            "Condition was true"
-        } else {
-            "Condition was false"
-        };
-        assert_eq!(result, "Condition was true");
+        assert_eq!(describe_condition(true), "Condition was true");
     }
 
     #[test]
     fn test_if_expression_false_case() {
-        let condition = false;
-        let result = if condition {
-            "Condition was true"
-        } else {
-            "Condition was false"
-        };
-        assert_eq!(result, "Condition was false");
+        assert_eq!(describe_condition(false), "Condition was false");
     }
 
     #[test]
     fn test_match_food_apple() {
-        // Recreate or call a helper for the match logic if it were more complex.
-        // For this direct example:
-        let food = "apple";
-        let fruit_type = match food {
-            "apple" => "It's a crunchy fruit!",
-            "banana" => "It's a soft fruit!",
-            "orange" | "lemon" => "It's a citrus fruit!",
-            _ => "It's some other kind of food.",
-        };
-        assert_eq!(fruit_type, "It's a crunchy fruit!");
+        assert_eq!(classify_food("apple"), "It's a crunchy fruit!");
     }
 
     #[test]
     fn test_match_food_banana() {
-        let food = "banana";
-        let fruit_type = match food {
-            "apple" => "It's a crunchy fruit!",
-            "banana" => "It's a soft fruit!",
-            "orange" | "lemon" => "It's a citrus fruit!",
-            _ => "It's some other kind of food.",
-        };
-        assert_eq!(fruit_type, "It's a soft fruit!");
+        assert_eq!(classify_food("banana"), "It's a soft fruit!");
+    }
+
+    #[test]
+    fn test_match_food_citrus() {
+        assert_eq!(classify_food("orange"), "It's a citrus fruit!");
+        assert_eq!(classify_food("lemon"), "It's a citrus fruit!");
     }
 
     #[test]
     fn test_match_food_other() {
-        let food = "carrot";
-        let fruit_type = match food {
-            "apple" => "It's a crunchy fruit!",
-            "banana" => "It's a soft fruit!",
-            "orange" | "lemon" => "It's a citrus fruit!",
-            _ => "It's some other kind of food.",
-        };
-        assert_eq!(fruit_type, "It's some other kind of food.");
+        assert_eq!(classify_food("carrot"), "It's some other kind of food.");
+    }
+
+    // The Option matches print directly, so these tests capture the lesson's output.
+    fn lesson_output() -> String {
+        let mut output: Vec<u8> = Vec::new();
+        demonstrate_control_flow(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
     }
 
     #[test]
     fn test_match_option_some() {
-        let some_number: Option<i32> = Some(5);
-        // Assign the result of the match expression directly to description
-        let description = match some_number {
-            Some(n) => format!("Got a number: {}", n),
-            None => String::from("Got nothing!"),
-        };
-        assert_eq!(description, "Got a number: 5");
+        assert!(lesson_output().contains("Matching an Option<i32> (Some(5)):\nGot a number: 5\n"));
     }
 
     #[test]
     fn test_match_option_none() {
-        let no_number: Option<i32> = None;
-        // Assign the result of the match expression directly to description
-        let description = match no_number {
-            Some(n) => format!("Got a number: {}", n),
-            None => String::from("Got nothing! (This is expected for no_number)"),
-        };
-        assert_eq!(description, "Got nothing! (This is expected for no_number)");
+        assert!(lesson_output().contains("Matching an Option<i32> (None):\nGot nothing! (This is expected for no_number)\n"));
     }
 }
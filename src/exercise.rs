@@ -0,0 +1,282 @@
+// Module: Exercises
+// Hands-on exercises: small Rust files in the `exercises/` directory that the learner edits
+// until they pass. Each exercise comes with hidden tests (in `exercises/hidden_tests/`,
+// built into the program) that are appended to the learner's file before it is compiled
+// with the local `rustc` (see toolchain.rs).
+
+use crate::toolchain::{self, RunOutput};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// How long compiling, and then running the tests, may take before giving up.
+const COMPILE_LIMIT: Duration = Duration::from_secs(60);
+const TEST_LIMIT: Duration = Duration::from_secs(10);
+
+pub struct Exercise {
+    // Also the file name: `exercises/<name>.rs`.
+    pub name: &'static str,
+    // The lesson that explains what is needed to solve it.
+    pub lesson: &'static str,
+    pub summary: &'static str,
+    pub hidden_tests: &'static str,
+}
+
+static EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "calculate_length",
+        lesson: "references_borrowing",
+        summary: "Implement calculate_length without taking ownership",
+        hidden_tests: include_str!("../exercises/hidden_tests/calculate_length.rs"),
+    },
+    Exercise {
+        name: "fix_borrow_error",
+        lesson: "more_borrowing_rules",
+        summary: "Fix a mutable borrow that overlaps an immutable one",
+        hidden_tests: include_str!("../exercises/hidden_tests/fix_borrow_error.rs"),
+    },
+    Exercise {
+        name: "add_without_overflow",
+        lesson: "original_main",
+        summary: "Make add_original_main return None instead of overflowing",
+        hidden_tests: include_str!("../exercises/hidden_tests/add_without_overflow.rs"),
+    },
+];
+
+pub fn all() -> &'static [Exercise] {
+    EXERCISES
+}
+
+pub fn find(name: &str) -> Option<&'static Exercise> {
+    EXERCISES.iter().find(|exercise| exercise.name == name)
+}
+
+// The result of checking one exercise.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    // Every hidden test passed; holds the number of tests.
+    Passed(usize),
+    // The learner's file could not be read.
+    Missing(String),
+    // `rustc` rejected the code; holds its diagnostics.
+    CompileError(String),
+    // The code compiled but some tests failed; holds the test output.
+    TestsFailed(String),
+    // `rustc` was still compiling after `COMPILE_LIMIT`.
+    CompileTimedOut,
+    // The tests were still running after `TEST_LIMIT`.
+    TestTimedOut,
+}
+
+impl Verdict {
+    pub fn passed(&self) -> bool {
+        matches!(self, Verdict::Passed(_))
+    }
+}
+
+// The `exercises/` directory: the one in the current directory if there is one,
+// otherwise the one in the source tree the program was built from.
+pub fn default_dir() -> PathBuf {
+    let local = PathBuf::from("exercises");
+    if local.is_dir() {
+        local
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("exercises")
+    }
+}
+
+impl Exercise {
+    pub fn path_in(&self, dir: &Path) -> PathBuf {
+        dir.join(format!("{}.rs", self.name))
+    }
+
+    // Compiles the learner's file from `dir` together with the hidden tests and runs them.
+    // Temporary files go into `work_dir`.
+    pub fn check(&self, dir: &Path, work_dir: &Path) -> io::Result<Verdict> {
+        let path = self.path_in(dir);
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(error) => return Ok(Verdict::Missing(format!("cannot read {}: {}", path.display(), error))),
+        };
+
+        fs::create_dir_all(work_dir)?;
+        let combined = work_dir.join(format!("{}.rs", self.name));
+        let binary = work_dir.join(format!("{}_tests", self.name));
+        fs::write(&combined, with_hidden_tests(&source, self.hidden_tests))?;
+
+        let compiled = toolchain::compile_tests(&combined, &binary, COMPILE_LIMIT)?;
+        if compiled.timed_out {
+            return Ok(Verdict::CompileTimedOut);
+        }
+        if !compiled.success {
+            // Point the diagnostics at the learner's file instead of our temporary copy.
+            // The learner's code comes first, so the line numbers still match.
+            let diagnostics = compiled.stderr.replace(&combined.display().to_string(), &path.display().to_string());
+            return Ok(Verdict::CompileError(diagnostics));
+        }
+
+        let tested = toolchain::run_with_timeout(&mut std::process::Command::new(&binary), TEST_LIMIT)?;
+        Ok(verdict_from_tests(&tested))
+    }
+}
+
+// The learner's code followed by the hidden tests, in a test module that can see everything.
+fn with_hidden_tests(source: &str, hidden_tests: &str) -> String {
+    format!(
+        "{}\n\n#[cfg(test)]\n#[allow(unused_imports)]\nmod hidden_tests {{\n    use super::*;\n\n{}}}\n",
+        source.trim_end(),
+        hidden_tests
+    )
+}
+
+fn verdict_from_tests(output: &RunOutput) -> Verdict {
+    if output.timed_out {
+        Verdict::TestTimedOut
+    } else if output.success {
+        Verdict::Passed(passed_count(&output.stdout))
+    } else {
+        Verdict::TestsFailed(format!("{}{}", output.stdout, output.stderr))
+    }
+}
+
+// Reads N from the "test result: ok. N passed; ..." line printed by the test harness.
+fn passed_count(test_output: &str) -> usize {
+    test_output
+        .lines()
+        .filter_map(|line| line.strip_prefix("test result: "))
+        .filter_map(|rest| rest.split("; ").next())
+        .filter_map(|first| first.rsplit(". ").next())
+        .filter_map(|count| count.trim_end_matches(" passed").parse::<usize>().ok())
+        .sum()
+}
+
+// Prints the outcome of one exercise the way the `exercise check` command shows it.
+pub fn write_verdict(exercise: &Exercise, verdict: &Verdict, out: &mut dyn Write) -> io::Result<()> {
+    match verdict {
+        Verdict::Passed(tests) => {
+            writeln!(out, "PASS  {} ({} test{} passed)", exercise.name, tests, if *tests == 1 { "" } else { "s" })
+        }
+        Verdict::Missing(problem) => writeln!(out, "FAIL  {}: {}", exercise.name, problem),
+        Verdict::CompileError(diagnostics) => {
+            writeln!(out, "FAIL  {}: the code does not compile\n", exercise.name)?;
+            writeln!(out, "{}", diagnostics.trim_end())
+        }
+        Verdict::TestsFailed(test_output) => {
+            writeln!(out, "FAIL  {}: some tests failed\n", exercise.name)?;
+            writeln!(out, "{}", test_output.trim_end())
+        }
+        Verdict::CompileTimedOut => writeln!(
+            out,
+            "FAIL  {}: the compiler gave up after {} seconds",
+            exercise.name,
+            COMPILE_LIMIT.as_secs()
+        ),
+        Verdict::TestTimedOut => writeln!(
+            out,
+            "FAIL  {}: the tests gave up after {} seconds (is there an endless loop?)",
+            exercise.name,
+            TEST_LIMIT.as_secs()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use std::env;
+
+    fn work_dir(test: &str) -> PathBuf {
+        env::temp_dir().join(format!("rust_practice_exercise_test_{}_{}", test, std::process::id()))
+    }
+
+    // Checks `exercise` against `solution` (or the stub from `exercises/` when `None`).
+    fn check_with(name: &str, solution: Option<&str>) -> Verdict {
+        let exercise = find(name).unwrap();
+        let work = work_dir(&format!("{}_{}", name, if solution.is_some() { "solution" } else { "stub" }));
+        let dir = match solution {
+            Some(code) => {
+                let dir = work.join("solutions");
+                fs::create_dir_all(&dir).unwrap();
+                fs::write(exercise.path_in(&dir), code).unwrap();
+                dir
+            }
+            None => Path::new(env!("CARGO_MANIFEST_DIR")).join("exercises"),
+        };
+        let verdict = exercise.check(&dir, &work.join("build")).unwrap();
+        fs::remove_dir_all(&work).unwrap();
+        verdict
+    }
+
+    #[test]
+    fn test_every_exercise_has_a_stub_and_a_lesson() {
+        for exercise in all() {
+            assert!(exercise.path_in(&default_dir()).is_file(), "no stub for {}", exercise.name);
+            assert!(registry::find(exercise.lesson).is_some(), "unknown lesson {}", exercise.lesson);
+        }
+    }
+
+    #[test]
+    fn test_passed_count() {
+        let output = "running 2 tests\ntest a ... ok\ntest b ... ok\n\ntest result: ok. 2 passed; 0 failed; 0 ignored\n";
+        assert_eq!(passed_count(output), 2);
+        assert_eq!(passed_count(""), 0);
+    }
+
+    #[test]
+    fn test_stubs_do_not_pass_yet() {
+        match check_with("fix_borrow_error", None) {
+            Verdict::CompileError(diagnostics) => {
+                assert!(diagnostics.contains("E0502"), "{}", diagnostics);
+                assert!(diagnostics.contains("fix_borrow_error.rs:"), "{}", diagnostics);
+            }
+            other => panic!("unexpected verdict: {:?}", other),
+        }
+        match check_with("calculate_length", None) {
+            Verdict::TestsFailed(output) => assert!(output.contains("not yet implemented"), "{}", output),
+            other => panic!("unexpected verdict: {:?}", other),
+        }
+        match check_with("add_without_overflow", None) {
+            Verdict::TestsFailed(output) => assert!(output.contains("attempt to add with overflow"), "{}", output),
+            other => panic!("unexpected verdict: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_solutions_pass() {
+        let fixed_borrow = "pub fn before_and_after() -> String {\n    let mut s = String::from(\"hello\");\n    \
+                            let before = s.clone();\n    s.push_str(\", world\");\n    format!(\"{} / {}\", before, s)\n}\n";
+        assert_eq!(check_with("fix_borrow_error", Some(fixed_borrow)), Verdict::Passed(1));
+
+        let length = "pub fn calculate_length(s: &String) -> usize {\n    s.len()\n}\n";
+        assert_eq!(check_with("calculate_length", Some(length)), Verdict::Passed(2));
+
+        let checked = "pub fn add_original_main(x: u8, y: u8) -> Option<u8> {\n    x.checked_add(y)\n}\n";
+        assert_eq!(check_with("add_without_overflow", Some(checked)), Verdict::Passed(2));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let exercise = find("calculate_length").unwrap();
+        let verdict = exercise.check(Path::new("does/not/exist"), &work_dir("missing")).unwrap();
+        assert!(matches!(verdict, Verdict::Missing(_)));
+    }
+
+    #[test]
+    fn test_timeouts_say_which_step_was_slow() {
+        let exercise = find("calculate_length").unwrap();
+        let text = |verdict: Verdict| {
+            let mut out: Vec<u8> = Vec::new();
+            write_verdict(exercise, &verdict, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(text(Verdict::CompileTimedOut), "FAIL  calculate_length: the compiler gave up after 60 seconds\n");
+        assert_eq!(
+            text(Verdict::TestTimedOut),
+            "FAIL  calculate_length: the tests gave up after 10 seconds (is there an endless loop?)\n"
+        );
+        let slow_tests = RunOutput { timed_out: true, ..RunOutput::default() };
+        assert_eq!(verdict_from_tests(&slow_tests), Verdict::TestTimedOut);
+    }
+}
@@ -0,0 +1,507 @@
+// Module: Exporter
+// Turns the lessons into pages that can be published: Markdown files, standalone HTML
+// pages, or the `src/` tree of an mdBook. Each page is built from the lesson's own source
+// file (see `Lesson::source`): comments between items become prose, the items become code
+// blocks, and the lesson is run to capture the output it really prints.
+//
+// The parser is line based and only understands the way the lesson files are written:
+// a header comment, `use` lines, then functions separated by comments. The `Lesson`
+// impl, the tests and notes meant for Clippy are left out of the pages.
+
+use crate::lesson::{Chapter, Lesson};
+use crate::registry;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Markdown,
+    Html,
+    MdBook,
+}
+
+impl Format {
+    pub const NAMES: &'static str = "markdown, html or mdbook";
+
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name.to_lowercase().as_str() {
+            "markdown" | "md" => Some(Format::Markdown),
+            "html" => Some(Format::Html),
+            "mdbook" => Some(Format::MdBook),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    // Explanation taken from the comments, one string per paragraph.
+    Prose(String),
+    Code(String),
+}
+
+pub struct Page<'a> {
+    pub lesson: &'a dyn Lesson,
+    pub blocks: Vec<Block>,
+    // What the lesson printed when it was run.
+    pub output: String,
+}
+
+// Builds the page for one lesson, running it to capture its output.
+// Interactive lessons are answered with `Lesson::example_input`.
+pub fn page_for(lesson: &dyn Lesson) -> io::Result<Page<'_>> {
+    let mut output: Vec<u8> = Vec::new();
+    lesson.run(&mut lesson.example_input().as_bytes(), &mut output)?;
+    Ok(Page {
+        lesson,
+        blocks: parse_source(lesson.source()),
+        output: String::from_utf8_lossy(&output).trim_matches('\n').to_string(),
+    })
+}
+
+// Splits a lesson's source into prose and code blocks.
+pub fn parse_source(source: &str) -> Vec<Block> {
+    let lines = without_lint_notes(source);
+    let mut blocks = Vec::new();
+    // A blank line was seen since the last block was extended.
+    let mut gap = false;
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        index += 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            gap = true;
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix("//") {
+            let text = comment.strip_prefix(' ').unwrap_or(comment);
+            if text.starts_with("Module: ") {
+                continue;
+            }
+            if text.is_empty() {
+                gap = true;
+            } else if looks_like_code(text) {
+                push_line(&mut blocks, Block::Code(text.to_string()), gap);
+                gap = false;
+            } else {
+                push_line(&mut blocks, Block::Prose(text.to_string()), gap);
+                gap = false;
+            }
+            continue;
+        }
+        if trimmed.starts_with("use ") {
+            continue;
+        }
+
+        // Anything else starts an item (a function, a struct, an attribute and its item, ...),
+        // which runs until its braces are balanced again.
+        let start = index - 1;
+        let mut depth = brace_change(line);
+        let mut finished = depth == 0 && (line.contains('}') || trimmed.ends_with(';'));
+        while !finished && index < lines.len() {
+            let next = lines[index];
+            index += 1;
+            depth += brace_change(next);
+            finished = depth <= 0 && (next.contains('}') || next.trim_end().ends_with(';'));
+        }
+        let item = &lines[start..index];
+        if is_hidden(item) {
+            continue;
+        }
+        push_line(&mut blocks, Block::Code(item.join("\n")), gap);
+        gap = false;
+    }
+    blocks
+}
+
+// Appends a line to the last block if it is of the same kind, or starts a new block.
+// After a blank line, prose starts a new paragraph and code is separated by an empty line.
+fn push_line(blocks: &mut Vec<Block>, block: Block, gap: bool) {
+    match (blocks.last_mut(), block) {
+        (Some(Block::Prose(text)), Block::Prose(line)) if !gap => {
+            text.push('\n');
+            text.push_str(&line);
+        }
+        (Some(Block::Code(text)), Block::Code(line)) => {
+            text.push_str(if gap { "\n\n" } else { "\n" });
+            text.push_str(&line);
+        }
+        (_, block) => blocks.push(block),
+    }
+}
+
+// Drops `#[allow(clippy::...)]` lines, together with the one-line comment above them that
+// explains why the lint is allowed. Those notes are for maintainers, not learners.
+fn without_lint_notes(source: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in source.lines() {
+        if line.trim_start().starts_with("#[allow(clippy::") {
+            let has_own_note = line.contains("] //");
+            if !has_own_note && lines.last().is_some_and(|previous| previous.trim_start().starts_with("//")) {
+                lines.pop();
+            }
+            continue;
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// Commented-out code (such as the original `pracN.rs` programs) is shown as code.
+fn looks_like_code(text: &str) -> bool {
+    let text = text.trim_end();
+    text.starts_with("    ")
+        || text.starts_with("fn ")
+        || text.starts_with("let ")
+        || text.starts_with("//")
+        || text == "}"
+        || text.ends_with(';')
+        || text.ends_with('{')
+}
+
+// The `Lesson` impl and the tests are part of the program, not of the lesson.
+fn is_hidden(item: &[&str]) -> bool {
+    let first = item.first().map(|line| line.trim()).unwrap_or("");
+    first.starts_with("#[cfg(test)]")
+        || first.starts_with("impl Lesson for ")
+        || (first.starts_with("pub struct ") && first.ends_with("Lesson;"))
+}
+
+// How much a line changes the brace depth, ignoring braces inside strings,
+// character literals and `//` comments.
+fn brace_change(line: &str) -> i32 {
+    let mut change = 0;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => break,
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '\'' => {
+                // A character literal ('x' or an escape like '\n'), or else a lifetime ('a).
+                let mut ahead = chars.clone();
+                let literal_length = match ahead.next() {
+                    Some('\\') => {
+                        ahead.next();
+                        ahead.position(|c| c == '\'').map(|position| position + 3)
+                    }
+                    Some(_) if ahead.next() == Some('\'') => Some(2),
+                    _ => None,
+                };
+                for _ in 0..literal_length.unwrap_or(0) {
+                    chars.next();
+                }
+            }
+            '{' => change += 1,
+            '}' => change -= 1,
+            _ => {}
+        }
+    }
+    change
+}
+
+// Writes the pages for `lessons` into `dir`, plus an index (or the mdBook summary).
+// Returns the files that were written.
+pub fn export(lessons: &[&dyn Lesson], format: Format, dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let pages_dir = match format {
+        Format::MdBook => dir.join("src"),
+        Format::Markdown | Format::Html => dir.to_path_buf(),
+    };
+    fs::create_dir_all(&pages_dir)?;
+    let mut written = Vec::new();
+    let mut write = |path: PathBuf, text: String| -> io::Result<()> {
+        fs::write(&path, text)?;
+        written.push(path);
+        Ok(())
+    };
+
+    for lesson in lessons {
+        let page = page_for(*lesson)?;
+        match format {
+            Format::Markdown | Format::MdBook => write(pages_dir.join(format!("{}.md", lesson.id())), render_markdown(&page))?,
+            Format::Html => write(pages_dir.join(format!("{}.html", lesson.id())), render_html(&page))?,
+        }
+    }
+
+    match format {
+        Format::Markdown => write(dir.join("index.md"), markdown_index(lessons, "md"))?,
+        Format::Html => write(dir.join("index.html"), html_index(lessons))?,
+        Format::MdBook => {
+            write(dir.join("book.toml"), String::from("[book]\ntitle = \"Rust Practice\"\nsrc = \"src\"\n"))?;
+            write(pages_dir.join("introduction.md"), markdown_index(lessons, "md"))?;
+            write(pages_dir.join("SUMMARY.md"), mdbook_summary(lessons))?;
+        }
+    }
+    Ok(written)
+}
+
+// The chapters of the README outline with the given lessons in each. Chapters that have
+// lessons, but none of them selected, are left out; chapters without lessons get `None`.
+fn chapters_of<'a>(lessons: &[&'a dyn Lesson]) -> Vec<(Chapter, Option<Vec<&'a dyn Lesson>>)> {
+    let mut chapters = Vec::new();
+    for chapter in Chapter::ALL {
+        let selected: Vec<&dyn Lesson> = lessons.iter().copied().filter(|lesson| lesson.chapter() == chapter).collect();
+        if !selected.is_empty() {
+            chapters.push((chapter, Some(selected)));
+        } else if registry::by_chapter(chapter).next().is_none() {
+            chapters.push((chapter, None));
+        }
+    }
+    chapters
+}
+
+pub fn render_markdown(page: &Page) -> String {
+    let lesson = page.lesson;
+    let mut text = format!("# {}\n\n*Chapter {}*", lesson.title(), lesson.chapter());
+    if !lesson.prerequisites().is_empty() {
+        let links: Vec<String> = lesson.prerequisites().iter().map(|id| format!("[{}]({}.md)", id, id)).collect();
+        text.push_str(&format!(" · Study first: {}", links.join(", ")));
+    }
+    text.push_str("\n\n");
+
+    for block in &page.blocks {
+        match block {
+            Block::Prose(prose) => text.push_str(&format!("{}\n\n", prose)),
+            Block::Code(code) => text.push_str(&format!("```rust\n{}\n```\n\n", code)),
+        }
+    }
+    text.push_str(&format!("## Output\n\n```text\n{}\n```\n", page.output));
+    text
+}
+
+fn markdown_index(lessons: &[&dyn Lesson], extension: &str) -> String {
+    let mut text = String::from("# Rust Practice\n\nThe lessons, grouped by the chapters of the tutorial.\n");
+    for (chapter, selected) in chapters_of(lessons) {
+        text.push_str(&format!("\n## {}\n\n", chapter));
+        match selected {
+            Some(selected) => {
+                for lesson in selected {
+                    text.push_str(&format!("- [{}]({}.{})\n", lesson.title(), lesson.id(), extension));
+                }
+            }
+            None => text.push_str("*To be added.*\n"),
+        }
+    }
+    text
+}
+
+fn mdbook_summary(lessons: &[&dyn Lesson]) -> String {
+    let mut text = String::from("# Summary\n\n[Introduction](introduction.md)\n");
+    for (chapter, selected) in chapters_of(lessons) {
+        // mdBook does not allow a part without chapters, so empty chapters are left out here.
+        let Some(selected) = selected else { continue };
+        text.push_str(&format!("\n# {}\n\n", chapter));
+        for lesson in selected {
+            text.push_str(&format!("- [{}]({}.md)\n", lesson.title(), lesson.id()));
+        }
+    }
+    text
+}
+
+const HTML_STYLE: &str = "body { font-family: sans-serif; max-width: 50rem; margin: 2rem auto; padding: 0 1rem; line-height: 1.5; }
+pre { background: #f4f4f4; padding: 0.75rem; overflow-x: auto; }
+code { font-family: monospace; }
+.output { background: #1e1e1e; color: #e0e0e0; }";
+
+fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        HTML_STYLE,
+        body
+    )
+}
+
+pub fn render_html(page: &Page) -> String {
+    let lesson = page.lesson;
+    let mut body = String::from("<p><a href=\"index.html\">All lessons</a></p>\n");
+    body.push_str(&format!("<h1>{}</h1>\n", escape_html(lesson.title())));
+    body.push_str(&format!("<p><em>Chapter {}</em>", escape_html(&lesson.chapter().to_string())));
+    if !lesson.prerequisites().is_empty() {
+        let links: Vec<String> =
+            lesson.prerequisites().iter().map(|id| format!("<a href=\"{}.html\">{}</a>", id, id)).collect();
+        body.push_str(&format!(" · Study first: {}", links.join(", ")));
+    }
+    body.push_str("</p>\n");
+
+    for block in &page.blocks {
+        match block {
+            Block::Prose(prose) => body.push_str(&format!("<p>{}</p>\n", inline_code(&escape_html(prose)))),
+            Block::Code(code) => body.push_str(&format!("<pre><code class=\"language-rust\">{}</code></pre>\n", escape_html(code))),
+        }
+    }
+    body.push_str(&format!("<h2>Output</h2>\n<pre class=\"output\"><code>{}</code></pre>\n", escape_html(&page.output)));
+    html_document(&format!("{} - Rust Practice", lesson.title()), &body)
+}
+
+pub fn html_index(lessons: &[&dyn Lesson]) -> String {
+    let mut body = String::from("<h1>Rust Practice</h1>\n<p>The lessons, grouped by the chapters of the tutorial.</p>\n");
+    for (chapter, selected) in chapters_of(lessons) {
+        body.push_str(&format!("<h2>{}</h2>\n", escape_html(&chapter.to_string())));
+        match selected {
+            Some(selected) => {
+                body.push_str("<ul>\n");
+                for lesson in selected {
+                    body.push_str(&format!("<li><a href=\"{}.html\">{}</a></li>\n", lesson.id(), escape_html(lesson.title())));
+                }
+                body.push_str("</ul>\n");
+            }
+            None => body.push_str("<p><em>To be added.</em></p>\n"),
+        }
+    }
+    html_document("Rust Practice", &body)
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Turns `code` spans (already HTML-escaped) into <code> elements. An unmatched backtick is kept.
+fn inline_code(text: &str) -> String {
+    let parts: Vec<&str> = text.split('`').collect();
+    if parts.len().is_multiple_of(2) {
+        return text.to_string();
+    }
+    let mut html = String::new();
+    for (index, part) in parts.iter().enumerate() {
+        if index % 2 == 1 {
+            html.push_str(&format!("<code>{}</code>", part));
+        } else {
+            html.push_str(part);
+        }
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const SAMPLE: &str = "// Module: Sample
+// Explains a thing.
+//
+// Second paragraph.
+
+use std::io;
+
+// Says hello.
+// (Clippy prefers something else here.)
+#[allow(clippy::ptr_arg)]
+pub fn hello(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, \"{{ not a brace }}\")?; // '}' neither
+    let c = '{';
+    Ok(())
+}
+
+pub struct SampleLesson;
+
+impl Lesson for SampleLesson {
+    fn id(&self) -> &'static str {
+        \"sample\"
+    }
+}
+
+// Original prac1.rs:
+// fn main(){
+//     println!(\"hi\");
+// }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_it() {}
+}
+";
+
+    #[test]
+    fn test_parse_source() {
+        let blocks = parse_source(SAMPLE);
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Prose(String::from("Explains a thing.")),
+                Block::Prose(String::from("Second paragraph.")),
+                Block::Prose(String::from("Says hello.")),
+                Block::Code(String::from(
+                    "pub fn hello(out: &mut dyn Write) -> io::Result<()> {\n    writeln!(out, \"{{ not a brace }}\")?; // '}' neither\n    let c = '{';\n    Ok(())\n}"
+                )),
+                Block::Prose(String::from("Original prac1.rs:")),
+                Block::Code(String::from("fn main(){\n    println!(\"hi\");\n}")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_brace_change() {
+        assert_eq!(brace_change("fn main() {"), 1);
+        assert_eq!(brace_change("    writeln!(out, \"{}\", x)?; // }"), 0);
+        assert_eq!(brace_change("let c = '}'; let e = '\\''; fn f<'a>(x: &'a str) {"), 1);
+    }
+
+    #[test]
+    fn test_real_lesson_page() {
+        let page = page_for(registry::find("string_ownership").unwrap()).unwrap();
+        let code: Vec<&String> = page.blocks.iter().filter_map(|block| match block { Block::Code(code) => Some(code), _ => None }).collect();
+        assert!(code[0].starts_with("pub fn demonstrate_string_ownership"));
+        assert!(code.iter().all(|code| !code.contains("impl Lesson")));
+        assert!(page.blocks.contains(&Block::Prose(String::from(
+            "This module demonstrates Rust's ownership system using the String type."
+        ))));
+        assert!(page.output.starts_with("--- String Ownership ---"));
+
+        let markdown = render_markdown(&page);
+        assert!(markdown.starts_with("# String Ownership\n\n*Chapter 2. Ownership and Borrowing* · Study first: [basic_syntax](basic_syntax.md)\n"));
+        assert!(markdown.contains("```rust\npub fn demonstrate_string_ownership"));
+        assert!(markdown.ends_with("s3 (received from takes_and_gives_back): \"Return me\"\n```\n"));
+    }
+
+    #[test]
+    fn test_interactive_lesson_uses_example_input() {
+        let page = page_for(registry::find("user_input").unwrap()).unwrap();
+        assert!(page.output.contains("Hello, Alice! Nice to meet you."));
+    }
+
+    #[test]
+    fn test_html_escaping() {
+        assert_eq!(escape_html("<a href=\"x\">&</a>"), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+        assert_eq!(inline_code("use `mut` to change"), "use <code>mut</code> to change");
+        assert_eq!(inline_code("a stray ` stays"), "a stray ` stays");
+
+        let page = page_for(registry::find("control_flow").unwrap()).unwrap();
+        let html = render_html(&page);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Control Flow: if/else and match - Rust Practice</title>"));
+        assert!(html.contains("&lt;"));
+    }
+
+    #[test]
+    fn test_export_mdbook_tree() {
+        let dir = env::temp_dir().join(format!("rust_practice_export_test_{}", std::process::id()));
+        let lessons = registry::study_order();
+        let written = export(&lessons, Format::MdBook, &dir).unwrap();
+        assert_eq!(written.len(), lessons.len() + 3);
+
+        let summary = fs::read_to_string(dir.join("src").join("SUMMARY.md")).unwrap();
+        assert!(summary.contains("\n# 1. Getting Started\n\n- [Basic Syntax, Variables, Functions, and Shadowing](basic_syntax.md)\n"));
+        assert!(!summary.contains("Structs and Enums"));
+        let introduction = fs::read_to_string(dir.join("src").join("introduction.md")).unwrap();
+        assert!(introduction.contains("## 6. Structs and Enums\n\n*To be added.*\n"));
+        assert!(dir.join("book.toml").is_file());
+        assert!(dir.join("src").join("loops.md").is_file());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,8 +1,11 @@
 // Module: Floating-Point Types
 // This module introduces Rust's floating-point number types.
 
-pub fn demonstrate_floating_point_types() {
-    println!("\n--- Floating-Point Types ---");
+use crate::lesson::{Chapter, Lesson};
+use std::io::{self, BufRead, Write};
+
+pub fn demonstrate_floating_point_types(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n--- Floating-Point Types ---")?;
 
     // Rust has two primary floating-point types: f32 and f64.
     // f32 is a single-precision float.
@@ -10,26 +13,29 @@ pub fn demonstrate_floating_point_types() {
     // The default type is f64 because on modern CPUs, it's roughly the same speed as f32
     // but is capable of more precision.
 
+    // (Clippy suggests the `std::f32::consts` constants for these values; plain literals are clearer here.)
+    #[allow(clippy::approx_constant)]
     let x: f32 = 3.14; // Explicitly an f32
+    #[allow(clippy::approx_constant)]
     let y = 2.71828;   // Implicitly an f64 (default type for floating-point literals)
     let z: f64 = 1.618; // Explicitly an f64
 
-    println!("x (f32): {}", x);
-    println!("y (f64 default): {}", y);
-    println!("z (f64 explicit): {}", z);
+    writeln!(out, "x (f32): {}", x)?;
+    writeln!(out, "y (f64 default): {}", y)?;
+    writeln!(out, "z (f64 explicit): {}", z)?;
 
     // Floating-point numbers can be used in arithmetic operations.
     let sum_float = x + y as f32; // y (f64) needs to be cast to f32 for this operation
-    println!("Sum of x + (y as f32): {}", sum_float);
+    writeln!(out, "Sum of x + (y as f32): {}", sum_float)?;
 
     let product_float = y * z; // Both are f64, so no cast needed
-    println!("Product of y * z: {}", product_float);
+    writeln!(out, "Product of y * z: {}", product_float)?;
 
     // Floating point numbers are not always exact and can lead to precision issues.
     let a: f32 = 0.1;
     let b: f32 = 0.2;
     let sum_ab: f32 = a + b;
-    println!("0.1 (f32) + 0.2 (f32) = {} (watch for precision!)", sum_ab); // Might not be exactly 0.3
+    writeln!(out, "0.1 (f32) + 0.2 (f32) = {} (watch for precision!)", sum_ab)?; // Might not be exactly 0.3
 
     // For applications requiring high precision without rounding errors (like financial calculations),
     // consider using specialized crates like `rust_decimal`.
@@ -42,4 +48,33 @@ pub fn demonstrate_floating_point_types() {
     //     println!("x = {}, y = {}", x, y);
     // }
     // This refactored module expands on these concepts.
+    Ok(())
+}
+
+pub struct FloatingPointTypesLesson;
+
+impl Lesson for FloatingPointTypesLesson {
+    fn id(&self) -> &'static str {
+        "floating_point_types"
+    }
+
+    fn title(&self) -> &'static str {
+        "Floating-Point Types"
+    }
+
+    fn chapter(&self) -> Chapter {
+        Chapter::DataTypes
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["basic_syntax"]
+    }
+
+    fn run(&self, _input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        demonstrate_floating_point_types(out)
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("floating_point_types.rs")
+    }
 }
@@ -1,35 +1,400 @@
-use rand::thread_rng;
-use rand::seq::SliceRandom;
-use std::io;
-fn main(){
-    let mut rng = thread_rng();
-    let mut input = String::new();
-    println!("Welcome to the Guessing Game!");
-    let choices = ["rock", "paper", "scissors"];
-    let random_choice = match choices.choose(&mut rng) {
-        Some(choice) => {
-            println!("Computer has chose now it's your turn!");
-            choice
-        },
+// Module: Guess Game (rock-paper-scissors)
+// The game logic behind the `guess_game` binary (src/bin/guess_game.rs).
+// It reads the player's moves from any `BufRead` and picks the computer's moves with any
+// `Rng`, so whole matches can be played from tests as well as from the keyboard.
+// The moves and what beats what come from a rule set (guess_game/rules.rs), and the computer
+// picks its moves with one of the strategies in guess_game/strategy.rs. guess_game/tui.rs
+// plays the same game full-screen.
+//
+// A match is a series of rounds: "best of 5" ends as soon as one side has won 3 rounds,
+// "first to 3" is the same thing said differently. Tied rounds do not count towards either.
+
+pub mod cli;
+pub mod lan;
+pub mod number;
+pub mod replay;
+pub mod rules;
+pub mod stats;
+pub mod strategy;
+pub mod tournament;
+pub mod tui;
+
+use rand::Rng;
+use rules::RuleSet;
+use serde::{Deserialize, Serialize};
+use strategy::{Round, Strategy, StrategyKind};
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// The result of one round, seen from the player's side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Win,
+    Lose,
+    Tie,
+}
+
+impl Outcome {
+    pub fn message(self) -> &'static str {
+        match self {
+            Outcome::Win => "You win!",
+            Outcome::Lose => "You lose!",
+            Outcome::Tie => "It's a tie!",
+        }
+    }
+}
+
+// How long a match lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchFormat {
+    // The first side to win a majority of `n` rounds wins (`--best-of n`).
+    BestOf(u32),
+    // The first side to win `n` rounds wins (`--first-to n`).
+    FirstTo(u32),
+}
+
+impl Default for MatchFormat {
+    // A single decided round, like the original game.
+    fn default() -> MatchFormat {
+        MatchFormat::BestOf(1)
+    }
+}
+
+impl MatchFormat {
+    pub fn wins_needed(self) -> u32 {
+        match self {
+            MatchFormat::BestOf(rounds) => rounds / 2 + 1,
+            MatchFormat::FirstTo(wins) => wins,
+        }
+    }
+}
+
+impl fmt::Display for MatchFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MatchFormat::BestOf(rounds) => write!(f, "Best of {}", rounds),
+            MatchFormat::FirstTo(wins) => write!(f, "First to {}", wins),
+        }
+    }
+}
+
+// The running score of one match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Scoreboard {
+    pub wins: u32,
+    pub losses: u32,
+    pub ties: u32,
+}
+
+impl Scoreboard {
+    pub fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => self.wins += 1,
+            Outcome::Lose => self.losses += 1,
+            Outcome::Tie => self.ties += 1,
+        }
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.wins + self.losses + self.ties
+    }
+
+    // `Some(Win)` or `Some(Lose)` once one side has won enough rounds.
+    pub fn winner(&self, format: MatchFormat) -> Option<Outcome> {
+        let needed = format.wins_needed();
+        if self.wins >= needed {
+            Some(Outcome::Win)
+        } else if self.losses >= needed {
+            Some(Outcome::Lose)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Scoreboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Score: you {}, computer {}, ties {}", self.wins, self.losses, self.ties)
+    }
+}
+
+// Everything chosen before the game starts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Settings {
+    pub format: MatchFormat,
+    pub rules: RuleSet,
+    pub strategy: StrategyKind,
+}
+
+// Everything played in one call to `play`, for the statistics (see guess_game/stats.rs).
+#[derive(Debug, Default, PartialEq)]
+pub struct Session {
+    pub rounds: Vec<Round>,
+    // Matches won and lost; unfinished matches are not counted.
+    pub matches: Scoreboard,
+}
+
+// What happened when the player was asked for a move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Turn {
+    // The outcome and the player's move.
+    Played(Outcome, String),
+    // The answer was not one of the moves; the round is played again.
+    Invalid,
+    // The input ran out (end of file or of the scripted answers).
+    NoInput,
+}
+
+// Plays matches until the player declines a rematch or the input runs out.
+pub fn play<R: Rng>(input: &mut dyn BufRead, out: &mut dyn Write, rng: &mut R, settings: &Settings) -> io::Result<Session> {
+    writeln!(out, "Welcome to the Guessing Game!")?;
+    settings.rules.describe(out)?;
+    writeln!(out, "Computer strategy: {} ({}).", settings.strategy, settings.strategy.description())?;
+    // The computer keeps learning across rematches, so the history spans the whole session.
+    let mut strategy = settings.strategy.build();
+    let mut session = Session::default();
+    while let Some(outcome) = play_match(input, out, rng, settings, strategy.as_mut(), &mut session.rounds)? {
+        session.matches.record(outcome);
+        writeln!(out, "Play again? (y/n)")?;
+        if !wants_rematch(input)? {
+            break;
+        }
+    }
+    if session.matches.rounds() > 1 {
+        writeln!(out, "Matches won: you {}, computer {}.", session.matches.wins, session.matches.losses)?;
+    }
+    writeln!(out, "Thanks for playing!")?;
+    Ok(session)
+}
+
+// Plays one match and prints its summary. Returns who won it, or `None` if the input ran out first.
+// Every round played is added to `history`.
+pub fn play_match<R: Rng>(
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    rng: &mut R,
+    settings: &Settings,
+    strategy: &mut dyn Strategy,
+    history: &mut Vec<Round>,
+) -> io::Result<Option<Outcome>> {
+    let format = settings.format;
+    let needed = format.wins_needed();
+    writeln!(out, "{}: the first to win {} round{} wins the match.", format, needed, plural(needed))?;
+    let mut score = Scoreboard::default();
+    while score.winner(format).is_none() {
+        // The computer makes one choice per round and keeps it if the player has to answer
+        // again, so a seed always gives the same computer moves (see guess_game/replay.rs).
+        let computer_choice = strategy.choose(&settings.rules, history, rng);
+        writeln!(out, "Computer has chose now it's your turn!")?;
+        let mut turn = play_against(input, out, &settings.rules, &computer_choice)?;
+        while turn == Turn::Invalid {
+            turn = play_against(input, out, &settings.rules, &computer_choice)?;
+        }
+        match turn {
+            Turn::Played(outcome, player_choice) => {
+                history.push(Round { player: player_choice, computer: computer_choice });
+                score.record(outcome);
+                writeln!(out, "{}", score)?;
+            }
+            Turn::Invalid | Turn::NoInput => {
+                writeln!(out, "No more moves; the match is left unfinished.")?;
+                return Ok(None);
+            }
+        }
+    }
+    let winner = score.winner(format);
+    let verdict = if winner == Some(Outcome::Win) { "You won the match" } else { "The computer won the match" };
+    writeln!(
+        out,
+        "{} {}-{} ({} round{}, {} tie{}).",
+        verdict,
+        score.wins,
+        score.losses,
+        score.rounds(),
+        plural(score.rounds()),
+        score.ties,
+        plural(score.ties)
+    )?;
+    Ok(winner)
+}
+
+// The rest of a round, once the computer's move is known.
+pub fn play_against(
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    rules: &RuleSet,
+    computer_choice: &str,
+) -> io::Result<Turn> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(Turn::NoInput);
+    }
+    let user_choice = match rules.find_move(&line) {
+        Some(choice) => choice,
         None => {
-            println!("Failed to choose a valid option.");
-            return;
+            writeln!(out, "Invalid choice. Please choose {}.", rules.choice_list())?;
+            return Ok(Turn::Invalid);
         }
     };
-    io::stdin().read_line(&mut input).expect("Failed to read line");
-    let user_choice = input.trim().to_lowercase();
-    if choices.contains(&user_choice.as_str()) {
-        println!("You chose: {}", user_choice);
-        if user_choice == "rock" && *random_choice == "scissors" ||
-           user_choice == "paper" && *random_choice == "rock" ||
-           user_choice == "scissors" && *random_choice == "paper" {
-            println!("You win!");
-        } else if user_choice.as_str() == *random_choice {
-            println!("It's a tie!");
-        } else {
-            println!("You lose!");
+    writeln!(out, "You chose: {}", user_choice)?;
+    writeln!(out, "Computer chose: {}", computer_choice)?;
+    let outcome = rules.judge(user_choice, computer_choice);
+    writeln!(out, "{}", outcome.message())?;
+    Ok(Turn::Played(outcome, user_choice.to_string()))
+}
+
+fn wants_rematch(input: &mut dyn BufRead) -> io::Result<bool> {
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    Ok(matches!(line.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+pub(crate) fn plural(count: u32) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    fn round_against(computer: &str, answers: &str) -> (Turn, String) {
+        let mut output: Vec<u8> = Vec::new();
+        let turn = play_against(&mut answers.as_bytes(), &mut output, &RuleSet::classic(), computer).unwrap();
+        (turn, String::from_utf8(output).unwrap())
+    }
+
+    // A `StepRng` that always returns 0 makes `choose` pick the first move, "rock".
+    fn game(answers: &str, settings: &Settings) -> String {
+        let mut rng = StepRng::new(0, 0);
+        let mut output: Vec<u8> = Vec::new();
+        play(&mut answers.as_bytes(), &mut output, &mut rng, settings).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn settings(format: MatchFormat) -> Settings {
+        Settings { format, ..Settings::default() }
+    }
+
+    #[test]
+    fn test_judge_every_pair() {
+        let rules = RuleSet::classic();
+        for (index, player) in rules.moves.iter().enumerate() {
+            // Each move beats the one before it in the list and loses to the one after it.
+            let beaten = &rules.moves[(index + 2) % 3];
+            let winner = &rules.moves[(index + 1) % 3];
+            assert_eq!(rules.judge(player, player), Outcome::Tie);
+            assert_eq!(rules.judge(player, beaten), Outcome::Win);
+            assert_eq!(rules.judge(player, winner), Outcome::Lose);
         }
-    } else {
-        println!("Invalid choice. Please choose rock, paper, or scissors.");
+    }
+
+    #[test]
+    fn test_win_tie_and_lose() {
+        assert_eq!(
+            round_against("scissors", "rock\n"),
+            (
+                Turn::Played(Outcome::Win, String::from("rock")),
+                String::from("You chose: rock\nComputer chose: scissors\nYou win!\n")
+            )
+        );
+        assert_eq!(round_against("paper", "paper\n").0, Turn::Played(Outcome::Tie, String::from("paper")));
+        assert_eq!(round_against("scissors", "paper\n").0, Turn::Played(Outcome::Lose, String::from("paper")));
+    }
+
+    #[test]
+    fn test_choice_is_trimmed_and_case_insensitive() {
+        assert_eq!(round_against("rock", "  PAPER \n").0, Turn::Played(Outcome::Win, String::from("paper")));
+    }
+
+    #[test]
+    fn test_invalid_and_missing_choice() {
+        assert_eq!(
+            round_against("rock", "lizard\n"),
+            (Turn::Invalid, String::from("Invalid choice. Please choose rock, paper, or scissors.\n"))
+        );
+        assert_eq!(round_against("rock", ""), (Turn::NoInput, String::new()));
+    }
+
+    #[test]
+    fn test_format_wins_needed() {
+        assert_eq!(MatchFormat::default().wins_needed(), 1);
+        assert_eq!(MatchFormat::BestOf(5).wins_needed(), 3);
+        assert_eq!(MatchFormat::BestOf(4).wins_needed(), 3);
+        assert_eq!(MatchFormat::FirstTo(2).wins_needed(), 2);
+    }
+
+    #[test]
+    fn test_single_round_with_scripted_input() {
+        assert_eq!(
+            game("paper\nn\n", &Settings::default()),
+            "Welcome to the Guessing Game!\n\
+             Rules: Rock-Paper-Scissors\n  \
+               rock beats scissors\n  \
+               paper beats rock\n  \
+               scissors beats paper\n\
+             Computer strategy: random (picks every move with the same chance).\n\
+             Best of 1: the first to win 1 round wins the match.\n\
+             Computer has chose now it's your turn!\n\
+             You chose: paper\n\
+             Computer chose: rock\n\
+             You win!\n\
+             Score: you 1, computer 0, ties 0\n\
+             You won the match 1-0 (1 round, 0 ties).\n\
+             Play again? (y/n)\n\
+             Thanks for playing!\n"
+        );
+    }
+
+    #[test]
+    fn test_ties_and_invalid_moves_do_not_decide_a_match() {
+        let output = game("rock\nlizard\nscissors\npaper\npaper\n", &settings(MatchFormat::BestOf(3)));
+        assert!(output.contains("Score: you 0, computer 1, ties 1\n"));
+        assert!(output.contains("You won the match 2-1 (4 rounds, 1 tie).\n"));
+    }
+
+    #[test]
+    fn test_rematch_and_session_summary() {
+        let output = game("paper\nyes\nscissors\nn\n", &settings(MatchFormat::FirstTo(1)));
+        assert!(output.contains("You won the match 1-0"));
+        assert!(output.contains("The computer won the match 0-1"));
+        assert!(output.ends_with("Matches won: you 1, computer 1.\nThanks for playing!\n"));
+    }
+
+    #[test]
+    fn test_running_out_of_input_leaves_the_match_unfinished() {
+        let output = game("paper\n", &settings(MatchFormat::FirstTo(2)));
+        assert!(output.ends_with("No more moves; the match is left unfinished.\nThanks for playing!\n"));
+        assert!(!output.contains("Play again?"));
+    }
+
+    #[test]
+    fn test_match_with_another_rule_set() {
+        let settings = Settings { rules: rules::built_in("rpsls").unwrap(), ..Settings::default() };
+        let output = game("lizard\n", &settings);
+        assert!(output.contains("  spock beats scissors and rock\n"));
+        assert!(output.contains("You chose: lizard\nComputer chose: rock\nYou lose!\n"));
+    }
+
+    #[test]
+    fn test_strategy_learns_across_rematches() {
+        // Without history the frequency strategy plays at random (rock); after seeing paper it answers with scissors.
+        let settings = Settings { strategy: StrategyKind::Frequency, ..Settings::default() };
+        let output = game("paper\ny\npaper\nn\n", &settings);
+        assert!(output.contains("Computer strategy: frequency (counters your most frequent move).\n"));
+        assert!(output.contains("Computer chose: rock\nYou win!\n"));
+        assert!(output.contains("Computer chose: scissors\nYou lose!\n"));
+        assert!(output.ends_with("Matches won: you 1, computer 1.\nThanks for playing!\n"));
+    }
+
+    #[test]
+    fn test_session_collects_rounds_and_matches() {
+        let mut rng = StepRng::new(0, 0);
+        let session = play(&mut "paper\ny\nlizard\nscissors\n".as_bytes(), &mut io::sink(), &mut rng, &Settings::default()).unwrap();
+        assert_eq!(session.matches, Scoreboard { wins: 1, losses: 1, ties: 0 });
+        let moves: Vec<&str> = session.rounds.iter().map(|round| round.player.as_str()).collect();
+        assert_eq!(moves, ["paper", "scissors"]);
     }
 }
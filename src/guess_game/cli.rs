@@ -0,0 +1,491 @@
+// Module: guess_game Command Line
+// Parses the arguments of the `guess_game` binary into a `Command`, the same way cli.rs
+// does for the tutorial, so the parsing can be unit-tested.
+
+use super::number::Difficulty;
+use super::strategy::StrategyKind;
+use super::tournament::Config;
+use super::{lan, rules};
+use super::{MatchFormat, Settings};
+use crate::input::InputSource;
+use crate::rng::{self, RngKind};
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: guess_game [play] [options]      Play rock-paper-scissors against the computer
+       guess_game tui [options]         The same, full-screen: arrow keys or hotkeys pick the move
+       guess_game host [options]        Wait for a second player to join over the network
+       guess_game join <address>        Play against a host, e.g. `join 192.168.1.20` or `join localhost:7878`
+       guess_game replay <file>         Show a session saved with --log and check that it replays
+       guess_game number [options]      Guess the number the computer is thinking of (higher or lower)
+       guess_game tournament [options]  Let the computer strategies play each other and rank them
+       guess_game stats [<player>]      Show a player's statistics (without <player>: everyone's)
+       guess_game leaderboard           Rank the players by matches won
+       guess_game help                  Show this message
+
+Options for `play`:
+  --best-of <rounds>                    Play until one side has won most of <rounds> rounds
+  --first-to <wins>                     Play until one side has won <wins> rounds
+  --rules <classic|rpsls|file>          A built-in rule set or a rule file (see rules/)
+  --strategy <name>                     How the computer plays: random, frequency, markov or wsls
+  --seed <n>                            Replay the same computer moves (or set RUST_PRACTICE_SEED)
+  --rng <name>                          The random generator: std (default), lcg, xorshift64star,
+                                        pcg32, splitmix64 or xoshiro256starstar (see src/rng/)
+  --player <name>                       Record the statistics for <name> instead of asking
+  --log <file>                          Save the session as JSON Lines, for `replay`
+  --input <text>                        Read the answers from <text>, e.g. \"alice\\nrock\\n\"
+  --answers <file>                      Read the answers from the lines of <file>
+
+Options for `tui`: the options of `play`, except --input and --answers.
+Options for `host`: --port <port> (default 7878), --best-of, --first-to, --rules,
+  --player, --input and --answers.
+Options for `join`: --player, --input and --answers.
+Options for `tournament`: --games <n> (matches per pair, default 100), --best-of, --first-to
+  (default: best of 5), --rules, --seed, --rng and --csv <file> (also save the results as CSV).
+Options for `number`: --difficulty <easy|medium|hard> (1-50, 1-100 or 1-1000; default medium),
+  --hints (show the range that is left), --bot (watch a binary-search bot play), --seed,
+  --rng, --input and --answers.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Play(PlayOptions),
+    // Like `Play`, in the terminal UI; the input always comes from the keyboard.
+    Tui(PlayOptions),
+    // The port to listen on; of the options, only the format, rules, player and input are used.
+    Host(u16, PlayOptions),
+    // The host's address ("host:port"); the format and rules come from the host.
+    Join(String, PlayOptions),
+    Number(NumberOptions),
+    Tournament(TournamentOptions),
+    // A log saved with `--log`.
+    Replay(PathBuf),
+    // `None` shows every player.
+    Stats(Option<String>),
+    Leaderboard,
+    Help,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct PlayOptions {
+    pub settings: Settings,
+    pub source: InputSource,
+    // `None` leaves the choice to `rng::choose_seed`.
+    pub seed: Option<u64>,
+    pub rng: RngKind,
+    // `None` asks for the name before the game.
+    pub player: Option<String>,
+    pub log: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct NumberOptions {
+    pub difficulty: Difficulty,
+    pub hints: bool,
+    // The binary-search bot guesses instead of the player.
+    pub bot: bool,
+    pub source: InputSource,
+    pub seed: Option<u64>,
+    pub rng: RngKind,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct TournamentOptions {
+    pub config: Config,
+    pub seed: Option<u64>,
+    pub rng: RngKind,
+    pub csv: Option<PathBuf>,
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.split_first() {
+        None => Ok(Command::Play(PlayOptions::default())),
+        Some((command, rest)) if command == "play" => parse_play(rest),
+        Some((command, rest)) if command == "tui" => {
+            let parsed = parse_options("tui", rest, TUI_OPTIONS)?;
+            expect_no_positional("tui", &parsed.positional)?;
+            Ok(Command::Tui(parsed.options))
+        }
+        Some((command, rest)) if command == "host" => {
+            let parsed = parse_options("host", rest, HOST_OPTIONS)?;
+            expect_no_positional("host", &parsed.positional)?;
+            Ok(Command::Host(parsed.port.unwrap_or(lan::DEFAULT_PORT), parsed.options))
+        }
+        Some((command, rest)) if command == "join" => {
+            let parsed = parse_options("join", rest, JOIN_OPTIONS)?;
+            match parsed.positional.as_slice() {
+                [address] => Ok(Command::Join(with_port(address), parsed.options)),
+                [] => Err(String::from("`join` needs the host's address, e.g. `join 192.168.1.20`")),
+                _ => Err(format!("`join` takes a single address, found: {}", parsed.positional.join(" "))),
+            }
+        }
+        Some((command, rest)) if command == "replay" => match rest {
+            [file] if !file.starts_with("--") => Ok(Command::Replay(PathBuf::from(file))),
+            [] => Err(String::from("`replay` needs the log file, e.g. `replay session.jsonl`")),
+            _ => Err(format!("unexpected arguments for `replay`: {}", rest.join(" "))),
+        },
+        Some((command, rest)) if command == "number" => parse_number(rest),
+        Some((command, rest)) if command == "tournament" => {
+            let parsed = parse_options("tournament", rest, TOURNAMENT_OPTIONS)?;
+            expect_no_positional("tournament", &parsed.positional)?;
+            let mut config = Config::default();
+            if parsed.format_given {
+                config.format = parsed.options.settings.format;
+            }
+            config.rules = parsed.options.settings.rules;
+            config.games = parsed.games.unwrap_or(config.games);
+            Ok(Command::Tournament(TournamentOptions {
+                config,
+                seed: parsed.options.seed,
+                rng: parsed.options.rng,
+                csv: parsed.csv,
+            }))
+        }
+        Some((command, rest)) if command == "stats" => match rest {
+            [] => Ok(Command::Stats(None)),
+            [player] if !player.starts_with("--") => Ok(Command::Stats(Some(player.clone()))),
+            _ => Err(format!("unexpected arguments for `stats`: {}", rest.join(" "))),
+        },
+        Some((command, rest)) if command == "leaderboard" => match rest {
+            [] => Ok(Command::Leaderboard),
+            _ => Err(format!("unexpected arguments for `leaderboard`: {}", rest.join(" "))),
+        },
+        Some((command, _)) if command == "help" || command == "--help" => Ok(Command::Help),
+        Some((option, _)) if option.starts_with("--") => parse_play(args),
+        Some((command, _)) => Err(format!("unknown command `{}`", command)),
+    }
+}
+
+// The options each command accepts.
+const PLAY_OPTIONS: &[&str] =
+    &["--best-of", "--first-to", "--rules", "--strategy", "--seed", "--rng", "--player", "--log", "--input", "--answers"];
+const TUI_OPTIONS: &[&str] = &["--best-of", "--first-to", "--rules", "--strategy", "--seed", "--rng", "--player", "--log"];
+// The host chooses the rules and the length of the match; the nonces must not be seeded (see lan.rs).
+const HOST_OPTIONS: &[&str] = &["--port", "--best-of", "--first-to", "--rules", "--player", "--input", "--answers"];
+const JOIN_OPTIONS: &[&str] = &["--player", "--input", "--answers"];
+const TOURNAMENT_OPTIONS: &[&str] = &["--games", "--best-of", "--first-to", "--rules", "--seed", "--rng", "--csv"];
+
+struct ParsedArgs {
+    options: PlayOptions,
+    // Whether --best-of or --first-to was given, for commands with another default format.
+    format_given: bool,
+    port: Option<u16>,
+    // Only for `tournament`.
+    games: Option<u32>,
+    csv: Option<PathBuf>,
+    // Arguments that are not options, in order.
+    positional: Vec<String>,
+}
+
+fn parse_play(args: &[String]) -> Result<Command, String> {
+    let parsed = parse_options("play", args, PLAY_OPTIONS)?;
+    expect_no_positional("play", &parsed.positional)?;
+    Ok(Command::Play(parsed.options))
+}
+
+fn parse_options(command: &str, args: &[String], allowed: &[&str]) -> Result<ParsedArgs, String> {
+    let mut parsed = ParsedArgs {
+        options: PlayOptions::default(),
+        format_given: false,
+        port: None,
+        games: None,
+        csv: None,
+        positional: Vec::new(),
+    };
+    let options = &mut parsed.options;
+    let mut source_given = false;
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if !flag.starts_with("--") {
+            parsed.positional.push(flag.clone());
+            continue;
+        }
+        if !allowed.contains(&flag.as_str()) {
+            return Err(format!("unknown option `{}` for `{}`", flag, command));
+        }
+        let mut value = || args.next().ok_or_else(|| format!("`{}` needs a value", flag));
+        match flag.as_str() {
+            "--best-of" | "--first-to" => {
+                if parsed.format_given {
+                    return Err(String::from("give only one of --best-of and --first-to"));
+                }
+                let count = parse_count(flag, value()?)?;
+                options.settings.format =
+                    if flag == "--best-of" { MatchFormat::BestOf(count) } else { MatchFormat::FirstTo(count) };
+                parsed.format_given = true;
+            }
+            "--rules" => options.settings.rules = rules::load(value()?)?,
+            "--strategy" => options.settings.strategy = parse_strategy(value()?)?,
+            "--seed" => options.seed = Some(rng::parse_seed(value()?)?),
+            "--rng" => options.rng = rng::parse_rng(value()?)?,
+            "--log" => options.log = Some(PathBuf::from(value()?)),
+            "--games" => parsed.games = Some(parse_count(flag, value()?)?),
+            "--csv" => parsed.csv = Some(PathBuf::from(value()?)),
+            "--port" => {
+                let port = value()?;
+                parsed.port = Some(port.parse().map_err(|_| format!("`--port` needs a port number, not '{}'", port))?);
+            }
+            "--player" => {
+                let name = value()?.trim();
+                if name.is_empty() {
+                    return Err(String::from("`--player` needs a name"));
+                }
+                options.player = Some(name.to_string());
+            }
+            _ => {
+                if source_given {
+                    return Err(String::from("give only one of --input and --answers"));
+                }
+                let value = value()?;
+                options.source =
+                    if flag == "--input" { InputSource::from_text(value) } else { InputSource::File(PathBuf::from(value)) };
+                source_given = true;
+            }
+        }
+    }
+    Ok(parsed)
+}
+
+// `number` has switches without values, so it does not share `parse_options`.
+fn parse_number(args: &[String]) -> Result<Command, String> {
+    let mut options = NumberOptions::default();
+    let mut source_given = false;
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("`{}` needs a value", flag));
+        match flag.as_str() {
+            "--difficulty" => {
+                let name = value()?;
+                options.difficulty = Difficulty::from_name(name).ok_or_else(|| {
+                    let names: Vec<&str> = Difficulty::ALL.iter().map(|difficulty| difficulty.name()).collect();
+                    format!("unknown difficulty '{}' (choose from {})", name, names.join(", "))
+                })?;
+            }
+            "--hints" => options.hints = true,
+            "--bot" => options.bot = true,
+            "--seed" => options.seed = Some(rng::parse_seed(value()?)?),
+            "--rng" => options.rng = rng::parse_rng(value()?)?,
+            "--input" | "--answers" => {
+                if source_given {
+                    return Err(String::from("give only one of --input and --answers"));
+                }
+                let value = value()?;
+                options.source =
+                    if flag == "--input" { InputSource::from_text(value) } else { InputSource::File(PathBuf::from(value)) };
+                source_given = true;
+            }
+            other if other.starts_with("--") => return Err(format!("unknown option `{}` for `number`", other)),
+            other => return Err(format!("unexpected argument `{}` for `number`", other)),
+        }
+    }
+    Ok(Command::Number(options))
+}
+
+// Adds the default port to an address that has none. IPv6 addresses contain ':' themselves,
+// so `::1` becomes `[::1]:7878` while `[::1]:9000` and `localhost:9000` stay as they are.
+fn with_port(address: &str) -> String {
+    if address.parse::<SocketAddr>().is_ok() {
+        return address.to_string();
+    }
+    let bare = address.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')).unwrap_or(address);
+    match bare.parse::<IpAddr>() {
+        Ok(ip) => SocketAddr::new(ip, lan::DEFAULT_PORT).to_string(),
+        Err(_) if address.contains(':') => address.to_string(),
+        Err(_) => format!("{}:{}", address, lan::DEFAULT_PORT),
+    }
+}
+
+fn expect_no_positional(command: &str, positional: &[String]) -> Result<(), String> {
+    match positional.first() {
+        Some(extra) => Err(format!("unexpected argument `{}` for `{}`", extra, command)),
+        None => Ok(()),
+    }
+}
+
+fn parse_count(flag: &str, value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!("`{}` needs a positive number, not '{}'", flag, value)),
+    }
+}
+
+pub fn parse_strategy(name: &str) -> Result<StrategyKind, String> {
+    StrategyKind::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = StrategyKind::ALL.iter().map(|kind| kind.name()).collect();
+        format!("unknown strategy '{}' (choose from {})", name, names.join(", "))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(list: &[&str]) -> Result<Command, String> {
+        let args: Vec<String> = list.iter().map(|arg| arg.to_string()).collect();
+        parse_args(&args)
+    }
+
+    fn play_options(list: &[&str]) -> PlayOptions {
+        match parse(list) {
+            Ok(Command::Play(options)) => options,
+            other => panic!("expected play options, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_input_args() {
+        assert_eq!(play_options(&[]).source, InputSource::Stdin);
+        assert_eq!(play_options(&["--input", "rock\\n"]).source, InputSource::Text(String::from("rock\n")));
+        assert_eq!(play_options(&["--answers", "moves.txt"]).source, InputSource::File(PathBuf::from("moves.txt")));
+        assert!(parse(&["--input"]).is_err());
+        assert!(parse(&["--input", "a", "--answers", "b"]).is_err());
+        assert_eq!(parse(&["--colour", "red"]).unwrap_err(), "unknown option `--colour` for `play`");
+        assert!(parse(&["play", "now"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_match_format() {
+        assert_eq!(play_options(&[]).settings.format, MatchFormat::BestOf(1));
+        assert_eq!(
+            play_options(&["--best-of", "5", "--input", "rock"]),
+            PlayOptions {
+                settings: Settings { format: MatchFormat::BestOf(5), ..Settings::default() },
+                source: InputSource::Text(String::from("rock")),
+                ..PlayOptions::default()
+            }
+        );
+        assert_eq!(play_options(&["play", "--first-to", "3"]).settings.format, MatchFormat::FirstTo(3));
+        assert!(parse(&["--best-of", "0"]).is_err());
+        assert!(parse(&["--best-of", "three"]).is_err());
+        assert!(parse(&["--best-of", "3", "--first-to", "2"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!(play_options(&[]).settings.rules.name, "Rock-Paper-Scissors");
+        assert_eq!(play_options(&["--rules", "rpsls"]).settings.rules.moves.len(), 5);
+        assert!(parse(&["--rules", "no/such/file.json"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_seed() {
+        assert_eq!(play_options(&[]).seed, None);
+        assert_eq!(play_options(&["--seed", "42"]).seed, Some(42));
+        assert!(parse(&["--seed", "x"]).is_err());
+        assert_eq!(play_options(&[]).rng, RngKind::Std);
+        assert_eq!(play_options(&["--rng", "xoshiro256**"]).rng, RngKind::Xoshiro256StarStar);
+        assert!(matches!(parse(&["number", "--rng", "lcg"]), Ok(Command::Number(NumberOptions { rng: RngKind::Lcg, .. }))));
+        assert!(matches!(
+            parse(&["tournament", "--rng", "splitmix64"]),
+            Ok(Command::Tournament(TournamentOptions { rng: RngKind::SplitMix64, .. }))
+        ));
+        assert_eq!(parse(&["host", "--rng", "pcg32"]).unwrap_err(), "unknown option `--rng` for `host`");
+    }
+
+    #[test]
+    fn test_parse_strategy() {
+        assert_eq!(play_options(&[]).settings.strategy, StrategyKind::Random);
+        assert_eq!(play_options(&["--strategy", "markov"]).settings.strategy, StrategyKind::Markov);
+        assert_eq!(
+            parse(&["--strategy", "psychic"]).unwrap_err(),
+            "unknown strategy 'psychic' (choose from random, frequency, markov, wsls)"
+        );
+    }
+
+    #[test]
+    fn test_parse_player_and_views() {
+        assert_eq!(play_options(&["--player", " alice "]).player, Some(String::from("alice")));
+        assert!(parse(&["--player", " "]).is_err());
+        assert_eq!(parse(&["stats"]), Ok(Command::Stats(None)));
+        assert_eq!(parse(&["stats", "alice"]), Ok(Command::Stats(Some(String::from("alice")))));
+        assert!(parse(&["stats", "alice", "bob"]).is_err());
+        assert_eq!(play_options(&["--log", "game.jsonl"]).log, Some(PathBuf::from("game.jsonl")));
+        assert_eq!(parse(&["replay", "game.jsonl"]), Ok(Command::Replay(PathBuf::from("game.jsonl"))));
+        assert!(parse(&["replay"]).is_err());
+        assert_eq!(parse(&["leaderboard"]), Ok(Command::Leaderboard));
+        assert_eq!(parse(&["help"]), Ok(Command::Help));
+        assert!(parse(&["chess"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_host_and_join() {
+        match parse(&["host", "--port", "9000", "--best-of", "3", "--player", "alice"]) {
+            Ok(Command::Host(9000, options)) => {
+                assert_eq!(options.settings.format, MatchFormat::BestOf(3));
+                assert_eq!(options.player, Some(String::from("alice")));
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(parse(&["host"]), Ok(Command::Host(lan::DEFAULT_PORT, _))));
+        assert_eq!(parse(&["host", "--strategy", "markov"]).unwrap_err(), "unknown option `--strategy` for `host`");
+        assert_eq!(parse(&["host", "--seed", "1"]).unwrap_err(), "unknown option `--seed` for `host`");
+        assert!(parse(&["host", "--port", "http"]).is_err());
+
+        assert!(matches!(parse(&["join", "localhost:9000"]), Ok(Command::Join(address, _)) if address == "localhost:9000"));
+        assert!(matches!(parse(&["join", "192.168.1.20"]), Ok(Command::Join(address, _)) if address == "192.168.1.20:7878"));
+        assert!(matches!(parse(&["join", "::1"]), Ok(Command::Join(address, _)) if address == "[::1]:7878"));
+        assert!(matches!(parse(&["join", "fe80::1"]), Ok(Command::Join(address, _)) if address == "[fe80::1]:7878"));
+        assert!(matches!(parse(&["join", "[fe80::1]"]), Ok(Command::Join(address, _)) if address == "[fe80::1]:7878"));
+        assert!(matches!(parse(&["join", "[::1]:9000"]), Ok(Command::Join(address, _)) if address == "[::1]:9000"));
+        assert!(matches!(parse(&["join", "localhost"]), Ok(Command::Join(address, _)) if address == "localhost:7878"));
+        assert!(parse(&["join"]).is_err());
+        assert!(parse(&["join", "a", "b"]).is_err());
+        assert!(parse(&["join", "a", "--rules", "rpsls"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_tui() {
+        match parse(&["tui", "--rules", "rpsls", "--first-to", "3", "--seed", "9"]) {
+            Ok(Command::Tui(options)) => {
+                assert_eq!(options.settings.rules.moves.len(), 5);
+                assert_eq!(options.settings.format, MatchFormat::FirstTo(3));
+                assert_eq!(options.seed, Some(9));
+            }
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(parse(&["tui", "--input", "rock"]).unwrap_err(), "unknown option `--input` for `tui`");
+        assert!(parse(&["tui", "now"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_tournament() {
+        assert_eq!(parse(&["tournament"]), Ok(Command::Tournament(TournamentOptions::default())));
+        match parse(&["tournament", "--games", "10", "--first-to", "3", "--seed", "4", "--csv", "out.csv"]) {
+            Ok(Command::Tournament(options)) => {
+                assert_eq!(options.config.games, 10);
+                assert_eq!(options.config.format, MatchFormat::FirstTo(3));
+                assert_eq!(options.config.strategies, StrategyKind::ALL);
+                assert_eq!(options.seed, Some(4));
+                assert_eq!(options.csv, Some(PathBuf::from("out.csv")));
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(parse(&["tournament", "--games", "0"]).is_err());
+        assert_eq!(parse(&["tournament", "--player", "x"]).unwrap_err(), "unknown option `--player` for `tournament`");
+        assert_eq!(parse(&["play", "--games", "3"]).unwrap_err(), "unknown option `--games` for `play`");
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse(&["number"]), Ok(Command::Number(NumberOptions::default())));
+        assert_eq!(
+            parse(&["number", "--difficulty", "Hard", "--hints", "--seed", "7", "--input", "500\\n"]),
+            Ok(Command::Number(NumberOptions {
+                difficulty: Difficulty::Hard,
+                hints: true,
+                bot: false,
+                source: InputSource::Text(String::from("500\n")),
+                seed: Some(7),
+                rng: RngKind::Std,
+            }))
+        );
+        assert!(matches!(parse(&["number", "--bot"]), Ok(Command::Number(NumberOptions { bot: true, .. }))));
+        assert_eq!(
+            parse(&["number", "--difficulty", "nightmare"]).unwrap_err(),
+            "unknown difficulty 'nightmare' (choose from easy, medium, hard)"
+        );
+        assert_eq!(parse(&["number", "--rules", "rpsls"]).unwrap_err(), "unknown option `--rules` for `number`");
+        assert!(parse(&["number", "42"]).is_err());
+        assert!(parse(&["number", "--difficulty"]).is_err());
+    }
+}
@@ -0,0 +1,422 @@
+// Module: Two-Player Games over the Network
+// `guess_game host` waits for an opponent on a TCP port and `guess_game join <address>`
+// connects to it, so two people can play each other from two terminals (or two computers
+// on the same network). The host picks the rules and the match format.
+//
+// The two programs exchange one JSON message per line. The interesting part is how a round
+// stays fair even though the moves travel over the network: whoever sends their move first
+// would let the other one pick a winning answer. So each round uses "commit-reveal":
+//   1. each side picks a move and sends only a commitment: the SHA-256 hash of a random
+//      nonce and the move, e.g. sha256("9f0c...:rock"),
+//   2. once both commitments have arrived, each side reveals its move and nonce,
+//   3. each side hashes the revealed move and nonce again and compares the result with the
+//      commitment it received. A reveal that doesn't match means the other side changed
+//      its move after seeing ours, and the game stops.
+// The hash hides the move because the nonce is unpredictable: without it, trying the few
+// possible moves would give the answer away. That is why the nonces always come from the
+// operating system's random source and never from `--seed`.
+
+use super::rules::RuleSet;
+use super::{plural, MatchFormat, Outcome, Scoreboard};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+pub const DEFAULT_PORT: u16 = 7878;
+const NONCE_BYTES: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    // Sent by both sides when they connect.
+    Hello { name: String },
+    // Sent by the host after the greetings.
+    Setup { rules: RuleSet, format: MatchFormat },
+    Commit { hash: String },
+    Reveal { choice: String, nonce: String },
+    // The player has no more moves (end of input); the match ends unfinished.
+    Bye,
+}
+
+// One end of a connection: reads and writes `Message`s, one JSON object per line.
+pub struct Connection<R: BufRead, W: Write> {
+    reader: R,
+    writer: W,
+}
+
+impl Connection<BufReader<TcpStream>, TcpStream> {
+    pub fn from_stream(stream: TcpStream) -> io::Result<Self> {
+        let reader = BufReader::new(stream.try_clone()?);
+        Ok(Connection { reader, writer: stream })
+    }
+}
+
+impl<R: BufRead, W: Write> Connection<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Connection { reader, writer }
+    }
+
+    pub fn send(&mut self, message: &Message) -> io::Result<()> {
+        let line = serde_json::to_string(message).map_err(io::Error::other)?;
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()
+    }
+
+    pub fn receive(&mut self) -> io::Result<Message> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "the other player disconnected"));
+        }
+        serde_json::from_str(&line).map_err(|error| invalid(format!("unreadable message from the other player: {}", error)))
+    }
+
+    // Reads and ignores everything until the other side closes the connection. Closing first
+    // while their next message is still on its way would make their system report an error
+    // ("connection reset") instead of delivering our last message.
+    fn wait_for_hang_up(&mut self) {
+        let mut line = String::new();
+        while matches!(self.reader.read_line(&mut line), Ok(count) if count > 0) {
+            line.clear();
+        }
+    }
+}
+
+// The hex-encoded SHA-256 hash of "<nonce>:<choice>".
+pub fn commitment(choice: &str, nonce: &str) -> String {
+    let digest = Sha256::digest(format!("{}:{}", nonce, choice).as_bytes());
+    to_hex(&digest)
+}
+
+// 16 random bytes, hex-encoded.
+pub fn new_nonce(rng: &mut dyn RngCore) -> String {
+    let mut bytes = [0_u8; NONCE_BYTES];
+    rng.fill_bytes(&mut bytes);
+    to_hex(&bytes)
+}
+
+// Whether `nonce` looks like one from `new_nonce`. Move names may contain ':', so without this
+// check the nonce "n:x" with the move "a" would open the same commitment as "n" with "x:a".
+fn is_nonce(nonce: &str) -> bool {
+    nonce.len() == NONCE_BYTES * 2 && nonce.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Everything the local player brings to a game.
+pub struct Player<'a> {
+    pub name: &'a str,
+    pub input: &'a mut dyn BufRead,
+    pub out: &'a mut dyn Write,
+    // Only used for the nonces: must be unpredictable (e.g. `rand::thread_rng()`).
+    pub rng: &'a mut dyn RngCore,
+}
+
+// Waits for one opponent on `listener`, then plays a match with `rules` and `format`.
+pub fn host(listener: &TcpListener, player: Player, rules: &RuleSet, format: MatchFormat) -> io::Result<Option<Outcome>> {
+    let (stream, _) = listener.accept()?;
+    let mut connection = Connection::from_stream(stream)?;
+    connection.send(&Message::Hello { name: player.name.to_string() })?;
+    let opponent = match connection.receive()? {
+        Message::Hello { name } => name,
+        other => return Err(invalid(format!("expected a greeting, got {:?}", other))),
+    };
+    connection.send(&Message::Setup { rules: rules.clone(), format })?;
+    play_online(&mut connection, player, &opponent, rules, format)
+}
+
+// Connects to a host and plays the match it sets up.
+pub fn join(address: impl ToSocketAddrs, player: Player) -> io::Result<Option<Outcome>> {
+    let mut connection = Connection::from_stream(TcpStream::connect(address)?)?;
+    connection.send(&Message::Hello { name: player.name.to_string() })?;
+    let opponent = match connection.receive()? {
+        Message::Hello { name } => name,
+        other => return Err(invalid(format!("expected a greeting, got {:?}", other))),
+    };
+    let (mut rules, format) = match connection.receive()? {
+        Message::Setup { rules, format } => (rules, format),
+        other => return Err(invalid(format!("expected the game setup, got {:?}", other))),
+    };
+    // The same checks as for a rule file, so mixed-case names still match what we type.
+    rules
+        .normalize()
+        .and_then(|()| rules.validate())
+        .map_err(|problem| invalid(format!("the host's rules are not valid: {}", problem)))?;
+    // `first to 0` would be over, and won by both players, before the first round.
+    if format.wins_needed() == 0 {
+        return Err(invalid(format!("the host's match format is not valid: {} needs no wins", format)));
+    }
+    play_online(&mut connection, player, &opponent, &rules, format)
+}
+
+// Plays one match against the other end of `connection`. Both sides run this same function.
+// Returns the local player's result, or `None` if either side ran out of moves first.
+pub fn play_online<R: BufRead, W: Write>(
+    connection: &mut Connection<R, W>,
+    player: Player,
+    opponent: &str,
+    rules: &RuleSet,
+    format: MatchFormat,
+) -> io::Result<Option<Outcome>> {
+    let Player { input, out, rng, .. } = player;
+    writeln!(out, "Connected to {}.", opponent)?;
+    rules.describe(out)?;
+    let needed = format.wins_needed();
+    writeln!(out, "{}: the first to win {} round{} wins the match.", format, needed, plural(needed))?;
+
+    let mut score = Scoreboard::default();
+    while score.winner(format).is_none() {
+        writeln!(out, "Round {}. Your move ({}):", score.rounds() + 1, rules.choice_list())?;
+        out.flush()?;
+        let Some(choice) = read_move(input, out, rules)? else {
+            connection.send(&Message::Bye)?;
+            writeln!(out, "No more moves; the match is left unfinished.")?;
+            connection.wait_for_hang_up();
+            return Ok(None);
+        };
+        let nonce = new_nonce(rng);
+        connection.send(&Message::Commit { hash: commitment(&choice, &nonce) })?;
+        writeln!(out, "Move locked in, waiting for {}...", opponent)?;
+        out.flush()?;
+
+        // Our move is only revealed once theirs is committed, so neither side can react to the other.
+        let their_hash = match connection.receive()? {
+            Message::Commit { hash } => hash,
+            Message::Bye => return opponent_left(out, opponent),
+            other => return Err(invalid(format!("expected a commitment, got {:?}", other))),
+        };
+        connection.send(&Message::Reveal { choice: choice.clone(), nonce })?;
+        let their_choice = match connection.receive()? {
+            Message::Reveal { choice, nonce } => {
+                if !is_nonce(&nonce) || commitment(&choice, &nonce) != their_hash {
+                    return Err(invalid(format!(
+                        "{} revealed '{}', which does not match their commitment; the game is stopped",
+                        opponent, choice
+                    )));
+                }
+                rules
+                    .find_move(&choice)
+                    .ok_or_else(|| invalid(format!("{} revealed '{}', which is not a move", opponent, choice)))?
+                    .to_string()
+            }
+            Message::Bye => return opponent_left(out, opponent),
+            other => return Err(invalid(format!("expected a reveal, got {:?}", other))),
+        };
+
+        let outcome = rules.judge(&choice, &their_choice);
+        score.record(outcome);
+        writeln!(out, "You chose: {}", choice)?;
+        writeln!(out, "{} chose: {}", opponent, their_choice)?;
+        writeln!(out, "{}", outcome.message())?;
+        writeln!(out, "Score: you {}, {} {}, ties {}", score.wins, opponent, score.losses, score.ties)?;
+    }
+    let winner = score.winner(format);
+    let verdict =
+        if winner == Some(Outcome::Win) { String::from("You won the match") } else { format!("{} won the match", opponent) };
+    writeln!(
+        out,
+        "{} {}-{} ({} round{}, {} tie{}).",
+        verdict,
+        score.wins,
+        score.losses,
+        score.rounds(),
+        plural(score.rounds()),
+        score.ties,
+        plural(score.ties)
+    )?;
+    Ok(winner)
+}
+
+// Asks until a valid move is given. `None` when the input runs out.
+fn read_move(input: &mut dyn BufRead, out: &mut dyn Write, rules: &RuleSet) -> io::Result<Option<String>> {
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        match rules.find_move(&line) {
+            Some(choice) => return Ok(Some(choice.to_string())),
+            None => writeln!(out, "Invalid choice. Please choose {}.", rules.choice_list())?,
+        }
+    }
+}
+
+fn opponent_left(out: &mut dyn Write, opponent: &str) -> io::Result<Option<Outcome>> {
+    writeln!(out, "{} left the game; the match is left unfinished.", opponent)?;
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::thread;
+
+    // Runs `join` in a thread against `host` on localhost and returns both transcripts.
+    fn play_pair(host_moves: &'static str, guest_moves: &'static str, format: MatchFormat) -> (String, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let guest = thread::spawn(move || {
+            let mut output: Vec<u8> = Vec::new();
+            let mut rng = StdRng::seed_from_u64(2);
+            let player = Player { name: "bob", input: &mut guest_moves.as_bytes(), out: &mut output, rng: &mut rng };
+            join(address, player).unwrap();
+            String::from_utf8(output).unwrap()
+        });
+        let mut output: Vec<u8> = Vec::new();
+        let mut rng = StdRng::seed_from_u64(1);
+        let player = Player { name: "alice", input: &mut host_moves.as_bytes(), out: &mut output, rng: &mut rng };
+        host(&listener, player, &RuleSet::classic(), format).unwrap();
+        (String::from_utf8(output).unwrap(), guest.join().unwrap())
+    }
+
+    #[test]
+    fn test_commitment_binds_move_and_nonce() {
+        let hash = commitment("rock", "00ff");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, commitment("rock", "00ff"));
+        assert_ne!(hash, commitment("paper", "00ff"));
+        assert_ne!(hash, commitment("rock", "00fe"));
+        // sha256("abc:rock"), computed independently.
+        assert_eq!(commitment("rock", "abc"), "230f7165932b7479c2df000c8c06092dab63fb49f286945042ba027ce11e6611");
+    }
+
+    #[test]
+    fn test_match_between_two_players_on_localhost() {
+        let (alice, bob) = play_pair("rock\npaper\n", "rock\nlizard\nrock\n", MatchFormat::BestOf(1));
+        assert!(alice.contains("Connected to bob.\n"));
+        assert!(alice.contains("It's a tie!\n"));
+        assert!(alice.ends_with(
+            "You chose: paper\nbob chose: rock\nYou win!\nScore: you 1, bob 0, ties 1\nYou won the match 1-0 (2 rounds, 1 tie).\n"
+        ));
+        assert!(bob.contains("Invalid choice. Please choose rock, paper, or scissors.\n"));
+        assert!(bob.ends_with("alice won the match 0-1 (2 rounds, 1 tie).\n"));
+    }
+
+    #[test]
+    fn test_running_out_of_moves_tells_the_other_side() {
+        let (alice, bob) = play_pair("rock\n", "", MatchFormat::FirstTo(2));
+        assert!(alice.ends_with("bob left the game; the match is left unfinished.\n"));
+        assert!(bob.ends_with("No more moves; the match is left unfinished.\n"));
+    }
+
+    #[test]
+    fn test_a_reveal_that_does_not_match_the_commitment_is_rejected() {
+        // A cheating opponent commits to rock, sees our reveal, then claims to have played paper.
+        let nonce = "00112233445566778899aabbccddeeff";
+        let script = [
+            Message::Commit { hash: commitment("rock", nonce) },
+            Message::Reveal { choice: String::from("paper"), nonce: String::from(nonce) },
+        ];
+        let incoming: String = script.iter().map(|message| serde_json::to_string(message).unwrap() + "\n").collect();
+        let mut sent: Vec<u8> = Vec::new();
+        let mut connection = Connection::new(incoming.as_bytes(), &mut sent);
+        let mut output: Vec<u8> = Vec::new();
+        let mut rng = StdRng::seed_from_u64(1);
+        let player = Player { name: "alice", input: &mut "rock\n".as_bytes(), out: &mut output, rng: &mut rng };
+        let error = play_online(&mut connection, player, "mallory", &RuleSet::classic(), MatchFormat::default()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("mallory revealed 'paper', which does not match their commitment"));
+        // Our own reveal went out only after their commitment had arrived.
+        let sent = String::from_utf8(sent).unwrap();
+        assert!(sent.lines().next().unwrap().starts_with(r#"{"type":"commit""#));
+        assert!(sent.lines().nth(1).unwrap().starts_with(r#"{"type":"reveal","choice":"rock""#));
+    }
+
+    #[test]
+    fn test_a_commitment_opens_to_one_move_only() {
+        // With moves "a" and "x:a", the same hash would open as either move if any nonce were accepted.
+        let nonce = "00112233445566778899aabbccddeeff";
+        assert_eq!(commitment("x:a", nonce), commitment("a", &format!("{}:x", nonce)));
+        let rules = RuleSet::parse(r#"{"name": "colons", "moves": ["a", "x:a"], "beats": {"a": ["x:a"]}}"#).unwrap();
+        let script = [
+            Message::Commit { hash: commitment("x:a", nonce) },
+            Message::Reveal { choice: String::from("a"), nonce: format!("{}:x", nonce) },
+        ];
+        let incoming: String = script.iter().map(|message| serde_json::to_string(message).unwrap() + "\n").collect();
+        let mut sent: Vec<u8> = Vec::new();
+        let mut connection = Connection::new(incoming.as_bytes(), &mut sent);
+        let mut rng = StdRng::seed_from_u64(1);
+        let player = Player { name: "alice", input: &mut "a\n".as_bytes(), out: &mut io::sink(), rng: &mut rng };
+        let error = play_online(&mut connection, player, "mallory", &rules, MatchFormat::default()).unwrap_err();
+        assert!(error.to_string().starts_with("mallory revealed 'a', which does not match their commitment"), "{}", error);
+        assert!(is_nonce(&new_nonce(&mut rng)));
+    }
+
+    #[test]
+    fn test_join_rejects_invalid_rules_from_the_host() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let host = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut connection = Connection::from_stream(stream).unwrap();
+            let mut rules = RuleSet::classic();
+            rules.beats.insert(String::from("rock"), vec![String::from("rock")]);
+            connection.send(&Message::Hello { name: String::from("host") }).unwrap();
+            connection.send(&Message::Setup { rules, format: MatchFormat::default() }).unwrap();
+            let _ = connection.receive();
+        });
+        let mut rng = StdRng::seed_from_u64(1);
+        let player = Player { name: "bob", input: &mut "rock\n".as_bytes(), out: &mut io::sink(), rng: &mut rng };
+        let error = join(address, player).unwrap_err();
+        assert!(error.to_string().contains("'rock' beats itself"), "{}", error);
+        host.join().unwrap();
+    }
+
+    #[test]
+    fn test_join_rejects_a_match_without_rounds() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let host = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut connection = Connection::from_stream(stream).unwrap();
+            connection.send(&Message::Hello { name: String::from("host") }).unwrap();
+            connection.send(&Message::Setup { rules: RuleSet::classic(), format: MatchFormat::FirstTo(0) }).unwrap();
+            let _ = connection.receive();
+        });
+        let mut rng = StdRng::seed_from_u64(1);
+        let player = Player { name: "bob", input: &mut "rock\n".as_bytes(), out: &mut io::sink(), rng: &mut rng };
+        let error = join(address, player).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("the host's match format is not valid"), "{}", error);
+        host.join().unwrap();
+    }
+
+    #[test]
+    fn test_join_accepts_host_rules_in_any_case() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let host = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut connection = Connection::from_stream(stream).unwrap();
+            let mut rules = RuleSet::classic();
+            rules.moves = rules.moves.iter().map(|name| name.to_uppercase()).collect();
+            rules.beats = rules
+                .beats
+                .iter()
+                .map(|(winner, losers)| (winner.to_uppercase(), losers.iter().map(|name| name.to_uppercase()).collect()))
+                .collect();
+            connection.send(&Message::Hello { name: String::from("host") }).unwrap();
+            connection.send(&Message::Setup { rules, format: MatchFormat::default() }).unwrap();
+            // After its greeting, the guest commits to a move instead of saying goodbye.
+            assert!(matches!(connection.receive().unwrap(), Message::Hello { .. }));
+            connection.receive().unwrap()
+        });
+        let mut output: Vec<u8> = Vec::new();
+        let mut rng = StdRng::seed_from_u64(1);
+        let player = Player { name: "bob", input: &mut "rock\n".as_bytes(), out: &mut output, rng: &mut rng };
+        // The host hangs up after the first message, so the match itself ends in an error.
+        let _ = join(address, player);
+        assert!(matches!(host.join().unwrap(), Message::Commit { .. }));
+        let output = String::from_utf8(output).unwrap();
+        assert!(!output.contains("Invalid choice"), "{}", output);
+    }
+}
@@ -0,0 +1,352 @@
+// Module: Number Guessing
+// The classic "guess my number" game: the computer picks a secret number in a range and
+// answers every guess with "too low", "too high" or "correct", until the attempts run out.
+// `guess_game number` plays it; `--difficulty` sets the range and the number of attempts,
+// and `--hints` also shows the range the secret is known to be in after each guess.
+//
+// With `--bot`, a bot plays instead of you. It always guesses the middle of the range that
+// is still possible, so every answer halves the range: this "binary search" finds any
+// number in 1-100 with at most 7 guesses, and it is the best any strategy can guarantee.
+
+use rand::Rng;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        let name = name.trim().to_lowercase();
+        Difficulty::ALL.into_iter().find(|difficulty| difficulty.name() == name)
+    }
+
+    pub fn range(self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=50,
+            Difficulty::Medium => 1..=100,
+            Difficulty::Hard => 1..=1000,
+        }
+    }
+
+    // Easy leaves room for mistakes; medium and hard give exactly what binary search needs.
+    pub fn attempts(self) -> u32 {
+        match self {
+            Difficulty::Easy => 8,
+            Difficulty::Medium => 7,
+            Difficulty::Hard => 10,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// The answer to a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feedback {
+    TooLow,
+    TooHigh,
+    Correct,
+}
+
+impl Feedback {
+    pub fn message(self) -> &'static str {
+        match self {
+            Feedback::TooLow => "Too low!",
+            Feedback::TooHigh => "Too high!",
+            Feedback::Correct => "Correct!",
+        }
+    }
+}
+
+// The state of one game, without any input or output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberGame {
+    secret: u32,
+    range: RangeInclusive<u32>,
+    attempts: u32,
+    guesses: Vec<u32>,
+    // The numbers still possible given the answers so far.
+    possible: RangeInclusive<u32>,
+}
+
+impl NumberGame {
+    pub fn new(secret: u32, range: RangeInclusive<u32>, attempts: u32) -> NumberGame {
+        NumberGame { secret, possible: range.clone(), range, attempts, guesses: Vec::new() }
+    }
+
+    // A game with a random secret, set up for `difficulty`.
+    pub fn random<R: Rng>(difficulty: Difficulty, rng: &mut R) -> NumberGame {
+        let range = difficulty.range();
+        NumberGame::new(rng.gen_range(range.clone()), range, difficulty.attempts())
+    }
+
+    pub fn secret(&self) -> u32 {
+        self.secret
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    pub fn possible(&self) -> &RangeInclusive<u32> {
+        &self.possible
+    }
+
+    pub fn guesses(&self) -> &[u32] {
+        &self.guesses
+    }
+
+    pub fn attempts_left(&self) -> u32 {
+        self.attempts.saturating_sub(self.guesses.len() as u32)
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.guesses.last() == Some(&self.secret)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.attempts_left() == 0
+    }
+
+    // Records a guess. The caller checks that it is inside `range` and that the game is not over.
+    pub fn guess(&mut self, number: u32) -> Feedback {
+        self.guesses.push(number);
+        let (low, high) = (*self.possible.start(), *self.possible.end());
+        if number < self.secret {
+            self.possible = low.max(number + 1)..=high;
+            Feedback::TooLow
+        } else if number > self.secret {
+            self.possible = low..=high.min(number - 1);
+            Feedback::TooHigh
+        } else {
+            self.possible = number..=number;
+            Feedback::Correct
+        }
+    }
+}
+
+// The most guesses binary search can need for a range: the smallest k with 2^k - 1 >= its size.
+pub fn optimal_guesses(range: &RangeInclusive<u32>) -> u32 {
+    let size = u64::from(range.end() - range.start()) + 1;
+    let mut guesses = 0;
+    while (1_u64 << guesses) - 1 < size {
+        guesses += 1;
+    }
+    guesses
+}
+
+// Guesses the middle of the numbers that are still possible.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinarySearchBot {
+    low: u32,
+    high: u32,
+}
+
+impl BinarySearchBot {
+    pub fn new(range: &RangeInclusive<u32>) -> BinarySearchBot {
+        BinarySearchBot { low: *range.start(), high: *range.end() }
+    }
+
+    pub fn next_guess(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+
+    pub fn learn(&mut self, guess: u32, feedback: Feedback) {
+        match feedback {
+            Feedback::TooLow => self.low = guess + 1,
+            Feedback::TooHigh => self.high = guess - 1,
+            Feedback::Correct => (self.low, self.high) = (guess, guess),
+        }
+    }
+}
+
+// Lets the player guess until they find the number, run out of attempts or out of input.
+pub fn play(input: &mut dyn BufRead, out: &mut dyn Write, game: &mut NumberGame, hints: bool) -> io::Result<()> {
+    let (low, high) = (*game.range().start(), *game.range().end());
+    writeln!(
+        out,
+        "Guess the number! I'm thinking of a number between {} and {}. You have {} attempts.",
+        low,
+        high,
+        game.attempts_left()
+    )?;
+    while !game.is_over() {
+        let attempt = game.guesses().len() + 1;
+        writeln!(out, "Attempt {} of {}. Your guess:", attempt, attempt as u32 + game.attempts_left() - 1)?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return writeln!(out, "No more guesses. The number was {}.", game.secret());
+        }
+        // Like the user_input lesson: trim, then `parse`, and explain what went wrong.
+        let number = match line.trim().parse::<u32>() {
+            Ok(number) if game.range().contains(&number) => number,
+            Ok(_) => {
+                writeln!(out, "Please guess a number between {} and {}.", low, high)?;
+                continue;
+            }
+            Err(_) => {
+                writeln!(out, "'{}' is not a whole number. Please type a number such as {}.", line.trim(), (low + high) / 2)?;
+                continue;
+            }
+        };
+        let feedback = game.guess(number);
+        writeln!(out, "{}", feedback.message())?;
+        if hints && feedback != Feedback::Correct {
+            writeln!(out, "Hint: the number is between {} and {}.", game.possible().start(), game.possible().end())?;
+        }
+    }
+    let count = game.guesses().len();
+    if game.is_won() {
+        writeln!(out, "You found {} in {} guess{}.", game.secret(), count, if count == 1 { "" } else { "es" })?;
+        writeln!(out, "(Binary search never needs more than {} for this range.)", optimal_guesses(game.range()))
+    } else {
+        writeln!(out, "Out of attempts! The number was {}.", game.secret())
+    }
+}
+
+// Lets the binary-search bot play `game` and explains each step.
+pub fn play_bot(out: &mut dyn Write, game: &mut NumberGame) -> io::Result<()> {
+    let range = game.range().clone();
+    writeln!(out, "The binary-search bot is guessing a number between {} and {}.", range.start(), range.end())?;
+    let mut bot = BinarySearchBot::new(&range);
+    while !game.is_over() {
+        let guess = bot.next_guess();
+        let feedback = game.guess(guess);
+        bot.learn(guess, feedback);
+        writeln!(out, "Guess {}: {} ({})", game.guesses().len(), guess, feedback.message().to_lowercase())?;
+    }
+    let count = game.guesses().len();
+    if game.is_won() {
+        writeln!(
+            out,
+            "The bot found {} in {} guess{}. Halving the range each time, it never needs more than {} for {}-{}.",
+            game.secret(),
+            count,
+            if count == 1 { "" } else { "es" },
+            optimal_guesses(&range),
+            range.start(),
+            range.end()
+        )
+    } else {
+        writeln!(out, "The bot ran out of attempts; the number was {}.", game.secret())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn human(secret: u32, answers: &str, hints: bool) -> String {
+        let mut game = NumberGame::new(secret, 1..=100, 7);
+        let mut output: Vec<u8> = Vec::new();
+        play(&mut answers.as_bytes(), &mut output, &mut game, hints).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_difficulties() {
+        for difficulty in Difficulty::ALL {
+            assert_eq!(Difficulty::from_name(difficulty.name()), Some(difficulty));
+            // Every preset can always be won by playing well.
+            assert!(optimal_guesses(&difficulty.range()) <= difficulty.attempts(), "{}", difficulty);
+        }
+        assert_eq!(Difficulty::from_name("Nightmare"), None);
+    }
+
+    #[test]
+    fn test_optimal_guesses() {
+        assert_eq!(optimal_guesses(&(1..=1)), 1);
+        assert_eq!(optimal_guesses(&(1..=3)), 2);
+        assert_eq!(optimal_guesses(&(1..=100)), 7);
+        assert_eq!(optimal_guesses(&(1..=1000)), 10);
+        assert_eq!(optimal_guesses(&(0..=u32::MAX)), 33);
+    }
+
+    #[test]
+    fn test_feedback_narrows_the_possible_range() {
+        let mut game = NumberGame::new(42, 1..=100, 7);
+        assert_eq!(game.guess(50), Feedback::TooHigh);
+        assert_eq!(game.guess(10), Feedback::TooLow);
+        assert_eq!(game.possible(), &(11..=49));
+        assert_eq!(game.attempts_left(), 5);
+        assert_eq!(game.guess(42), Feedback::Correct);
+        assert!(game.is_won() && game.is_over());
+    }
+
+    #[test]
+    fn test_player_wins_with_hints() {
+        assert_eq!(
+            human(42, "50\n42\n", true),
+            "Guess the number! I'm thinking of a number between 1 and 100. You have 7 attempts.\n\
+             Attempt 1 of 7. Your guess:\n\
+             Too high!\n\
+             Hint: the number is between 1 and 49.\n\
+             Attempt 2 of 7. Your guess:\n\
+             Correct!\n\
+             You found 42 in 2 guesses.\n\
+             (Binary search never needs more than 7 for this range.)\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_guesses_do_not_use_attempts() {
+        let output = human(7, "seven\n0\n 7 \n", false);
+        assert!(output.contains("'seven' is not a whole number. Please type a number such as 50.\n"));
+        assert!(output.contains("Please guess a number between 1 and 100.\n"));
+        assert!(output.contains("You found 7 in 1 guess.\n"));
+        assert!(!output.contains("Hint:"));
+    }
+
+    #[test]
+    fn test_running_out_of_attempts_or_input() {
+        assert!(human(99, "1\n2\n3\n4\n5\n6\n7\n8\n", false).ends_with("Out of attempts! The number was 99.\n"));
+        assert!(human(99, "1\n", false).ends_with("No more guesses. The number was 99.\n"));
+    }
+
+    #[test]
+    fn test_bot_always_wins_within_the_optimal_number_of_guesses() {
+        for difficulty in Difficulty::ALL {
+            for secret in difficulty.range() {
+                let mut game = NumberGame::new(secret, difficulty.range(), difficulty.attempts());
+                play_bot(&mut io::sink(), &mut game).unwrap();
+                assert!(game.is_won(), "{} {}", difficulty, secret);
+                assert!(game.guesses().len() as u32 <= optimal_guesses(&difficulty.range()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bot_explains_its_guesses() {
+        let mut game = NumberGame::random(Difficulty::Medium, &mut StdRng::seed_from_u64(5));
+        let secret = game.secret();
+        let mut output: Vec<u8> = Vec::new();
+        play_bot(&mut output, &mut game).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("The binary-search bot is guessing a number between 1 and 100.\nGuess 1: 50 ("));
+        assert!(output.contains(&format!("The bot found {} in ", secret)));
+        assert!(output.ends_with("it never needs more than 7 for 1-100.\n"));
+    }
+}
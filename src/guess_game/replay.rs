@@ -0,0 +1,290 @@
+// Module: Replay Logs
+// `--log <file>` saves a session as JSON Lines: one JSON object per line, first the
+// settings and the seed, then one line per round:
+//
+//     {"type":"session","player":"alice","seed":42,"strategy":"markov","format":{"best_of":3},"rules":{...}}
+//     {"type":"round","player":"rock","computer":"scissors","outcome":"win"}
+//
+// `guess_game replay <file>` shows the matches again, round by round, and then checks the
+// log against the program: the computer makes exactly one choice per round, so the same
+// seed and strategy must give the same computer moves. A log that does not replay points at
+// a bug (or at a log that was edited by hand), which makes it useful in bug reports.
+// Sessions played with `--rng` record the generator ("rng":"pcg32").
+
+use super::rules::RuleSet;
+use super::strategy::{Round, StrategyKind};
+use super::{MatchFormat, Outcome, Scoreboard, Settings};
+use crate::rng::RngKind;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+// One line of a log file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Entry {
+    Session {
+        player: String,
+        seed: u64,
+        strategy: String,
+        format: MatchFormat,
+        rules: RuleSet,
+        #[serde(default, skip_serializing_if = "RngKind::is_std")]
+        rng: RngKind,
+    },
+    // `outcome` is only there for people reading the file; it is checked when the log is read.
+    Round { player: String, computer: String, outcome: Outcome },
+}
+
+// A recorded session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Log {
+    pub player: String,
+    pub seed: u64,
+    // The generator the seed was given to.
+    pub rng: RngKind,
+    pub settings: Settings,
+    pub rounds: Vec<Round>,
+}
+
+impl Log {
+    pub fn write(&self, out: &mut dyn Write) -> io::Result<()> {
+        let settings = &self.settings;
+        let header = Entry::Session {
+            player: self.player.clone(),
+            seed: self.seed,
+            strategy: settings.strategy.name().to_string(),
+            format: settings.format,
+            rules: settings.rules.clone(),
+            rng: self.rng,
+        };
+        writeln!(out, "{}", serde_json::to_string(&header)?)?;
+        for round in &self.rounds {
+            let entry = Entry::Round {
+                player: round.player.clone(),
+                computer: round.computer.clone(),
+                outcome: settings.rules.judge(&round.player, &round.computer),
+            };
+            writeln!(out, "{}", serde_json::to_string(&entry)?)?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut text: Vec<u8> = Vec::new();
+        self.write(&mut text)?;
+        fs::write(path, text)
+    }
+
+    pub fn load(path: &Path) -> Result<Log, String> {
+        let text = fs::read_to_string(path).map_err(|error| format!("cannot read {}: {}", path.display(), error))?;
+        Log::parse(&text).map_err(|problem| format!("{}: {}", path.display(), problem))
+    }
+
+    // Reads a log and checks that it makes sense on its own: valid rules, known moves and
+    // outcomes that follow from them. Whether the seed gives the same moves is `verify`'s job.
+    pub fn parse(text: &str) -> Result<Log, String> {
+        let mut log: Option<Log> = None;
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let problem = |message: String| format!("line {}: {}", index + 1, message);
+            let entry: Entry = serde_json::from_str(line).map_err(|error| problem(error.to_string()))?;
+            match (entry, log.as_mut()) {
+                (Entry::Session { player, seed, strategy, format, rules, rng }, None) => {
+                    rules.validate().map_err(|error| problem(format!("rules: {}", error)))?;
+                    let strategy = StrategyKind::from_name(&strategy)
+                        .ok_or_else(|| problem(format!("unknown strategy '{}'", strategy)))?;
+                    log = Some(Log { player, seed, rng, settings: Settings { format, rules, strategy }, rounds: Vec::new() });
+                }
+                (Entry::Session { .. }, Some(_)) => return Err(problem(String::from("a second session starts here"))),
+                (Entry::Round { .. }, None) => return Err(problem(String::from("the log must start with the session"))),
+                (Entry::Round { player, computer, outcome }, Some(log)) => {
+                    let rules = &log.settings.rules;
+                    for name in [&player, &computer] {
+                        if !rules.moves.contains(name) {
+                            return Err(problem(format!("'{}' is not a move of {}", name, rules.name)));
+                        }
+                    }
+                    let judged = rules.judge(&player, &computer);
+                    if judged != outcome {
+                        return Err(problem(format!(
+                            "{} against {} is a {:?}, not a {:?}",
+                            player, computer, judged, outcome
+                        )));
+                    }
+                    log.rounds.push(Round { player, computer });
+                }
+            }
+        }
+        log.ok_or_else(|| String::from("the log is empty"))
+    }
+
+    // Shows the session again, match by match.
+    pub fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        let settings = &self.settings;
+        match self.rng {
+            RngKind::Std => writeln!(out, "Replay of {}'s session (seed {})", self.player, self.seed)?,
+            other => writeln!(out, "Replay of {}'s session (seed {}, generator {})", self.player, self.seed, other)?,
+        }
+        writeln!(out, "{}, {}, computer strategy: {}.", settings.rules.name, settings.format, settings.strategy)?;
+        let mut score = Scoreboard::default();
+        let mut matches = 0;
+        for (index, round) in self.rounds.iter().enumerate() {
+            if score.rounds() == 0 {
+                matches += 1;
+                writeln!(out, "Match {}:", matches)?;
+            }
+            let outcome = settings.rules.judge(&round.player, &round.computer);
+            score.record(outcome);
+            writeln!(
+                out,
+                "  Round {}: you {}, computer {}. {} {}",
+                index + 1,
+                round.player,
+                round.computer,
+                outcome.message(),
+                score
+            )?;
+            if let Some(winner) = score.winner(settings.format) {
+                let verdict = if winner == Outcome::Win { "You won the match" } else { "The computer won the match" };
+                writeln!(out, "  {} {}-{}.", verdict, score.wins, score.losses)?;
+                score = Scoreboard::default();
+            }
+        }
+        if score.rounds() > 0 {
+            writeln!(out, "  (The match was left unfinished.)")?;
+        }
+        if self.rounds.is_empty() {
+            writeln!(out, "No rounds were played.")?;
+        }
+        Ok(())
+    }
+
+    // Plays the computer's side again with the recorded seed and strategy, and returns how
+    // many moves matched, or which round is the first to differ.
+    pub fn verify(&self) -> Result<usize, String> {
+        let settings = &self.settings;
+        let mut strategy = settings.strategy.build();
+        let mut rng = self.rng.seeded(self.seed);
+        for (index, round) in self.rounds.iter().enumerate() {
+            let expected = strategy.choose(&settings.rules, &self.rounds[..index], &mut rng);
+            if expected != round.computer {
+                return Err(format!(
+                    "round {}: the log says the computer played {}, but seed {} gives {}",
+                    index + 1,
+                    round.computer,
+                    self.seed,
+                    expected
+                ));
+            }
+        }
+        Ok(self.rounds.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guess_game::{play, rules};
+
+    // Plays a real session with a seed and returns its log.
+    fn recorded(seed: u64, settings: Settings, answers: &str) -> Log {
+        recorded_with(RngKind::Std, seed, settings, answers)
+    }
+
+    fn recorded_with(rng: RngKind, seed: u64, settings: Settings, answers: &str) -> Log {
+        let session = play(&mut answers.as_bytes(), &mut io::sink(), &mut rng.seeded(seed), &settings).unwrap();
+        Log { player: String::from("alice"), seed, rng, settings, rounds: session.rounds }
+    }
+
+    fn text(log: &Log) -> String {
+        let mut output: Vec<u8> = Vec::new();
+        log.write(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_log_round_trip() {
+        let settings = Settings {
+            format: MatchFormat::FirstTo(2),
+            rules: rules::built_in("rpsls").unwrap(),
+            strategy: StrategyKind::Markov,
+        };
+        let log = recorded(11, settings, "spock\nlizard\nrock\npaper\nscissors\nspock\nlizard\n");
+        let text = text(&log);
+        assert!(text.starts_with(r#"{"type":"session","player":"alice","seed":11,"strategy":"markov","format":{"first_to":2},"#));
+        assert_eq!(text.lines().count(), log.rounds.len() + 1);
+        assert_eq!(Log::parse(&text).unwrap(), log);
+        assert!(!text.contains(r#""rng""#));
+    }
+
+    #[test]
+    fn test_the_generator_is_recorded_and_replayed() {
+        let settings = Settings { format: MatchFormat::FirstTo(3), ..Settings::default() };
+        let mut log = recorded_with(RngKind::Pcg32, 3, settings, "rock\n".repeat(12).as_str());
+        let text = text(&log);
+        assert!(text.lines().next().unwrap().ends_with(r#","rng":"pcg32"}"#));
+        assert_eq!(Log::parse(&text).unwrap(), log);
+        assert_eq!(log.verify(), Ok(log.rounds.len()));
+        // The same seed in another generator gives other moves.
+        log.rng = RngKind::Std;
+        assert!(log.verify().is_err());
+    }
+
+    #[test]
+    fn test_a_recorded_session_replays_even_with_invalid_answers() {
+        let settings = Settings { format: MatchFormat::BestOf(3), strategy: StrategyKind::Frequency, ..Settings::default() };
+        let log = recorded(5, settings, "rock\nbanana\nrock\npaper\n\nrock\nscissors\npaper\nrock\n");
+        assert!(log.rounds.len() >= 2);
+        assert_eq!(log.verify(), Ok(log.rounds.len()));
+    }
+
+    #[test]
+    fn test_verify_finds_the_first_different_move() {
+        let mut log = recorded(5, Settings { format: MatchFormat::FirstTo(3), ..Settings::default() }, "rock\n".repeat(9).as_str());
+        let computer = log.rounds[1].computer.clone();
+        let changed = if computer == "rock" { "paper" } else { "rock" };
+        log.rounds[1].computer = changed.to_string();
+        assert_eq!(
+            log.verify(),
+            Err(format!("round 2: the log says the computer played {}, but seed 5 gives {}", changed, computer))
+        );
+        log.seed = 6;
+        assert!(log.verify().is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_broken_logs() {
+        let header = text(&Log { player: String::from("bob"), seed: 1, rng: RngKind::Std, settings: Settings::default(), rounds: Vec::new() });
+        let round = |line: &str| Log::parse(&format!("{}{}\n", header, line)).unwrap_err();
+        assert_eq!(round(r#"{"type":"round","player":"rock","computer":"scissors","outcome":"lose"}"#), "line 2: rock against scissors is a Win, not a Lose");
+        assert_eq!(round(r#"{"type":"round","player":"rock","computer":"well","outcome":"win"}"#), "line 2: 'well' is not a move of Rock-Paper-Scissors");
+        assert_eq!(round(header.trim_end()), "line 2: a second session starts here");
+        assert!(round("not json").starts_with("line 2: "));
+        assert_eq!(Log::parse("").unwrap_err(), "the log is empty");
+        assert_eq!(
+            Log::parse(r#"{"type":"round","player":"rock","computer":"rock","outcome":"tie"}"#).unwrap_err(),
+            "line 1: the log must start with the session"
+        );
+        assert_eq!(Log::parse(&header.replace("random", "psychic")).unwrap_err(), "line 1: unknown strategy 'psychic'");
+    }
+
+    #[test]
+    fn test_render_shows_every_match() {
+        let rounds = [("rock", "scissors"), ("rock", "rock"), ("paper", "rock"), ("paper", "scissors")]
+            .iter()
+            .map(|(player, computer)| Round { player: player.to_string(), computer: computer.to_string() })
+            .collect();
+        let log = Log { player: String::from("bob"), seed: 3, rng: RngKind::Std, settings: Settings::default(), rounds };
+        let mut output: Vec<u8> = Vec::new();
+        log.render(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("Replay of bob's session (seed 3)\nRock-Paper-Scissors, Best of 1, computer strategy: random.\nMatch 1:\n"));
+        assert!(output.contains("  Round 2: you rock, computer rock. It's a tie! Score: you 0, computer 0, ties 1\n"));
+        assert!(output.contains("Match 3:\n  Round 4: you paper, computer scissors. You lose! Score: you 0, computer 1, ties 0\n"));
+        assert!(output.ends_with("  The computer won the match 0-1.\n"));
+    }
+}
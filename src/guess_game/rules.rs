@@ -0,0 +1,242 @@
+// Module: Rule Sets
+// Which moves exist and which move beats which. The built-in rule sets live in data files
+// under `rules/` and are built into the program with `include_str!`; players can also load
+// their own file with `--rules <file>`. A rule file looks like this:
+//
+//     {
+//       "name": "Rock-Paper-Scissors",
+//       "moves": ["rock", "paper", "scissors"],
+//       "beats": { "rock": ["scissors"], "paper": ["rock"], "scissors": ["paper"] }
+//     }
+//
+// `beats` is a graph: an arrow from every move to each move it defeats. A file is only
+// accepted if that graph decides every pair of different moves exactly one way round
+// and no move beats itself, so a round can never end in an undecided state.
+
+use super::Outcome;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+
+// (name for `--rules`, file) for every built-in rule set. The first one is the default.
+const BUILT_IN: &[(&str, &str)] = &[
+    ("classic", include_str!("../../rules/classic.json")),
+    ("rpsls", include_str!("../../rules/rpsls.json")),
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleSet {
+    pub name: String,
+    // In the order they are offered to the player.
+    pub moves: Vec<String>,
+    // Move -> the moves it beats.
+    pub beats: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for RuleSet {
+    fn default() -> RuleSet {
+        RuleSet::classic()
+    }
+}
+
+pub fn built_in_names() -> impl Iterator<Item = &'static str> {
+    BUILT_IN.iter().map(|(name, _)| *name)
+}
+
+// A built-in rule set by its short name.
+// The built-in files are checked by the tests, so a broken one is a bug in the program.
+pub fn built_in(name: &str) -> Option<RuleSet> {
+    BUILT_IN
+        .iter()
+        .find(|(built_in, _)| *built_in == name)
+        .map(|(name, text)| RuleSet::parse(text).unwrap_or_else(|error| panic!("rules/{}.json: {}", name, error)))
+}
+
+// The argument of `--rules`: the name of a built-in rule set or the path of a rule file.
+pub fn load(name_or_path: &str) -> Result<RuleSet, String> {
+    if let Some(rules) = built_in(name_or_path) {
+        return Ok(rules);
+    }
+    let text = fs::read_to_string(name_or_path).map_err(|error| {
+        let names: Vec<&str> = built_in_names().collect();
+        format!(
+            "'{}' is not a built-in rule set ({}) and cannot be read as a file: {}",
+            name_or_path,
+            names.join(", "),
+            error
+        )
+    })?;
+    RuleSet::parse(&text).map_err(|problem| format!("{}: {}", name_or_path, problem))
+}
+
+impl RuleSet {
+    pub fn classic() -> RuleSet {
+        built_in("classic").unwrap()
+    }
+
+    // Reads a rule set from JSON and checks that its graph is consistent.
+    // Move names are compared without case, so they are stored in lowercase.
+    pub fn parse(text: &str) -> Result<RuleSet, String> {
+        let mut rules: RuleSet = serde_json::from_str(text).map_err(|error| error.to_string())?;
+        rules.normalize()?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    // Trims and lowercases every move name. Two `beats` entries that only differ in case
+    // would end up as one, so they are an error.
+    pub fn normalize(&mut self) -> Result<(), String> {
+        let normalize = |name: &String| name.trim().to_lowercase();
+        self.moves = self.moves.iter().map(normalize).collect();
+        let mut beats = BTreeMap::new();
+        for (winner, losers) in &self.beats {
+            if beats.insert(normalize(winner), losers.iter().map(normalize).collect()).is_some() {
+                return Err(format!("'{}' has more than one entry in `beats`", normalize(winner)));
+            }
+        }
+        self.beats = beats;
+        Ok(())
+    }
+
+    // Checks the rules `parse` enforces; also used for rule sets that arrive over the network.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.moves.len() < 2 {
+            return Err(format!("needs at least two moves, found {}", self.moves.len()));
+        }
+        for (index, name) in self.moves.iter().enumerate() {
+            if name.is_empty() {
+                return Err(String::from("a move has an empty name"));
+            }
+            if self.moves[..index].contains(name) {
+                return Err(format!("move '{}' is listed twice", name));
+            }
+        }
+        for (winner, losers) in &self.beats {
+            for name in std::iter::once(winner).chain(losers) {
+                if !self.moves.contains(name) {
+                    return Err(format!("'{}' in `beats` is not one of the moves", name));
+                }
+            }
+            if losers.contains(winner) {
+                return Err(format!("'{}' beats itself", winner));
+            }
+        }
+        for (index, first) in self.moves.iter().enumerate() {
+            for second in &self.moves[index + 1..] {
+                match (self.beats(first, second), self.beats(second, first)) {
+                    (true, true) => return Err(format!("'{}' and '{}' beat each other", first, second)),
+                    (false, false) => return Err(format!("nothing decides between '{}' and '{}'", first, second)),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn beats(&self, winner: &str, loser: &str) -> bool {
+        self.beats.get(winner).is_some_and(|losers| losers.iter().any(|name| name == loser))
+    }
+
+    // Decides a round. Both moves must be moves of this rule set.
+    pub fn judge(&self, player: &str, computer: &str) -> Outcome {
+        if player == computer {
+            Outcome::Tie
+        } else if self.beats(player, computer) {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    // The move the player meant by `answer`, if it is one. Case and surrounding spaces are ignored.
+    pub fn find_move(&self, answer: &str) -> Option<&str> {
+        let answer = answer.trim().to_lowercase();
+        self.moves.iter().find(|name| **name == answer).map(String::as_str)
+    }
+
+    // "rock, paper, or scissors"
+    pub fn choice_list(&self) -> String {
+        match self.moves.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{}, or {}", rest.join(", "), last),
+            None => String::new(),
+        }
+    }
+
+    // One line per move: what it beats.
+    pub fn describe(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Rules: {}", self.name)?;
+        for name in &self.moves {
+            let losers = self.beats.get(name).map(|losers| losers.join(" and ")).unwrap_or_default();
+            if losers.is_empty() {
+                writeln!(out, "  {} beats nothing", name)?;
+            } else {
+                writeln!(out, "  {} beats {}", name, losers)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_built_in_rule_set_is_valid() {
+        for name in built_in_names() {
+            assert!(built_in(name).is_some(), "rules/{}.json", name);
+        }
+        assert_eq!(RuleSet::default().moves, ["rock", "paper", "scissors"]);
+        assert_eq!(built_in("rpsls").unwrap().moves.len(), 5);
+        assert!(built_in("chess").is_none());
+    }
+
+    #[test]
+    fn test_judge_rpsls() {
+        let rules = built_in("rpsls").unwrap();
+        assert_eq!(rules.judge("spock", "rock"), Outcome::Win);
+        assert_eq!(rules.judge("lizard", "rock"), Outcome::Lose);
+        assert_eq!(rules.judge("lizard", "lizard"), Outcome::Tie);
+        // Every move of a balanced rule set wins against exactly half of the others.
+        for name in &rules.moves {
+            let wins = rules.moves.iter().filter(|other| rules.judge(name, other) == Outcome::Win).count();
+            assert_eq!(wins, 2, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_parse_normalizes_names() {
+        let rules = RuleSet::parse(r#"{"name": "Coin", "moves": [" Heads", "TAILS"], "beats": {"heads": ["Tails"]}}"#).unwrap();
+        assert_eq!(rules.moves, ["heads", "tails"]);
+        assert_eq!(rules.find_move("  Tails\n"), Some("tails"));
+        assert_eq!(rules.find_move("edge"), None);
+        assert_eq!(rules.choice_list(), "heads, or tails");
+    }
+
+    #[test]
+    fn test_parse_rejects_inconsistent_graphs() {
+        let error = |moves: &str, beats: &str| {
+            RuleSet::parse(&format!(r#"{{"name": "x", "moves": {}, "beats": {}}}"#, moves, beats)).unwrap_err()
+        };
+        assert_eq!(error(r#"["a"]"#, "{}"), "needs at least two moves, found 1");
+        assert_eq!(error(r#"["a", "b", "a"]"#, "{}"), "move 'a' is listed twice");
+        assert_eq!(error(r#"["a", "b"]"#, r#"{"a": ["c"]}"#), "'c' in `beats` is not one of the moves");
+        assert_eq!(error(r#"["a", "b"]"#, r#"{"a": ["a", "b"]}"#), "'a' beats itself");
+        assert_eq!(error(r#"["a", "b"]"#, r#"{"a": ["b"], "b": ["a"]}"#), "'a' and 'b' beat each other");
+        assert_eq!(error(r#"["a", "b", "c"]"#, r#"{"a": ["b"], "b": ["c"]}"#), "nothing decides between 'a' and 'c'");
+        assert!(RuleSet::parse(r#"{"name": "x"}"#).is_err());
+        assert_eq!(
+            error(r#"["rock", "paper"]"#, r#"{"Rock": ["paper"], "rock": [], "paper": ["rock"]}"#),
+            "'rock' has more than one entry in `beats`"
+        );
+    }
+
+    #[test]
+    fn test_load_built_in_or_file() {
+        assert_eq!(load("rpsls").unwrap().name, "Rock-Paper-Scissors-Lizard-Spock");
+        let error = load("no/such/rules.json").unwrap_err();
+        assert!(error.starts_with("'no/such/rules.json' is not a built-in rule set (classic, rpsls)"), "{}", error);
+    }
+}
@@ -0,0 +1,260 @@
+// Module: Player Statistics
+// Remembers every player's games between runs: matches and rounds won, lost and tied,
+// winning streaks and which moves they like to play. Everything is saved in
+// `guess_game_stats.json` inside the data directory (see storage.rs), next to the
+// tutorial's progress file. The `stats` and `leaderboard` commands print it.
+
+use super::rules::RuleSet;
+use super::{Outcome, Session};
+use crate::progress;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+pub const FILE_NAME: &str = "guess_game_stats.json";
+
+// `BTreeMap` keeps the players and moves sorted, so the saved file is stable and easy to diff.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    #[serde(default)]
+    pub players: BTreeMap<String, PlayerStats>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerStats {
+    pub matches_won: u32,
+    pub matches_lost: u32,
+    pub rounds_won: u32,
+    pub rounds_lost: u32,
+    pub rounds_tied: u32,
+    // Rounds won in a row, up to the last round played, and the longest such run.
+    pub current_streak: u32,
+    pub best_streak: u32,
+    // Move -> how often the player chose it.
+    pub moves: BTreeMap<String, u32>,
+    // Unix seconds (see `storage::unix_now`).
+    pub last_played_at: u64,
+}
+
+impl PlayerStats {
+    pub fn matches(&self) -> u32 {
+        self.matches_won + self.matches_lost
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.rounds_won + self.rounds_lost + self.rounds_tied
+    }
+
+    // Share of the finished matches that the player won, in percent.
+    pub fn match_win_percent(&self) -> u32 {
+        (self.matches_won * 100).checked_div(self.matches()).unwrap_or(0)
+    }
+}
+
+// Asks for the player's name, the same way the user_input lesson does. An empty answer
+// means the default name (`RUST_PRACTICE_USER` or the login name, see progress.rs).
+pub fn read_player_name(input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<String> {
+    writeln!(out, "Please enter your name:")?;
+    let mut name_input = String::new();
+    input.read_line(&mut name_input)?;
+    let name = name_input.trim();
+    let name = if name.is_empty() { progress::current_learner(None) } else { name.to_string() };
+    writeln!(out, "Hello, {}! Nice to meet you.", name)?;
+    Ok(name)
+}
+
+impl Stats {
+    pub fn path() -> io::Result<PathBuf> {
+        Ok(storage::data_dir()?.join(FILE_NAME))
+    }
+
+    pub fn load() -> io::Result<Stats> {
+        storage::load_json(&Stats::path()?)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        storage::save_json(&Stats::path()?, self)
+    }
+
+    pub fn player(&self, name: &str) -> Option<&PlayerStats> {
+        self.players.get(name)
+    }
+
+    // Adds the rounds and matches of one session to `player`'s record.
+    pub fn record_session(&mut self, player: &str, rules: &RuleSet, session: &Session, at: u64) {
+        let stats = self.players.entry(player.to_string()).or_default();
+        stats.matches_won += session.matches.wins;
+        stats.matches_lost += session.matches.losses;
+        for round in &session.rounds {
+            match rules.judge(&round.player, &round.computer) {
+                Outcome::Win => {
+                    stats.rounds_won += 1;
+                    stats.current_streak += 1;
+                    stats.best_streak = stats.best_streak.max(stats.current_streak);
+                }
+                Outcome::Lose => {
+                    stats.rounds_lost += 1;
+                    stats.current_streak = 0;
+                }
+                Outcome::Tie => {
+                    stats.rounds_tied += 1;
+                    stats.current_streak = 0;
+                }
+            }
+            *stats.moves.entry(round.player.clone()).or_insert(0) += 1;
+        }
+        stats.last_played_at = at;
+    }
+
+    // Players ranked by matches won, then by their share of matches won, then by name.
+    pub fn ranking(&self) -> Vec<(&str, &PlayerStats)> {
+        let mut ranking: Vec<(&str, &PlayerStats)> =
+            self.players.iter().map(|(name, stats)| (name.as_str(), stats)).collect();
+        ranking.sort_by(|(name_a, a), (name_b, b)| {
+            b.matches_won
+                .cmp(&a.matches_won)
+                .then(b.match_win_percent().cmp(&a.match_win_percent()))
+                .then(name_a.cmp(name_b))
+        });
+        ranking
+    }
+
+    // The report shown by `stats <player>`.
+    pub fn write_player(&self, name: &str, out: &mut dyn Write) -> io::Result<()> {
+        let Some(stats) = self.player(name) else {
+            return writeln!(out, "No games recorded for {}.", name);
+        };
+        writeln!(out, "Statistics for {}", name)?;
+        writeln!(
+            out,
+            "  Matches:  {} played, {} won, {} lost ({}% won)",
+            stats.matches(),
+            stats.matches_won,
+            stats.matches_lost,
+            stats.match_win_percent()
+        )?;
+        writeln!(
+            out,
+            "  Rounds:   {} played, {} won, {} lost, {} tied",
+            stats.rounds(),
+            stats.rounds_won,
+            stats.rounds_lost,
+            stats.rounds_tied
+        )?;
+        writeln!(out, "  Streaks:  {} round wins in a row now, best {}", stats.current_streak, stats.best_streak)?;
+        let total: u32 = stats.moves.values().sum();
+        let mut moves: Vec<(&String, &u32)> = stats.moves.iter().collect();
+        moves.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let moves: Vec<String> = moves
+            .iter()
+            .map(|(name, count)| format!("{} {} ({}%)", name, count, (*count * 100).checked_div(total).unwrap_or(0)))
+            .collect();
+        writeln!(out, "  Moves:    {}", if moves.is_empty() { String::from("none yet") } else { moves.join(", ") })?;
+        writeln!(out, "  Last played {}", storage::format_timestamp(stats.last_played_at))
+    }
+
+    // The table shown by `leaderboard`.
+    pub fn write_leaderboard(&self, out: &mut dyn Write) -> io::Result<()> {
+        if self.players.is_empty() {
+            return writeln!(out, "No games recorded yet. Play one with `guess_game`!");
+        }
+        writeln!(out, "Rank  {:<16} Matches   Won  Win %  Rounds W-L-T    Best streak", "Player")?;
+        for (rank, (name, stats)) in self.ranking().iter().enumerate() {
+            writeln!(
+                out,
+                "{:>4}  {:<16} {:>7} {:>5} {:>5}%  {:<15} {:>11}",
+                rank + 1,
+                name,
+                stats.matches(),
+                stats.matches_won,
+                stats.match_win_percent(),
+                format!("{}-{}-{}", stats.rounds_won, stats.rounds_lost, stats.rounds_tied),
+                stats.best_streak
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guess_game::strategy::Round;
+    use crate::guess_game::Scoreboard;
+
+    fn session(moves: &[(&str, &str)], wins: u32, losses: u32) -> Session {
+        Session {
+            rounds: moves
+                .iter()
+                .map(|(player, computer)| Round { player: player.to_string(), computer: computer.to_string() })
+                .collect(),
+            matches: Scoreboard { wins, losses, ties: 0 },
+        }
+    }
+
+    fn report(stats: &Stats, player: Option<&str>) -> String {
+        let mut output: Vec<u8> = Vec::new();
+        match player {
+            Some(name) => stats.write_player(name, &mut output).unwrap(),
+            None => stats.write_leaderboard(&mut output).unwrap(),
+        }
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_record_session_counts_rounds_streaks_and_moves() {
+        let rules = RuleSet::classic();
+        let mut stats = Stats::default();
+        let won_three = [("rock", "scissors"), ("paper", "rock"), ("paper", "rock"), ("rock", "rock"), ("rock", "scissors")];
+        stats.record_session("alice", &rules, &session(&won_three, 2, 0), 100);
+        stats.record_session("alice", &rules, &session(&[("scissors", "scissors"), ("scissors", "rock")], 0, 1), 200);
+        let alice = stats.player("alice").unwrap();
+        assert_eq!((alice.matches_won, alice.matches_lost), (2, 1));
+        assert_eq!((alice.rounds_won, alice.rounds_lost, alice.rounds_tied), (4, 1, 2));
+        assert_eq!((alice.current_streak, alice.best_streak), (0, 3));
+        assert_eq!(alice.moves["rock"], 3);
+        assert_eq!(alice.moves["scissors"], 2);
+        assert_eq!(alice.last_played_at, 200);
+    }
+
+    #[test]
+    fn test_player_report() {
+        let mut stats = Stats::default();
+        stats.record_session("alice", &RuleSet::classic(), &session(&[("rock", "scissors"), ("rock", "paper")], 1, 0), 0);
+        assert_eq!(
+            report(&stats, Some("alice")),
+            "Statistics for alice\n  \
+               Matches:  1 played, 1 won, 0 lost (100% won)\n  \
+               Rounds:   2 played, 1 won, 1 lost, 0 tied\n  \
+               Streaks:  0 round wins in a row now, best 1\n  \
+               Moves:    rock 2 (100%)\n  \
+               Last played 1970-01-01 00:00 UTC\n"
+        );
+        assert_eq!(report(&stats, Some("bob")), "No games recorded for bob.\n");
+    }
+
+    #[test]
+    fn test_leaderboard_ranks_by_matches_won() {
+        let rules = RuleSet::classic();
+        let mut stats = Stats::default();
+        assert!(report(&stats, None).starts_with("No games recorded yet."));
+        stats.record_session("carol", &rules, &session(&[], 1, 3), 0);
+        stats.record_session("alice", &rules, &session(&[], 2, 2), 0);
+        stats.record_session("bob", &rules, &session(&[], 2, 0), 0);
+        let names: Vec<&str> = stats.ranking().iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["bob", "alice", "carol"]);
+        let table = report(&stats, None);
+        assert!(table.contains("   1  bob                    2     2   100%  0-0-0                     0\n"), "{}", table);
+    }
+
+    #[test]
+    fn test_read_player_name_is_trimmed() {
+        let mut output: Vec<u8> = Vec::new();
+        let name = read_player_name(&mut "  Alice \n".as_bytes(), &mut output).unwrap();
+        assert_eq!(name, "Alice");
+        assert_eq!(String::from_utf8(output).unwrap(), "Please enter your name:\nHello, Alice! Nice to meet you.\n");
+    }
+}
@@ -0,0 +1,269 @@
+// Module: Computer Strategies
+// How the computer picks its move. Every strategy implements the `Strategy` trait and sees
+// the rounds played so far, so it can try to learn from the player's habits:
+//   random      every move equally likely (the original behaviour)
+//   frequency   counters the move the player has chosen most often
+//   markov      counters the move that usually followed the player's last few moves
+//   wsls        "win-stay, lose-shift": repeats a winning move, otherwise counters the player's last move
+// Strategies that have nothing to go on yet fall back to a random move.
+
+use super::rules::RuleSet;
+use super::Outcome;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::collections::HashMap;
+use std::fmt;
+
+// The moves of one round. `player` is the opponent the strategy is playing against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub player: String,
+    pub computer: String,
+}
+
+pub trait Strategy {
+    // Picks the computer's next move from `rules.moves`. `history` holds every earlier round
+    // of the session, oldest first.
+    fn choose(&mut self, rules: &RuleSet, history: &[Round], rng: &mut dyn RngCore) -> String;
+}
+
+// The strategies that can be picked with `--strategy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StrategyKind {
+    #[default]
+    Random,
+    Frequency,
+    Markov,
+    WinStayLoseShift,
+}
+
+impl StrategyKind {
+    pub const ALL: [StrategyKind; 4] =
+        [StrategyKind::Random, StrategyKind::Frequency, StrategyKind::Markov, StrategyKind::WinStayLoseShift];
+
+    // The name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            StrategyKind::Random => "random",
+            StrategyKind::Frequency => "frequency",
+            StrategyKind::Markov => "markov",
+            StrategyKind::WinStayLoseShift => "wsls",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            StrategyKind::Random => "picks every move with the same chance",
+            StrategyKind::Frequency => "counters your most frequent move",
+            StrategyKind::Markov => "counters the move that usually follows your last two moves",
+            StrategyKind::WinStayLoseShift => "keeps a winning move, otherwise counters your last move",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<StrategyKind> {
+        let name = name.trim().to_lowercase();
+        StrategyKind::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    // A fresh strategy of this kind, with nothing learned yet.
+    pub fn build(self) -> Box<dyn Strategy> {
+        match self {
+            StrategyKind::Random => Box::new(UniformRandom),
+            StrategyKind::Frequency => Box::new(FrequencyCounter),
+            StrategyKind::Markov => Box::new(MarkovPredictor { order: 2 }),
+            StrategyKind::WinStayLoseShift => Box::new(WinStayLoseShift),
+        }
+    }
+}
+
+impl fmt::Display for StrategyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+pub struct UniformRandom;
+
+impl Strategy for UniformRandom {
+    fn choose(&mut self, rules: &RuleSet, _history: &[Round], rng: &mut dyn RngCore) -> String {
+        random_move(rules, rng)
+    }
+}
+
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn choose(&mut self, rules: &RuleSet, history: &[Round], rng: &mut dyn RngCore) -> String {
+        match most_common(rules, history.iter().map(|round| round.player.as_str())) {
+            Some(predicted) => counter(rules, predicted, rng),
+            None => random_move(rules, rng),
+        }
+    }
+}
+
+// Looks at the player's last `order` moves and finds what the player did next every other
+// time that sequence came up. Without a match it tries a shorter sequence, down to plain
+// frequency counting.
+pub struct MarkovPredictor {
+    pub order: usize,
+}
+
+impl Strategy for MarkovPredictor {
+    fn choose(&mut self, rules: &RuleSet, history: &[Round], rng: &mut dyn RngCore) -> String {
+        let moves: Vec<&str> = history.iter().map(|round| round.player.as_str()).collect();
+        for length in (0..=self.order.min(moves.len())).rev() {
+            let context = &moves[moves.len() - length..];
+            let followers = moves
+                .windows(length + 1)
+                .filter(|window| &window[..length] == context)
+                .map(|window| window[length]);
+            if let Some(predicted) = most_common(rules, followers) {
+                return counter(rules, predicted, rng);
+            }
+        }
+        random_move(rules, rng)
+    }
+}
+
+pub struct WinStayLoseShift;
+
+impl Strategy for WinStayLoseShift {
+    fn choose(&mut self, rules: &RuleSet, history: &[Round], rng: &mut dyn RngCore) -> String {
+        match history.last() {
+            Some(last) if rules.judge(&last.computer, &last.player) == Outcome::Win => last.computer.clone(),
+            Some(last) => counter(rules, &last.player, rng),
+            None => random_move(rules, rng),
+        }
+    }
+}
+
+fn random_move(rules: &RuleSet, rng: &mut dyn RngCore) -> String {
+    rules.moves.choose(rng).cloned().unwrap_or_default()
+}
+
+// A move that beats `predicted`, picked at random if several do. If no move beats it,
+// copying it at least gives a tie.
+fn counter(rules: &RuleSet, predicted: &str, rng: &mut dyn RngCore) -> String {
+    let winners: Vec<&String> = rules.moves.iter().filter(|name| rules.beats(name, predicted)).collect();
+    match winners.choose(rng) {
+        Some(winner) => winner.to_string(),
+        None => predicted.to_string(),
+    }
+}
+
+// The most frequent move, or `None` for an empty list. Ties go to the move listed first in the rules.
+fn most_common<'a>(rules: &'a RuleSet, moves: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for name in moves {
+        *counts.entry(name).or_insert(0) += 1;
+    }
+    let best = counts.values().copied().max()?;
+    rules.moves.iter().map(String::as_str).find(|name| counts.get(name) == Some(&best))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guess_game::Scoreboard;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn rounds(pairs: &[(&str, &str)]) -> Vec<Round> {
+        pairs
+            .iter()
+            .map(|(player, computer)| Round { player: player.to_string(), computer: computer.to_string() })
+            .collect()
+    }
+
+    // Plays `rounds` rounds between `kind` and a scripted player, scored from the computer's side.
+    fn simulate(kind: StrategyKind, rounds: usize, mut player: impl FnMut(usize, &mut StdRng) -> &'static str) -> Scoreboard {
+        let rules = RuleSet::classic();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut strategy = kind.build();
+        let mut history: Vec<Round> = Vec::new();
+        let mut score = Scoreboard::default();
+        for number in 0..rounds {
+            let computer = strategy.choose(&rules, &history, &mut rng);
+            let player = player(number, &mut rng).to_string();
+            score.record(rules.judge(&computer, &player));
+            history.push(Round { player, computer });
+        }
+        score
+    }
+
+    // Plays rock 60% of the time and paper or scissors otherwise.
+    fn biased(_: usize, rng: &mut StdRng) -> &'static str {
+        match rng.gen_range(0..10) {
+            0..=5 => "rock",
+            6 | 7 => "paper",
+            _ => "scissors",
+        }
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for kind in StrategyKind::ALL {
+            assert_eq!(StrategyKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(StrategyKind::from_name(" WSLS "), Some(StrategyKind::WinStayLoseShift));
+        assert_eq!(StrategyKind::from_name("psychic"), None);
+    }
+
+    #[test]
+    fn test_frequency_counters_the_most_common_move() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let history = rounds(&[("rock", "rock"), ("scissors", "rock"), ("scissors", "paper")]);
+        assert_eq!(FrequencyCounter.choose(&RuleSet::classic(), &history, &mut rng), "rock");
+    }
+
+    #[test]
+    fn test_markov_uses_the_longest_context_it_has_seen() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut markov = MarkovPredictor { order: 2 };
+        // After "rock, rock" this player went on with scissors, so the computer plays rock.
+        let history = rounds(&[("rock", "x"), ("rock", "x"), ("scissors", "x"), ("paper", "x"), ("rock", "x"), ("rock", "x")]);
+        assert_eq!(markov.choose(&RuleSet::classic(), &history, &mut rng), "rock");
+        // "paper, paper" never came up before; after "paper" alone came rock, so play paper.
+        let history = rounds(&[("paper", "x"), ("rock", "x"), ("paper", "x"), ("paper", "x")]);
+        assert_eq!(markov.choose(&RuleSet::classic(), &history, &mut rng), "paper");
+    }
+
+    #[test]
+    fn test_win_stay_lose_shift() {
+        let rules = RuleSet::classic();
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(WinStayLoseShift.choose(&rules, &rounds(&[("rock", "paper")]), &mut rng), "paper");
+        assert_eq!(WinStayLoseShift.choose(&rules, &rounds(&[("rock", "scissors")]), &mut rng), "paper");
+        assert_eq!(WinStayLoseShift.choose(&rules, &rounds(&[("scissors", "scissors")]), &mut rng), "rock");
+    }
+
+    #[test]
+    fn test_without_history_every_strategy_plays_a_valid_move() {
+        let rules = crate::guess_game::rules::built_in("rpsls").unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        for kind in StrategyKind::ALL {
+            let choice = kind.build().choose(&rules, &[], &mut rng);
+            assert!(rules.find_move(&choice).is_some(), "{}: {}", kind, choice);
+        }
+    }
+
+    #[test]
+    fn test_predictors_beat_a_biased_player() {
+        let random = simulate(StrategyKind::Random, 3000, biased);
+        // Against uniform random play, no bias can be exploited: wins and losses are about even.
+        assert!(random.wins.abs_diff(random.losses) < 200, "{:?}", random);
+        for kind in [StrategyKind::Frequency, StrategyKind::Markov] {
+            let score = simulate(kind, 3000, biased);
+            assert!(score.wins > score.losses + 600, "{}: {:?}", kind, score);
+        }
+    }
+
+    #[test]
+    fn test_markov_learns_a_repeating_pattern() {
+        let cycle = |number: usize, _: &mut StdRng| ["rock", "rock", "paper", "scissors"][number % 4];
+        let markov = simulate(StrategyKind::Markov, 400, cycle);
+        assert!(markov.wins > 380, "{:?}", markov);
+        let frequency = simulate(StrategyKind::Frequency, 400, cycle);
+        assert!(frequency.wins < markov.wins, "{:?}", frequency);
+    }
+}
@@ -0,0 +1,371 @@
+// Module: Strategy Tournament
+// `guess_game tournament` lets the computer strategies (guess_game/strategy.rs) play each
+// other, without any input or output in between. Every pair of strategies plays `games`
+// matches; the pairs take turns, so each one's matches are spread over the whole tournament.
+// Each match starts with an empty history, so a strategy learns its opponent during the match
+// and the frequency and markov strategies, which scan the whole history every round, take
+// the same time per match however many games are played.
+//
+// After every match both strategies' Elo ratings are updated: everyone starts at 1500, and
+// the winner takes points from the loser, more of them the less likely the win was. A match
+// that is still undecided after `MAX_ROUNDS` rounds (two strategies can tie forever) is a
+// draw. The results are printed as a ranked table and a win matrix, or saved as CSV.
+
+use super::rules::RuleSet;
+use super::strategy::{Round, Strategy, StrategyKind};
+use super::{MatchFormat, Outcome, Scoreboard};
+use rand::RngCore;
+use std::io::{self, Write};
+
+pub const START_RATING: f64 = 1500.0;
+// How far one match can move a rating.
+pub const K_FACTOR: f64 = 32.0;
+pub const MAX_ROUNDS: u32 = 100;
+
+// What the tournament is played with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub strategies: Vec<StrategyKind>,
+    // Matches per pair of strategies.
+    pub games: u32,
+    pub format: MatchFormat,
+    pub rules: RuleSet,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            strategies: StrategyKind::ALL.to_vec(),
+            games: 100,
+            format: MatchFormat::BestOf(5),
+            rules: RuleSet::classic(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub strategy: StrategyKind,
+    pub rating: f64,
+    pub won: u32,
+    pub lost: u32,
+    pub drawn: u32,
+}
+
+impl Standing {
+    pub fn games(&self) -> u32 {
+        self.won + self.lost + self.drawn
+    }
+
+    // Draws count as half a win, as in chess.
+    pub fn score_percent(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.0;
+        }
+        (f64::from(self.won) + f64::from(self.drawn) / 2.0) * 100.0 / f64::from(self.games())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Results {
+    pub config: Config,
+    // In the order of `config.strategies`.
+    pub standings: Vec<Standing>,
+    // `wins[a][b]`: matches strategy `a` won against strategy `b`.
+    pub wins: Vec<Vec<u32>>,
+}
+
+// The chance that a player rated `rating` beats one rated `opponent`.
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10_f64.powf((opponent - rating) / 400.0))
+}
+
+// Both ratings after a match; `score` is 1 if the first player won, 0.5 for a draw, 0 for a loss.
+pub fn update_ratings(first: f64, second: f64, score: f64) -> (f64, f64) {
+    let change = K_FACTOR * (score - expected_score(first, second));
+    (first + change, second - change)
+}
+
+// Two strategies that keep playing each other. Each sees the rounds from its own side.
+struct Pairing {
+    first: usize,
+    second: usize,
+    strategies: (Box<dyn Strategy>, Box<dyn Strategy>),
+    // This match's rounds so far. `player` is the opponent's move in both, as
+    // `Strategy::choose` expects.
+    histories: (Vec<Round>, Vec<Round>),
+}
+
+impl Pairing {
+    // Plays the next match of the tournament, starting from an empty history.
+    fn play_match(&mut self, rules: &RuleSet, format: MatchFormat, rng: &mut dyn RngCore) -> Outcome {
+        self.histories.0.clear();
+        self.histories.1.clear();
+        self.play(rules, format, rng)
+    }
+
+    // Plays one match on top of the rounds in `histories` and returns its outcome for
+    // `first`, `Tie` for a draw.
+    fn play(&mut self, rules: &RuleSet, format: MatchFormat, rng: &mut dyn RngCore) -> Outcome {
+        let mut score = Scoreboard::default();
+        while score.rounds() < MAX_ROUNDS {
+            let first = self.strategies.0.choose(rules, &self.histories.0, rng);
+            let second = self.strategies.1.choose(rules, &self.histories.1, rng);
+            score.record(rules.judge(&first, &second));
+            self.histories.0.push(Round { player: second.clone(), computer: first.clone() });
+            self.histories.1.push(Round { player: first, computer: second });
+            if let Some(winner) = score.winner(format) {
+                return winner;
+            }
+        }
+        Outcome::Tie
+    }
+}
+
+// Plays the whole round robin. The same config and random numbers give the same results.
+pub fn run(config: &Config, rng: &mut dyn RngCore) -> Results {
+    let count = config.strategies.len();
+    let mut standings: Vec<Standing> = config
+        .strategies
+        .iter()
+        .map(|&strategy| Standing { strategy, rating: START_RATING, won: 0, lost: 0, drawn: 0 })
+        .collect();
+    let mut wins = vec![vec![0; count]; count];
+    let mut pairings = Vec::new();
+    for first in 0..count {
+        for second in first + 1..count {
+            let strategies = (config.strategies[first].build(), config.strategies[second].build());
+            pairings.push(Pairing { first, second, strategies, histories: (Vec::new(), Vec::new()) });
+        }
+    }
+    for _ in 0..config.games {
+        for pairing in &mut pairings {
+            let (first, second) = (pairing.first, pairing.second);
+            let outcome = pairing.play_match(&config.rules, config.format, rng);
+            let score = match outcome {
+                Outcome::Win => {
+                    wins[first][second] += 1;
+                    standings[first].won += 1;
+                    standings[second].lost += 1;
+                    1.0
+                }
+                Outcome::Lose => {
+                    wins[second][first] += 1;
+                    standings[second].won += 1;
+                    standings[first].lost += 1;
+                    0.0
+                }
+                Outcome::Tie => {
+                    standings[first].drawn += 1;
+                    standings[second].drawn += 1;
+                    0.5
+                }
+            };
+            let (a, b) = update_ratings(standings[first].rating, standings[second].rating, score);
+            standings[first].rating = a;
+            standings[second].rating = b;
+        }
+    }
+    Results { config: config.clone(), standings, wins }
+}
+
+impl Results {
+    // Indexes into `standings`, best rating first.
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.standings.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.standings[a], &self.standings[b]);
+            b.rating.total_cmp(&a.rating).then(a.strategy.name().cmp(b.strategy.name()))
+        });
+        order
+    }
+
+    pub fn write_table(&self, out: &mut dyn Write) -> io::Result<()> {
+        let config = &self.config;
+        writeln!(
+            out,
+            "Tournament: {} strategies, {} matches per pair ({}, {})",
+            config.strategies.len(),
+            config.games,
+            config.format,
+            config.rules.name
+        )?;
+        writeln!(out)?;
+        writeln!(out, "Rank  Strategy    Elo   Won  Lost  Drawn  Score")?;
+        for (rank, &index) in self.ranking().iter().enumerate() {
+            let standing = &self.standings[index];
+            writeln!(
+                out,
+                "{:>4}  {:<9} {:>5.0} {:>5} {:>5} {:>6} {:>5.1}%",
+                rank + 1,
+                standing.strategy.name(),
+                standing.rating,
+                standing.won,
+                standing.lost,
+                standing.drawn,
+                standing.score_percent()
+            )?;
+        }
+        writeln!(out)?;
+        writeln!(out, "Matches won by the row against the column:")?;
+        let names: Vec<&str> = self.standings.iter().map(|standing| standing.strategy.name()).collect();
+        write!(out, "{:<9}", "")?;
+        for name in &names {
+            write!(out, " {:>9}", name)?;
+        }
+        writeln!(out)?;
+        for (row, name) in names.iter().enumerate() {
+            write!(out, "{:<9}", name)?;
+            for column in 0..names.len() {
+                if row == column {
+                    write!(out, " {:>9}", "-")?;
+                } else {
+                    write!(out, " {:>9}", self.wins[row][column])?;
+                }
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    // One row per strategy in ranking order, with the win matrix as the `vs_<name>` columns.
+    pub fn write_csv(&self, out: &mut dyn Write) -> io::Result<()> {
+        write!(out, "rank,strategy,elo,won,lost,drawn,score_percent")?;
+        for standing in &self.standings {
+            write!(out, ",vs_{}", standing.strategy.name())?;
+        }
+        writeln!(out)?;
+        for (rank, &index) in self.ranking().iter().enumerate() {
+            let standing = &self.standings[index];
+            write!(
+                out,
+                "{},{},{:.1},{},{},{},{:.1}",
+                rank + 1,
+                standing.strategy.name(),
+                standing.rating,
+                standing.won,
+                standing.lost,
+                standing.drawn,
+                standing.score_percent()
+            )?;
+            for (column, wins) in self.wins[index].iter().enumerate() {
+                if column == index {
+                    write!(out, ",")?;
+                } else {
+                    write!(out, ",{}", wins)?;
+                }
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+
+    fn tournament(games: u32, seed: u64) -> Results {
+        run(&Config { games, ..Config::default() }, &mut rng::seeded_rng(seed))
+    }
+
+    #[test]
+    fn test_elo() {
+        assert_eq!(expected_score(1500.0, 1500.0), 0.5);
+        // A 200 point gap gives the favourite about a 76% chance.
+        assert!((expected_score(1700.0, 1500.0) - 0.7597).abs() < 0.0001);
+        assert_eq!(update_ratings(1500.0, 1500.0, 1.0), (1516.0, 1484.0));
+        assert_eq!(update_ratings(1500.0, 1500.0, 0.5), (1500.0, 1500.0));
+        // Beating a much stronger player is worth more than beating an equal one.
+        let (upset, _) = update_ratings(1300.0, 1700.0, 1.0);
+        assert!(upset - 1300.0 > 16.0);
+    }
+
+    #[test]
+    fn test_every_pair_plays_every_game() {
+        let results = tournament(20, 1);
+        let count = results.standings.len();
+        assert_eq!(count, StrategyKind::ALL.len());
+        for (a, standing) in results.standings.iter().enumerate() {
+            assert_eq!(standing.games(), 20 * (count as u32 - 1));
+            assert_eq!(standing.won, results.wins[a].iter().sum::<u32>());
+            for b in 0..count {
+                if a != b {
+                    assert!(results.wins[a][b] + results.wins[b][a] <= 20);
+                }
+            }
+        }
+        // Elo only moves points around.
+        let total: f64 = results.standings.iter().map(|standing| standing.rating).sum();
+        assert!((total - START_RATING * count as f64).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_every_match_starts_afresh() {
+        let mut pairing = Pairing {
+            first: 0,
+            second: 1,
+            strategies: (StrategyKind::Frequency.build(), StrategyKind::Markov.build()),
+            histories: (Vec::new(), Vec::new()),
+        };
+        let rules = RuleSet::classic();
+        let mut rng = rng::seeded_rng(4);
+        for _ in 0..20 {
+            pairing.play_match(&rules, MatchFormat::FirstTo(1), &mut rng);
+            // Only this match's rounds: ties, then the one that decided it.
+            let (last, earlier) = pairing.histories.0.split_last().unwrap();
+            assert!(earlier.iter().all(|round| round.player == round.computer), "{:?}", pairing.histories.0);
+            assert_ne!(last.player, last.computer);
+            assert_eq!(pairing.histories.1.len(), pairing.histories.0.len());
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_results() {
+        assert_eq!(tournament(10, 7), tournament(10, 7));
+        assert_ne!(tournament(10, 7).wins, tournament(10, 8).wins);
+    }
+
+    #[test]
+    fn test_endless_ties_are_a_draw() {
+        // After a tie, win-stay-lose-shift counters the other side's last move. Two copies
+        // that start from the same tie therefore keep playing the same move forever.
+        let tie = Round { player: String::from("rock"), computer: String::from("rock") };
+        let mut pairing = Pairing {
+            first: 0,
+            second: 1,
+            strategies: (StrategyKind::WinStayLoseShift.build(), StrategyKind::WinStayLoseShift.build()),
+            histories: (vec![tie.clone()], vec![tie]),
+        };
+        let outcome = pairing.play(&RuleSet::classic(), MatchFormat::FirstTo(1), &mut rng::seeded_rng(2));
+        assert_eq!(outcome, Outcome::Tie);
+        assert_eq!(pairing.histories.0.len() as u32, MAX_ROUNDS + 1);
+    }
+
+    #[test]
+    fn test_table_and_csv() {
+        let results = tournament(5, 3);
+        let mut table: Vec<u8> = Vec::new();
+        results.write_table(&mut table).unwrap();
+        let table = String::from_utf8(table).unwrap();
+        assert!(table.starts_with("Tournament: 4 strategies, 5 matches per pair (Best of 5, Rock-Paper-Scissors)\n"));
+        assert!(table.contains("Rank  Strategy    Elo   Won  Lost  Drawn  Score\n   1  "));
+        assert!(table.contains("Matches won by the row against the column:\n             random frequency    markov      wsls\n"));
+
+        let mut csv: Vec<u8> = Vec::new();
+        results.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "rank,strategy,elo,won,lost,drawn,score_percent,vs_random,vs_frequency,vs_markov,vs_wsls");
+        assert_eq!(lines.len(), 5);
+        let best = results.ranking()[0];
+        assert!(lines[1].starts_with(&format!("1,{},", results.standings[best].strategy)));
+        // Every row has an empty cell against itself.
+        for line in &lines[1..] {
+            let cells: Vec<&str> = line.split(',').collect();
+            assert_eq!(cells.len(), 11, "{}", line);
+            assert_eq!(cells.iter().filter(|cell| cell.is_empty()).count(), 1, "{}", line);
+        }
+    }
+}
@@ -0,0 +1,513 @@
+// Module: Terminal UI
+// A full-screen version of the game (`guess_game tui`), drawn with ratatui on top of crossterm.
+// It is split in three parts so that only the last one needs a real terminal:
+// - `App` holds the state of the game and changes it on a `Key` or a clock `tick`. It knows
+//   nothing about drawing, so the game logic can be tested by sending it keys.
+// - `draw` turns an `App` into widgets on any ratatui backend; the tests draw on ratatui's
+//   in-memory `TestBackend` and look at the characters on the screen.
+// - `run` is the event loop: it draws, waits for a key or the next tick, and repeats.
+//   `play` connects it to the terminal and to crossterm's keyboard events.
+//
+// The player picks a move with the arrow keys and Enter, or with its hotkey: the number in
+// front of it, or the highlighted letter of its name. A short countdown then runs before
+// both moves are shown, like "rock, paper, scissors, shoot!" in the schoolyard.
+
+use super::rules::RuleSet;
+use super::strategy::{Round, Strategy};
+use super::{Outcome, Scoreboard, Session, Settings};
+use crate::rng::AnyRng;
+use ratatui::backend::Backend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph};
+use ratatui::{Frame, Terminal};
+use std::io;
+use std::time::Duration;
+
+// Steps of the countdown ("3", "2", "1"), and how long each one is shown.
+pub const COUNTDOWN: u32 = 3;
+pub const TICK: Duration = Duration::from_millis(400);
+
+// The keys the game reacts to, independent of the terminal library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Left,
+    Right,
+    Enter,
+    Esc,
+    Char(char),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Phase {
+    // Waiting for the player's move.
+    Choosing,
+    // The player's move is locked in; `left` steps of the countdown remain.
+    Countdown { player: String, left: u32 },
+    // Someone has won the match; the player is asked for a rematch.
+    MatchOver(Outcome),
+    // The player has left the game.
+    Quit,
+}
+
+pub struct App {
+    settings: Settings,
+    strategy: Box<dyn Strategy>,
+    rng: AnyRng,
+    // The computer's move for the current round, chosen before the player's.
+    computer_choice: String,
+    hotkeys: Vec<Option<char>>,
+    // Index of the highlighted move.
+    pub selected: usize,
+    pub phase: Phase,
+    // The score of the current match.
+    pub score: Scoreboard,
+    // Every round and match so far, for the history panel and the statistics.
+    pub session: Session,
+}
+
+impl App {
+    pub fn new(settings: Settings, rng: AnyRng) -> App {
+        let strategy = settings.strategy.build();
+        let hotkeys = hotkeys(&settings.rules);
+        let mut app = App {
+            settings,
+            strategy,
+            rng,
+            computer_choice: String::new(),
+            hotkeys,
+            selected: 0,
+            phase: Phase::Choosing,
+            score: Scoreboard::default(),
+            session: Session::default(),
+        };
+        app.start_round();
+        app
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.phase == Phase::Quit
+    }
+
+    // The hotkey letter of each move, if it has one (see `hotkeys`).
+    pub fn hotkeys(&self) -> &[Option<char>] {
+        &self.hotkeys
+    }
+
+    pub fn handle(&mut self, key: Key) {
+        if matches!(key, Key::Esc | Key::Char('q')) {
+            self.phase = Phase::Quit;
+            return;
+        }
+        match self.phase {
+            Phase::Choosing => {
+                let count = self.settings.rules.moves.len();
+                match key {
+                    Key::Left => self.selected = (self.selected + count - 1) % count,
+                    Key::Right => self.selected = (self.selected + 1) % count,
+                    Key::Enter | Key::Char(' ') => self.lock_in(self.selected),
+                    Key::Char(letter) => {
+                        if let Some(index) = self.move_for_hotkey(letter) {
+                            self.selected = index;
+                            self.lock_in(index);
+                        }
+                    }
+                    Key::Esc => {}
+                }
+            }
+            Phase::MatchOver(_) => match key {
+                Key::Enter | Key::Char('y') => {
+                    self.score = Scoreboard::default();
+                    self.phase = Phase::Choosing;
+                }
+                Key::Char('n') => self.phase = Phase::Quit,
+                _ => {}
+            },
+            // Keys pressed during the countdown are ignored.
+            Phase::Countdown { .. } | Phase::Quit => {}
+        }
+    }
+
+    // One step of the clock: advances the countdown and plays the round when it reaches zero.
+    pub fn tick(&mut self) {
+        let Phase::Countdown { player, left } = &mut self.phase else {
+            return;
+        };
+        if *left > 1 {
+            *left -= 1;
+            return;
+        }
+        let player = player.clone();
+        let outcome = self.settings.rules.judge(&player, &self.computer_choice);
+        self.session.rounds.push(Round { player, computer: self.computer_choice.clone() });
+        self.score.record(outcome);
+        match self.score.winner(self.settings.format) {
+            Some(winner) => {
+                self.session.matches.record(winner);
+                self.phase = Phase::MatchOver(winner);
+            }
+            None => self.phase = Phase::Choosing,
+        }
+        self.start_round();
+    }
+
+    // The last round played, with its outcome.
+    pub fn last_round(&self) -> Option<(&Round, Outcome)> {
+        let round = self.session.rounds.last()?;
+        Some((round, self.settings.rules.judge(&round.player, &round.computer)))
+    }
+
+    fn start_round(&mut self) {
+        self.computer_choice = self.strategy.choose(&self.settings.rules, &self.session.rounds, &mut self.rng);
+    }
+
+    fn lock_in(&mut self, index: usize) {
+        let player = self.settings.rules.moves[index].clone();
+        self.phase = Phase::Countdown { player, left: COUNTDOWN };
+    }
+
+    fn move_for_hotkey(&self, key: char) -> Option<usize> {
+        let key = key.to_ascii_lowercase();
+        if let Some(digit) = key.to_digit(10) {
+            return (1..=self.hotkeys.len()).contains(&(digit as usize)).then(|| digit as usize - 1);
+        }
+        self.hotkeys.iter().position(|hotkey| *hotkey == Some(key))
+    }
+}
+
+// A letter for each move: the first letter of its name that no earlier move has taken, so
+// "rock, paper, scissors, lizard, spock" get r, p, s, l and o. The digits always work too.
+pub fn hotkeys(rules: &RuleSet) -> Vec<Option<char>> {
+    let mut taken: Vec<char> = vec!['q', 'y', 'n'];
+    rules
+        .moves
+        .iter()
+        .map(|name| {
+            let letter = name.chars().find(|letter| letter.is_ascii_alphabetic() && !taken.contains(letter));
+            taken.extend(letter);
+            letter
+        })
+        .collect()
+}
+
+// Runs the game until the player quits. `next_key` waits at most the given time for a key
+// and returns `None` if none came; the clock ticks whenever that happens during a countdown.
+pub fn run<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    next_key: &mut dyn FnMut(Duration) -> io::Result<Option<Key>>,
+) -> io::Result<()> {
+    while !app.is_over() {
+        terminal.draw(|frame| draw(frame, app))?;
+        let counting = matches!(app.phase, Phase::Countdown { .. });
+        // Outside the countdown nothing changes on its own, so there is no need to wake up often.
+        let wait = if counting { TICK } else { Duration::from_secs(60) };
+        match next_key(wait)? {
+            Some(key) => app.handle(key),
+            None if counting => app.tick(),
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+// Plays in the real terminal, then gives it back the way it was.
+pub fn play(settings: Settings, rng: AnyRng) -> io::Result<Session> {
+    let mut app = App::new(settings, rng);
+    let mut terminal = ratatui::try_init()?;
+    let result = run(&mut terminal, &mut app, &mut read_key);
+    ratatui::try_restore()?;
+    result.map(|_| app.session)
+}
+
+// The next key press from crossterm, if one comes within `wait`.
+fn read_key(wait: Duration) -> io::Result<Option<Key>> {
+    if !event::poll(wait)? {
+        return Ok(None);
+    }
+    let Event::Key(press) = event::read()? else {
+        return Ok(None);
+    };
+    // Some terminals also report releases; a move must only be played once.
+    if press.kind != KeyEventKind::Press {
+        return Ok(None);
+    }
+    Ok(match press.code {
+        KeyCode::Left | KeyCode::Up => Some(Key::Left),
+        KeyCode::Right | KeyCode::Down | KeyCode::Tab => Some(Key::Right),
+        KeyCode::Enter => Some(Key::Enter),
+        KeyCode::Esc => Some(Key::Esc),
+        KeyCode::Char(letter) => Some(Key::Char(letter)),
+        _ => None,
+    })
+}
+
+// The screen: a title bar, then moves | arena | score, then the history and a line of help.
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [title, middle, history, help] =
+        Layout::vertical([Constraint::Length(3), Constraint::Length(9), Constraint::Min(3), Constraint::Length(1)])
+            .areas(frame.area());
+    let [moves, arena, score] =
+        Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(40), Constraint::Percentage(30)])
+            .areas(middle);
+
+    let settings = app.settings();
+    let heading = format!("{} | {} | computer: {}", settings.rules.name, settings.format, settings.strategy);
+    frame.render_widget(
+        Paragraph::new(heading)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).title(" Guessing Game ")),
+        title,
+    );
+    draw_moves(frame, app, moves);
+    draw_arena(frame, app, arena);
+    draw_score(frame, app, score);
+    draw_history(frame, app, history);
+
+    let keys = match app.phase {
+        Phase::MatchOver(_) => "y/Enter: rematch   n: stop   q: quit",
+        _ => "←/→: choose   Enter: play   or press a move's key   q: quit",
+    };
+    frame.render_widget(Paragraph::new(keys).style(Style::default().fg(Color::DarkGray)), help);
+}
+
+fn draw_moves(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .settings()
+        .rules
+        .moves
+        .iter()
+        .zip(app.hotkeys())
+        .enumerate()
+        .map(|(index, (name, hotkey))| {
+            let mut spans = vec![Span::raw(format!("{} ", index + 1))];
+            // The hotkey letter is underlined inside the name.
+            match hotkey.and_then(|letter| name.find(letter)) {
+                Some(at) => {
+                    let (before, rest) = name.split_at(at);
+                    let (letter, after) = rest.split_at(1);
+                    spans.push(Span::raw(before.to_string()));
+                    spans.push(Span::styled(letter.to_string(), Style::default().add_modifier(Modifier::UNDERLINED)));
+                    spans.push(Span::raw(after.to_string()));
+                }
+                None => spans.push(Span::raw(name.clone())),
+            }
+            let item = ListItem::new(Line::from(spans));
+            if index == app.selected && app.phase == Phase::Choosing {
+                item.style(Style::default().fg(Color::Black).bg(Color::Yellow))
+            } else {
+                item
+            }
+        })
+        .collect();
+    frame.render_widget(List::new(items).block(Block::default().borders(Borders::ALL).title(" Your move ")), area);
+}
+
+fn draw_arena(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title(" Arena ");
+    match &app.phase {
+        Phase::Countdown { player, left } => {
+            let [text, bar] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(block.inner(area));
+            frame.render_widget(block, area);
+            let lines = vec![
+                Line::from(format!("You picked {}.", player)),
+                Line::from(""),
+                Line::from(Span::styled(format!("{}...", left), Style::default().add_modifier(Modifier::BOLD))),
+            ];
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), text);
+            let done = f64::from(COUNTDOWN - left) / f64::from(COUNTDOWN);
+            frame.render_widget(Gauge::default().ratio(done).label("").gauge_style(Style::default().fg(Color::Cyan)), bar);
+        }
+        phase => {
+            let mut lines = match app.last_round() {
+                Some((round, outcome)) => vec![
+                    Line::from(format!("You: {}", round.player)),
+                    Line::from(format!("Computer: {}", round.computer)),
+                    Line::from(Span::styled(outcome.message(), outcome_style(outcome))),
+                ],
+                None => vec![Line::from("Pick your move!")],
+            };
+            if let Phase::MatchOver(winner) = phase {
+                let verdict = if *winner == Outcome::Win { "You won the match!" } else { "The computer won the match." };
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(verdict, outcome_style(*winner))));
+                lines.push(Line::from("Play again? (y/n)"));
+            }
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center).block(block), area);
+        }
+    }
+}
+
+fn draw_score(frame: &mut Frame, app: &App, area: Rect) {
+    let needed = app.settings().format.wins_needed();
+    let lines = vec![
+        Line::from(format!("You       {} / {}", app.score.wins, needed)),
+        Line::from(format!("Computer  {} / {}", app.score.losses, needed)),
+        Line::from(format!("Ties      {}", app.score.ties)),
+        Line::from(""),
+        Line::from(format!("Matches   {} - {}", app.session.matches.wins, app.session.matches.losses)),
+    ];
+    frame.render_widget(Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Score ")), area);
+}
+
+// The most recent rounds first, as many as fit.
+fn draw_history(frame: &mut Frame, app: &App, area: Rect) {
+    let rules = &app.settings().rules;
+    let rounds = &app.session.rounds;
+    let items: Vec<ListItem> = rounds
+        .iter()
+        .enumerate()
+        .rev()
+        .take(area.height.saturating_sub(2) as usize)
+        .map(|(index, round)| {
+            let outcome = rules.judge(&round.player, &round.computer);
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:>3}. {:<10} vs {:<10} ", index + 1, round.player, round.computer)),
+                Span::styled(outcome.message(), outcome_style(outcome)),
+            ]))
+        })
+        .collect();
+    frame.render_widget(List::new(items).block(Block::default().borders(Borders::ALL).title(" History ")), area);
+}
+
+fn outcome_style(outcome: Outcome) -> Style {
+    match outcome {
+        Outcome::Win => Style::default().fg(Color::Green),
+        Outcome::Lose => Style::default().fg(Color::Red),
+        Outcome::Tie => Style::default().fg(Color::Yellow),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guess_game::replay::Log;
+    use crate::guess_game::rules;
+    use crate::guess_game::MatchFormat;
+    use crate::rng::RngKind;
+    use ratatui::backend::TestBackend;
+
+    fn app(format: MatchFormat) -> App {
+        App::new(Settings { format, ..Settings::default() }, RngKind::Std.seeded(1))
+    }
+
+    // Locks in a move and lets the countdown run out.
+    fn play_round(app: &mut App, key: Key) {
+        app.handle(key);
+        for _ in 0..COUNTDOWN {
+            app.tick();
+        }
+    }
+
+    fn screen(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let mut text = String::new();
+        for y in 0..buffer.area.height {
+            for x in 0..buffer.area.width {
+                text.push_str(buffer[(x, y)].symbol());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    #[test]
+    fn test_hotkeys() {
+        assert_eq!(hotkeys(&RuleSet::classic()), [Some('r'), Some('p'), Some('s')]);
+        assert_eq!(hotkeys(&rules::built_in("rpsls").unwrap()), [Some('r'), Some('p'), Some('s'), Some('l'), Some('o')]);
+    }
+
+    #[test]
+    fn test_arrow_keys_wrap_around_and_enter_locks_in() {
+        let mut app = app(MatchFormat::BestOf(1));
+        app.handle(Key::Left);
+        assert_eq!(app.selected, 2);
+        app.handle(Key::Right);
+        app.handle(Key::Right);
+        assert_eq!(app.selected, 1);
+        app.handle(Key::Enter);
+        assert_eq!(app.phase, Phase::Countdown { player: String::from("paper"), left: COUNTDOWN });
+        // The countdown cannot be interrupted by another move.
+        app.handle(Key::Char('r'));
+        app.tick();
+        assert_eq!(app.phase, Phase::Countdown { player: String::from("paper"), left: COUNTDOWN - 1 });
+    }
+
+    #[test]
+    fn test_rounds_are_played_until_the_match_is_won() {
+        let mut app = app(MatchFormat::FirstTo(2));
+        while !matches!(app.phase, Phase::MatchOver(_)) {
+            play_round(&mut app, Key::Char('s'));
+            assert_eq!(app.session.rounds.last().unwrap().player, "scissors");
+        }
+        let Phase::MatchOver(winner) = app.phase else { unreachable!() };
+        assert_eq!(app.score.winner(MatchFormat::FirstTo(2)), Some(winner));
+        assert_eq!(app.session.matches.rounds(), 1);
+        assert_eq!(app.score.rounds() as usize, app.session.rounds.len());
+
+        app.handle(Key::Char('y'));
+        assert_eq!((app.phase.clone(), app.score), (Phase::Choosing, Scoreboard::default()));
+        play_round(&mut app, Key::Char('2'));
+        assert_eq!(app.session.rounds.last().unwrap().player, "paper");
+        app.handle(Key::Char('q'));
+        assert!(app.is_over());
+
+        // The computer chose once per round, so the session replays from its seed.
+        let log = Log {
+            player: String::from("tester"),
+            seed: 1,
+            rng: RngKind::Std,
+            settings: app.settings().clone(),
+            rounds: app.session.rounds,
+        };
+        assert_eq!(log.verify(), Ok(log.rounds.len()));
+    }
+
+    #[test]
+    fn test_screen_shows_moves_score_and_history() {
+        let mut app = app(MatchFormat::BestOf(3));
+        let start = screen(&app);
+        assert!(start.contains("Rock-Paper-Scissors | Best of 3 | computer: random"));
+        assert!(start.contains("1 rock") && start.contains("3 scissors"));
+        assert!(start.contains("Pick your move!"));
+        assert!(start.contains("You       0 / 2"));
+
+        app.handle(Key::Char('r'));
+        assert!(screen(&app).contains("You picked rock."));
+        assert!(screen(&app).contains("3..."));
+        for _ in 0..COUNTDOWN {
+            app.tick();
+        }
+        let (round, outcome) = app.last_round().unwrap();
+        let after = screen(&app);
+        assert!(after.contains(&format!("Computer: {}", round.computer)));
+        assert!(after.contains(outcome.message()));
+        assert!(after.contains(&format!("  1. rock       vs {:<10} {}", round.computer, outcome.message())));
+    }
+
+    #[test]
+    fn test_run_ticks_while_no_key_is_pressed() {
+        let mut app = app(MatchFormat::BestOf(1));
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        // `None` is a timeout: the countdown advances only then.
+        let mut script = vec![Some(Key::Char('p')), None, None, None, Some(Key::Char('n'))].into_iter();
+        let mut played = 0;
+        run(&mut terminal, &mut app, &mut |_| {
+            played += 1;
+            Ok(script.next().unwrap_or(Some(Key::Char('q'))))
+        })
+        .unwrap();
+        assert!(app.is_over());
+        // Best of 1: ties are replayed, so the match is only over if the round was decided.
+        assert_eq!(app.session.rounds.len(), 1);
+        assert!(played >= 5);
+    }
+}
@@ -0,0 +1,87 @@
+// Module: Input Sources
+// Lessons and games read their input from any `BufRead` instead of calling `io::stdin()`
+// directly. Normally that is the keyboard, but `--input "Alice\n30\n"` or `--answers <file>`
+// provide the answers up front, so interactive code can run in scripts, CI and tests.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum InputSource {
+    // Read from the keyboard (standard input).
+    #[default]
+    Stdin,
+    // Answers given on the command line with `--input`.
+    Text(String),
+    // Answers read from a file given with `--answers`.
+    File(PathBuf),
+}
+
+impl InputSource {
+    // Builds the source for `--input <text>`. Escapes such as `\n` are expanded,
+    // because most shells pass them through literally.
+    pub fn from_text(text: &str) -> InputSource {
+        InputSource::Text(unescape(text))
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::Text(text) => Ok(Box::new(Cursor::new(text.clone().into_bytes()))),
+            InputSource::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+        }
+    }
+}
+
+// Expands `\n`, `\t` and `\\`. Any other backslash sequence is kept as it is.
+pub fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape("Alice\\n30\\n"), "Alice\n30\n");
+        assert_eq!(unescape("a\\tb\\\\c"), "a\tb\\c");
+        assert_eq!(unescape("keep \\x and \\"), "keep \\x and \\");
+        assert_eq!(unescape("already\nreal"), "already\nreal");
+    }
+
+    #[test]
+    fn test_text_source_reads_lines_until_exhausted() {
+        let mut input = InputSource::from_text("Alice\\n30\\n").open().unwrap();
+        let mut text = String::new();
+        assert_eq!(input.read_line(&mut text).unwrap(), 6);
+        assert_eq!(input.read_line(&mut text).unwrap(), 3);
+        assert_eq!(input.read_line(&mut text).unwrap(), 0);
+        assert_eq!(text, "Alice\n30\n");
+    }
+
+    #[test]
+    fn test_missing_answers_file_is_an_error() {
+        let source = InputSource::File(PathBuf::from("does/not/exist.txt"));
+        assert!(source.open().is_err());
+    }
+}
@@ -0,0 +1,153 @@
+// Module: Lesson
+// Every tutorial topic implements the `Lesson` trait defined here. `main` no longer calls
+// each `demonstrate_*` function by hand; it asks the registry (see registry.rs) for the
+// lessons and runs them. Listing, filtering and ordering all build on the same information.
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// The chapters follow the "Topics to be covered" outline in the README.
+// Some chapters (Structs and Enums, Modules, Error Handling) have no lessons yet,
+// but they are listed so tooling can show the whole outline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Chapter {
+    GettingStarted,
+    OwnershipAndBorrowing,
+    DataTypes,
+    Collections,
+    ControlFlow,
+    StructsAndEnums,
+    Modules,
+    ErrorHandling,
+    UserInput,
+}
+
+impl Chapter {
+    pub const ALL: [Chapter; 9] = [
+        Chapter::GettingStarted,
+        Chapter::OwnershipAndBorrowing,
+        Chapter::DataTypes,
+        Chapter::Collections,
+        Chapter::ControlFlow,
+        Chapter::StructsAndEnums,
+        Chapter::Modules,
+        Chapter::ErrorHandling,
+        Chapter::UserInput,
+    ];
+
+    // The chapter number used in the README (1 to 9).
+    pub fn number(self) -> u8 {
+        match self {
+            Chapter::GettingStarted => 1,
+            Chapter::OwnershipAndBorrowing => 2,
+            Chapter::DataTypes => 3,
+            Chapter::Collections => 4,
+            Chapter::ControlFlow => 5,
+            Chapter::StructsAndEnums => 6,
+            Chapter::Modules => 7,
+            Chapter::ErrorHandling => 8,
+            Chapter::UserInput => 9,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Chapter::GettingStarted => "Getting Started",
+            Chapter::OwnershipAndBorrowing => "Ownership and Borrowing",
+            Chapter::DataTypes => "Data Types In-Depth",
+            Chapter::Collections => "Collections",
+            Chapter::ControlFlow => "Control Flow",
+            Chapter::StructsAndEnums => "Structs and Enums",
+            Chapter::Modules => "Modules and Project Organization",
+            Chapter::ErrorHandling => "Error Handling",
+            Chapter::UserInput => "User Input",
+        }
+    }
+
+    // A short, lowercase name that is easy to type on the command line.
+    pub fn slug(self) -> &'static str {
+        match self {
+            Chapter::GettingStarted => "getting-started",
+            Chapter::OwnershipAndBorrowing => "ownership",
+            Chapter::DataTypes => "data-types",
+            Chapter::Collections => "collections",
+            Chapter::ControlFlow => "control-flow",
+            Chapter::StructsAndEnums => "structs-and-enums",
+            Chapter::Modules => "modules",
+            Chapter::ErrorHandling => "error-handling",
+            Chapter::UserInput => "user-input",
+        }
+    }
+
+    // Looks a chapter up by its slug or by its README number ("2" finds Ownership and Borrowing).
+    pub fn from_name(name: &str) -> Option<Chapter> {
+        let name = name.trim().to_lowercase();
+        Chapter::ALL
+            .into_iter()
+            .find(|chapter| chapter.slug() == name || chapter.number().to_string() == name)
+    }
+}
+
+impl fmt::Display for Chapter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}. {}", self.number(), self.title())
+    }
+}
+
+// A single tutorial topic.
+// `Sync` is required so lessons can live in a `static` list (see registry.rs).
+pub trait Lesson: Sync {
+    // A stable identifier, the same as the module name (e.g. "basic_syntax").
+    fn id(&self) -> &'static str;
+
+    // A human-readable title, matching the heading the lesson prints.
+    fn title(&self) -> &'static str;
+
+    fn chapter(&self) -> Chapter;
+
+    // Ids of the lessons that should be studied before this one.
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    // Lessons that wait for keyboard input return true, so they can be skipped
+    // in automated runs (`run --all --skip-interactive`).
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    // Runs the demonstration, reading any answers from `input` and writing everything
+    // it prints to `out`. `main` passes the keyboard and standard output (or scripted
+    // answers, see input.rs); tests pass a byte slice and a `Vec<u8>`.
+    fn run(&self, input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()>;
+
+    // The lesson's own source file (`include_str!("<id>.rs")`). The exporter (export.rs)
+    // turns its comments and code into a page of the published tutorial.
+    fn source(&self) -> &'static str;
+
+    // Answers used when the lesson is run without a person at the keyboard, e.g. to
+    // capture its output for the exported pages. Only interactive lessons need them.
+    fn example_input(&self) -> &'static str {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chapter_numbers_follow_readme_outline() {
+        for (index, chapter) in Chapter::ALL.iter().enumerate() {
+            assert_eq!(chapter.number() as usize, index + 1);
+        }
+    }
+
+    #[test]
+    fn test_chapter_from_name() {
+        assert_eq!(Chapter::from_name("ownership"), Some(Chapter::OwnershipAndBorrowing));
+        assert_eq!(Chapter::from_name("Control-Flow"), Some(Chapter::ControlFlow));
+        assert_eq!(Chapter::from_name("9"), Some(Chapter::UserInput));
+        assert_eq!(Chapter::from_name("closures"), None);
+    }
+}
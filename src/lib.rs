@@ -0,0 +1,42 @@
+// The RustPractice library.
+// All lessons and the reusable pieces (input handling, the random number helpers and the
+// game logic) live here. The three binaries are thin wrappers around it:
+//   src/main.rs                the tutorial itself (`cargo run -- list`)
+//   src/bin/guess_game.rs      rock-paper-scissors (`cargo run --bin guess_game`)
+//   src/bin/random_number.rs   random values (`cargo run --bin random_number`)
+// Integration tests in `tests/` use the library the same way the binaries do.
+
+// Lesson infrastructure
+pub mod cli;
+pub mod exercise;
+pub mod export;
+pub mod input;
+pub mod lesson;
+pub mod progress;
+pub mod quiz;
+pub mod registry;
+pub mod server;
+pub mod storage;
+pub mod toolchain;
+
+// Lessons
+pub mod basic_syntax;
+pub mod string_ownership;
+pub mod references_borrowing;
+pub mod more_borrowing_rules;
+pub mod references_and_dereferencing;
+pub mod floating_point_types;
+pub mod boolean_type;
+pub mod char_type;
+pub mod array_type;
+pub mod vector_type;
+pub mod control_flow;
+pub mod loops;
+pub mod user_input;
+pub mod original_main;
+// prac11.rs has been merged into basic_syntax.rs
+
+// Games and random numbers
+pub mod guess_game;
+pub mod random_number;
+pub mod rng;
@@ -1,34 +1,37 @@
 // Module: Loops
 // This module explains different types of loops in Rust: `loop`, `while`, and `for`.
 
-pub fn demonstrate_loops() {
-    println!("\n--- Loops: loop, while, for ---");
+use crate::lesson::{Chapter, Lesson};
+use std::io::{self, BufRead, Write};
+
+pub fn demonstrate_loops(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n--- Loops: loop, while, for ---")?;
 
     // --- `loop` expression ---
     // The `loop` keyword creates an infinite loop.
     // You must use `break` to exit a `loop`, or it will run forever.
     // `loop` can also be used as an expression to return a value from a broken loop.
-    println!("\nDemonstrating `loop` with break and returning a value:");
+    writeln!(out, "\nDemonstrating `loop` with break and returning a value:")?;
     let mut counter_loop = 0;
     let result_from_loop = loop {
         counter_loop += 1;
-        print!("."); // Print a dot for each iteration to show progress
+        write!(out, ".")?; // Print a dot for each iteration to show progress
         if counter_loop == 5 {
-            println!(" Counter in loop reached 5, breaking and returning value.");
+            writeln!(out, " Counter in loop reached 5, breaking and returning value.")?;
             break counter_loop * 2; // Exit loop and return counter_loop * 2
         }
     };
-    println!("Result returned from loop: {}", result_from_loop); // Should be 10
+    writeln!(out, "Result returned from loop: {}", result_from_loop)?; // Should be 10
 
     // --- `while` loop ---
     // A `while` loop executes as long as a condition remains true.
-    println!("\nDemonstrating `while` loop:");
+    writeln!(out, "\nDemonstrating `while` loop:")?;
     let mut counter_while = 0;
     while counter_while < 5 {
-        print!("{} ", counter_while);
+        write!(out, "{} ", counter_while)?;
         counter_while += 1;
     }
-    println!("\nCounter after while loop: {}", counter_while); // Should be 5
+    writeln!(out, "\nCounter after while loop: {}", counter_while)?; // Should be 5
 
     // --- `for` loop ---
     // A `for` loop is used to iterate over a collection or a range.
@@ -36,30 +39,30 @@ pub fn demonstrate_loops() {
     // because it handles the iteration variable's state internally.
 
     // Iterating over a range
-    println!("\nDemonstrating `for` loop with a range (1..5):"); // 1 up to (but not including) 5
+    writeln!(out, "\nDemonstrating `for` loop with a range (1..5):")?; // 1 up to (but not including) 5
     for i in 1..5 { // The range `1..5` creates numbers 1, 2, 3, 4
-        print!("{} ", i);
+        write!(out, "{} ", i)?;
     }
-    println!();
+    writeln!(out)?;
 
-    println!("Demonstrating `for` loop with an inclusive range (1..=5):"); // 1 up to (and including) 5
+    writeln!(out, "Demonstrating `for` loop with an inclusive range (1..=5):")?; // 1 up to (and including) 5
     for i in 1..=5 { // The range `1..=5` creates numbers 1, 2, 3, 4, 5
-        print!("{} ", i);
+        write!(out, "{} ", i)?;
     }
-    println!();
+    writeln!(out)?;
 
     // Iterating over an array (or vector)
     let arr_for_loop: [&str; 5] = ["Hello", "Blockchain", "Rust", "Programming", "Language"];
-    println!("\nDemonstrating `for` loop iterating over an array:");
+    writeln!(out, "\nDemonstrating `for` loop iterating over an array:")?;
     for element in arr_for_loop.iter() { // `.iter()` provides immutable references to elements
-        print!("[{}] ", element);
+        write!(out, "[{}] ", element)?;
     }
-    println!();
+    writeln!(out)?;
 
     // Using `enumerate()` to get both index and value
-    println!("\nDemonstrating `for` loop with `enumerate()`:");
+    writeln!(out, "\nDemonstrating `for` loop with `enumerate()`:")?;
     for (index, element) in arr_for_loop.iter().enumerate() {
-        println!("Index: {}, Value: {}", index, element);
+        writeln!(out, "Index: {}, Value: {}", index, element)?;
     }
 
     // Original prac13.rs functions:
@@ -92,4 +95,33 @@ pub fn demonstrate_loops() {
     //     }
     // }
     // The refactored module consolidates and expands these examples.
+    Ok(())
+}
+
+pub struct LoopsLesson;
+
+impl Lesson for LoopsLesson {
+    fn id(&self) -> &'static str {
+        "loops"
+    }
+
+    fn title(&self) -> &'static str {
+        "Loops: loop, while, for"
+    }
+
+    fn chapter(&self) -> Chapter {
+        Chapter::ControlFlow
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["control_flow"]
+    }
+
+    fn run(&self, _input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        demonstrate_loops(out)
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("loops.rs")
+    }
 }
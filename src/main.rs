@@ -1,90 +1,330 @@
-// Declare the modules
-mod basic_syntax;
-mod string_ownership;
-mod references_borrowing;
-mod more_borrowing_rules;
-mod references_and_dereferencing;
-mod floating_point_types;
-mod boolean_type;
-mod char_type;
-mod array_type;
-mod vector_type;
-mod control_flow;
-mod loops;
-mod user_input;
-mod random_number;
-mod guess_game;
-// Added this module
-// prac11.rs has been merged into basic_syntax.rs
+// The tutorial binary. The lessons themselves live in the library (see lib.rs);
+// this file only turns the command line into calls to it.
+
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::process;
+
+use rust_practice::cli::{
+    self, Command, ExerciseCommand, ExerciseOptions, ExportOptions, ResetOptions, RunOptions, RunSelection,
+};
+use rust_practice::exercise;
+use rust_practice::export;
+use rust_practice::lesson::{Chapter, Lesson};
+use rust_practice::progress::{self, Progress};
+use rust_practice::quiz;
+use rust_practice::registry;
+use rust_practice::server::{self, Limits};
+use rust_practice::storage;
 
 fn main() {
-    println!("Welcome to the Rust Tutorial!");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            process::exit(2);
+        }
+    };
 
-    // Call a function from the basic_syntax module
-    basic_syntax::demonstrate_basics();
+    match command {
+        Command::Help => {
+            println!("Welcome to the Rust Tutorial!\n");
+            println!("{}", cli::USAGE);
+        }
+        Command::List => list_lessons(),
+        Command::Search(keyword) => search_lessons(&keyword),
+        Command::Run(selection, options) => {
+            if let Err(message) = run_lessons(selection, &options) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Command::Quiz(None, _) => list_quizzes(),
+        Command::Quiz(Some(topic), options) => {
+            if let Err(message) = take_quiz(&topic, &options) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Command::Exercise(ExerciseCommand::List(options)) => {
+            if let Err(message) = list_exercises(&options) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Command::Exercise(ExerciseCommand::Check(names, options)) => match check_exercises(&names, &options) {
+            Ok(true) => {}
+            // The failures have been reported already; the exit code tells scripts about them.
+            Ok(false) => process::exit(1),
+            Err(message) => {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        },
+        Command::Export(topics, options) => {
+            if let Err(message) = export_lessons(&topics, &options) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Command::Serve { port } => {
+            if let Err(message) = serve_lessons(port) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Command::Progress { user } => {
+            if let Err(message) = show_progress(user.as_deref()) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Command::Reset(options) => {
+            if let Err(message) = reset_progress(&options) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+    }
+}
 
-    // Call a function from the string_ownership module
-    string_ownership::demonstrate_string_ownership();
+// Prints every chapter of the README outline with its lessons.
+// Every lesson registers itself in registry.rs, so adding a topic does not require changes here.
+fn list_lessons() {
+    for chapter in Chapter::ALL {
+        println!("{} ({})", chapter, chapter.slug());
+        let mut lessons = registry::by_chapter(chapter).peekable();
+        if lessons.peek().is_none() {
+            println!("    (to be added)");
+        }
+        for lesson in lessons {
+            print_lesson_line(lesson);
+        }
+    }
+}
 
-    // Call a function from the references_borrowing module
-    references_borrowing::demonstrate_references_and_borrowing();
+fn search_lessons(keyword: &str) {
+    let found = registry::search(keyword);
+    if found.is_empty() {
+        println!("No lessons match '{}'.", keyword);
+    }
+    for lesson in found {
+        print_lesson_line(lesson);
+    }
+}
 
-    // Call a function from the more_borrowing_rules module
-    more_borrowing_rules::demonstrate_more_borrowing_rules();
+fn print_lesson_line(lesson: &dyn Lesson) {
+    let interactive = if lesson.is_interactive() { " (interactive)" } else { "" };
+    let quiz = if quiz::has_quiz(lesson.id()) { " (quiz)" } else { "" };
+    println!("    {:<30} {}{}{}", lesson.id(), lesson.title(), interactive, quiz);
+}
 
-    // Call a function from the references_and_dereferencing module
-    references_and_dereferencing::demonstrate_references_and_dereferencing();
+fn run_lessons(selection: RunSelection, options: &RunOptions) -> Result<(), String> {
+    let lessons: Vec<&dyn Lesson> = match selection {
+        RunSelection::Topics(ids) => {
+            let mut lessons = Vec::new();
+            for id in ids {
+                let lesson = registry::find(&id).ok_or_else(|| unknown_topic(&id))?;
+                lessons.push(lesson);
+            }
+            lessons
+        }
+        RunSelection::Chapter(chapter) => {
+            let lessons: Vec<&dyn Lesson> = registry::by_chapter(chapter).collect();
+            if lessons.is_empty() {
+                return Err(format!("chapter {} has no lessons yet", chapter));
+            }
+            lessons
+        }
+        RunSelection::All => registry::study_order(),
+    };
 
-    // Call a function from the floating_point_types module
-    floating_point_types::demonstrate_floating_point_types();
+    let mut input = options
+        .input
+        .open()
+        .map_err(|error| format!("cannot open the answers: {}", error))?;
+    let learner = progress::current_learner(options.user.as_deref());
+    let mut progress = load_progress()?;
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    for lesson in lessons {
+        if options.skip_interactive && lesson.is_interactive() {
+            println!("\n(Skipping interactive lesson '{}')", lesson.id());
+            continue;
+        }
+        lesson
+            .run(&mut input, &mut out)
+            .map_err(|error| format!("lesson '{}' failed to write its output: {}", lesson.id(), error))?;
+        // Saved after every lesson, so stopping halfway through `--all` keeps what was done.
+        progress.record_lesson(&learner, lesson.id(), storage::unix_now());
+        progress.save().map_err(|error| format!("cannot save progress: {}", error))?;
+        if quiz::has_quiz(lesson.id()) {
+            println!("\n(Check what you learned with `quiz {}`)", lesson.id());
+        }
+    }
+    Ok(())
+}
 
-    // Call a function from the boolean_type module
-    boolean_type::demonstrate_boolean_type();
+fn list_quizzes() {
+    println!("Lessons with a quiz:");
+    for id in quiz::lessons_with_quizzes() {
+        if let Some(lesson) = registry::find(id) {
+            print_lesson_line(lesson);
+        }
+    }
+}
 
-    // Call a function from the char_type module
-    char_type::demonstrate_char_type();
+fn take_quiz(topic: &str, options: &RunOptions) -> Result<(), String> {
+    let lesson = registry::find(topic).ok_or_else(|| unknown_topic(topic))?;
+    let bank = quiz::bank_for(lesson.id())
+        .ok_or_else(|| format!("lesson '{}' has no quiz yet (use `quiz` to see which ones do)", lesson.id()))?;
 
-    // Call a function from the array_type module
-    array_type::demonstrate_array_type();
+    let mut input = options
+        .input
+        .open()
+        .map_err(|error| format!("cannot open the answers: {}", error))?;
+    let result = quiz::run_quiz(&bank, &mut input, &mut io::stdout().lock(), &mut rand::thread_rng())
+        .map_err(|error| format!("the quiz failed: {}", error))?;
 
-    // Call a function from the vector_type module
-    vector_type::demonstrate_vector_type();
+    let learner = progress::current_learner(options.user.as_deref());
+    let mut progress = load_progress()?;
+    progress.record_quiz(&learner, lesson.id(), result.score, result.total, storage::unix_now());
+    progress.save().map_err(|error| format!("cannot save progress: {}", error))
+}
 
-    // Call a function from the control_flow module
-    control_flow::demonstrate_control_flow();
+fn list_exercises(options: &ExerciseOptions) -> Result<(), String> {
+    let learner = progress::current_learner(options.user.as_deref());
+    let progress = load_progress()?;
+    let solved = progress.learner(&learner).map(|learner| &learner.exercises);
+    let dir = options.dir.clone().unwrap_or_else(exercise::default_dir);
+
+    println!("Exercises in {} (solve them, then run `exercise check <name>`):", dir.display());
+    for exercise in exercise::all() {
+        let done = solved.is_some_and(|solved| solved.contains_key(exercise.name));
+        println!("    [{}] {:<24} {} (lesson: {})", if done { "x" } else { " " }, exercise.name, exercise.summary, exercise.lesson);
+    }
+    Ok(())
+}
 
-    // Call a function from the loops module
-    loops::demonstrate_loops();
+// Returns whether every checked exercise passed.
+fn check_exercises(names: &[String], options: &ExerciseOptions) -> Result<bool, String> {
+    let exercises = if names.is_empty() {
+        exercise::all().iter().collect()
+    } else {
+        let mut exercises = Vec::new();
+        for name in names {
+            let found = exercise::find(name)
+                .ok_or_else(|| format!("unknown exercise '{}' (use `exercise list` to see them all)", name))?;
+            exercises.push(found);
+        }
+        exercises
+    };
 
-    // Call a function from the user_input module
-    // NOTE: This module will pause execution and wait for user input.
-    // To run through other modules quickly without typing, you might want to comment this line out temporarily.
-    user_input::demonstrate_user_input();
+    let dir = options.dir.clone().unwrap_or_else(exercise::default_dir);
+    let work_dir = env::temp_dir().join(format!("rust_practice_exercises_{}", process::id()));
+    let learner = progress::current_learner(options.user.as_deref());
+    let mut progress = load_progress()?;
+    let mut passed = 0;
+    for exercise in &exercises {
+        let verdict = exercise
+            .check(&dir, &work_dir.join(exercise.name))
+            .map_err(|error| format!("cannot check '{}': {} (is `rustc` installed?)", exercise.name, error))?;
+        exercise::write_verdict(exercise, &verdict, &mut io::stdout().lock())
+            .map_err(|error| format!("cannot print the result: {}", error))?;
+        if verdict.passed() {
+            passed += 1;
+            progress.record_exercise(&learner, exercise.name, storage::unix_now());
+        }
+    }
+    let _ = std::fs::remove_dir_all(&work_dir);
+    progress.save().map_err(|error| format!("cannot save progress: {}", error))?;
+
+    println!("\n{} of {} exercises passed.", passed, exercises.len());
+    Ok(passed == exercises.len())
+}
+
+fn export_lessons(topics: &[String], options: &ExportOptions) -> Result<(), String> {
+    let lessons = if topics.is_empty() {
+        registry::study_order()
+    } else {
+        let mut lessons = Vec::new();
+        for id in topics {
+            lessons.push(registry::find(id).ok_or_else(|| unknown_topic(id))?);
+        }
+        lessons
+    };
+    let written = export::export(&lessons, options.format, &options.out)
+        .map_err(|error| format!("cannot export to {}: {}", options.out.display(), error))?;
+    println!("Wrote {} files to {}.", written.len(), options.out.display());
+    Ok(())
+}
+
+fn serve_lessons(port: u16) -> Result<(), String> {
+    // Only this machine can connect: the playground runs whatever code it is sent.
+    let listener = std::net::TcpListener::bind(("127.0.0.1", port))
+        .map_err(|error| format!("cannot listen on port {}: {}", port, error))?;
+    let address = listener.local_addr().map_err(|error| error.to_string())?;
+    let limits = Limits::default();
+    println!("Serving the lessons on http://{}/ (press Ctrl+C to stop)", address);
+    println!(
+        "Playground limits: {} s to compile, {} s to run, {} MiB of memory",
+        limits.compile_time.as_secs(),
+        limits.run_time.as_secs(),
+        limits.memory_bytes / (1024 * 1024)
+    );
+    let _ = io::stdout().flush();
+    server::serve(listener, limits).map_err(|error| error.to_string())
+}
+
+fn load_progress() -> Result<Progress, String> {
+    Progress::load().map_err(|error| format!("cannot load progress: {}", error))
+}
+
+fn show_progress(user: Option<&str>) -> Result<(), String> {
+    let learner = progress::current_learner(user);
+    let progress = load_progress()?;
+    progress
+        .write_report(&learner, &mut io::stdout().lock())
+        .map_err(|error| format!("cannot print progress: {}", error))
+}
 
+fn reset_progress(options: &ResetOptions) -> Result<(), String> {
+    let learner = progress::current_learner(options.user.as_deref());
+    let what = if options.all { String::from("the progress of every learner") } else { format!("the progress of {}", learner) };
+    if !options.yes && !confirm(&format!("Forget {}? [y/N] ", what)) {
+        println!("Nothing was reset.");
+        return Ok(());
+    }
 
-    // The old code from main.rs, which can also be a module or integrated elsewhere.
-    // For now, I'll keep it here and we can decide where it fits best.
-    println!("\n--- Original Main.rs Demonstrations ---");
-    let string_literal:&str = "Hello, Blockchain!";
-    println!("{}", string_literal);
-    let mut string_var:String = String::from("Happy Coding!");
-    println!("{}", string_var);
-    string_var.push_str(" Enjoy Rust!");
-    println!("{}", string_var);
-    let employee_info:(&str, u8) = ("Mazhar", 20);
-    let (name, age) = employee_info;
-    println!("{} is {} years old.", name, age);
-    print_value_original_main(7);
-    let sum:u8 = add_original_main(12, 18);
-    println!("Sum: {}", sum);
+    let mut progress = load_progress()?;
+    if options.all {
+        progress.reset_all();
+    } else if !progress.reset_learner(&learner) {
+        println!("There is no recorded progress for {}.", learner);
+        return Ok(());
+    }
+    progress.save().map_err(|error| format!("cannot save progress: {}", error))?;
+    println!("Forgot {}.", what);
+    Ok(())
 }
 
-// These functions were originally in main.rs.
-// They are kept here for now. We might move them to a more appropriate module later.
-fn print_value_original_main(value:u8){
-    println!("Value from original main: {}", value);
+// Asks a yes/no question on the terminal. Anything but "y" or "yes" counts as no.
+fn confirm(question: &str) -> bool {
+    print!("{}", question);
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
 }
 
-fn add_original_main(x:u8, y:u8) -> u8 {
-    x + y // Implicit return
+fn unknown_topic(id: &str) -> String {
+    let suggestions: Vec<&str> = registry::search(id).iter().map(|lesson| lesson.id()).collect();
+    if suggestions.is_empty() {
+        format!("unknown topic '{}' (use `list` to see all lessons)", id)
+    } else {
+        format!("unknown topic '{}'. Did you mean: {}?", id, suggestions.join(", "))
+    }
 }
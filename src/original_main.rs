@@ -0,0 +1,60 @@
+// Module: Original Main.rs Demonstrations
+// This code used to live directly in `main.rs`. It mixes a few early examples
+// (string literals vs. `String`, tuples, simple functions) and now runs as its own lesson.
+
+use crate::lesson::{Chapter, Lesson};
+use std::io::{self, BufRead, Write};
+
+pub fn demonstrate_original_main(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n--- Original Main.rs Demonstrations ---")?;
+    let string_literal:&str = "Hello, Blockchain!";
+    writeln!(out, "{}", string_literal)?;
+    let mut string_var:String = String::from("Happy Coding!");
+    writeln!(out, "{}", string_var)?;
+    string_var.push_str(" Enjoy Rust!");
+    writeln!(out, "{}", string_var)?;
+    let employee_info:(&str, u8) = ("Mazhar", 20);
+    let (name, age) = employee_info;
+    writeln!(out, "{} is {} years old.", name, age)?;
+    print_value_original_main(out, 7)?;
+    let sum:u8 = add_original_main(12, 18);
+    writeln!(out, "Sum: {}", sum)?;
+    Ok(())
+}
+
+pub struct OriginalMainLesson;
+
+impl Lesson for OriginalMainLesson {
+    fn id(&self) -> &'static str {
+        "original_main"
+    }
+
+    fn title(&self) -> &'static str {
+        "Original Main.rs Demonstrations"
+    }
+
+    fn chapter(&self) -> Chapter {
+        Chapter::GettingStarted
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["basic_syntax"]
+    }
+
+    fn run(&self, _input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        demonstrate_original_main(out)
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("original_main.rs")
+    }
+}
+
+// These functions were originally in main.rs.
+fn print_value_original_main(out: &mut dyn Write, value:u8) -> io::Result<()> {
+    writeln!(out, "Value from original main: {}", value)
+}
+
+fn add_original_main(x:u8, y:u8) -> u8 {
+    x + y // Implicit return
+}
@@ -0,0 +1,314 @@
+// Module: Learner Progress
+// Records which lessons and exercises each learner has completed and when, and their quiz scores.
+// Everything is saved in `progress.json` inside the data directory (see storage.rs),
+// so several people can share one checkout and each keep their own record.
+
+use crate::lesson::Chapter;
+use crate::registry;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+pub const FILE_NAME: &str = "progress.json";
+
+// `BTreeMap` (instead of `HashMap`) keeps the keys sorted, so the saved file is stable
+// and easy to read or diff.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Progress {
+    #[serde(default)]
+    pub learners: BTreeMap<String, LearnerProgress>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LearnerProgress {
+    // Lesson id -> when it was completed.
+    #[serde(default)]
+    pub lessons: BTreeMap<String, Completion>,
+    // Exercise name -> when it was completed.
+    #[serde(default)]
+    pub exercises: BTreeMap<String, Completion>,
+    // Lesson id -> scores of its quiz.
+    #[serde(default)]
+    pub quizzes: BTreeMap<String, QuizRecord>,
+}
+
+// Timestamps are Unix seconds (see `storage::unix_now`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Completion {
+    pub first_completed_at: u64,
+    pub last_completed_at: u64,
+}
+
+impl Completion {
+    fn record(existing: Option<Completion>, at: u64) -> Completion {
+        match existing {
+            Some(completion) => Completion { last_completed_at: at, ..completion },
+            None => Completion { first_completed_at: at, last_completed_at: at },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QuizRecord {
+    pub attempts: u32,
+    pub best_score: usize,
+    pub last_score: usize,
+    // Number of questions in the quiz when it was last taken.
+    pub total: usize,
+    pub last_taken_at: u64,
+}
+
+// Completed vs. available lessons for one chapter of the README outline.
+#[derive(Debug, PartialEq)]
+pub struct ChapterSummary {
+    pub chapter: Chapter,
+    pub completed: usize,
+    pub total: usize,
+}
+
+// The learner name used when none is given with `--user`:
+// `RUST_PRACTICE_USER`, then the login name, then "learner".
+pub fn current_learner(explicit: Option<&str>) -> String {
+    if let Some(name) = explicit {
+        return name.trim().to_string();
+    }
+    ["RUST_PRACTICE_USER", "USER", "USERNAME"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .map(|name| name.trim().to_string())
+        .find(|name| !name.is_empty())
+        .unwrap_or_else(|| String::from("learner"))
+}
+
+impl Progress {
+    pub fn path() -> io::Result<PathBuf> {
+        Ok(storage::data_dir()?.join(FILE_NAME))
+    }
+
+    pub fn load() -> io::Result<Progress> {
+        storage::load_json(&Progress::path()?)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        storage::save_json(&Progress::path()?, self)
+    }
+
+    pub fn learner(&self, name: &str) -> Option<&LearnerProgress> {
+        self.learners.get(name)
+    }
+
+    pub fn record_lesson(&mut self, learner: &str, lesson_id: &str, at: u64) {
+        let lessons = &mut self.learners.entry(learner.to_string()).or_default().lessons;
+        let completion = Completion::record(lessons.get(lesson_id).copied(), at);
+        lessons.insert(lesson_id.to_string(), completion);
+    }
+
+    pub fn record_exercise(&mut self, learner: &str, exercise: &str, at: u64) {
+        let exercises = &mut self.learners.entry(learner.to_string()).or_default().exercises;
+        let completion = Completion::record(exercises.get(exercise).copied(), at);
+        exercises.insert(exercise.to_string(), completion);
+    }
+
+    pub fn record_quiz(&mut self, learner: &str, lesson_id: &str, score: usize, total: usize, at: u64) {
+        let quizzes = &mut self.learners.entry(learner.to_string()).or_default().quizzes;
+        let record = match quizzes.get(lesson_id) {
+            Some(old) => QuizRecord {
+                attempts: old.attempts + 1,
+                best_score: old.best_score.max(score),
+                last_score: score,
+                total,
+                last_taken_at: at,
+            },
+            None => QuizRecord { attempts: 1, best_score: score, last_score: score, total, last_taken_at: at },
+        };
+        quizzes.insert(lesson_id.to_string(), record);
+    }
+
+    // Removes one learner's record. Returns false if there was nothing to remove.
+    pub fn reset_learner(&mut self, learner: &str) -> bool {
+        self.learners.remove(learner).is_some()
+    }
+
+    pub fn reset_all(&mut self) {
+        self.learners.clear();
+    }
+
+    pub fn is_lesson_completed(&self, learner: &str, lesson_id: &str) -> bool {
+        self.learner(learner).is_some_and(|progress| progress.lessons.contains_key(lesson_id))
+    }
+
+    // One entry per chapter of the README outline, in order, including empty chapters.
+    pub fn chapter_summary(&self, learner: &str) -> Vec<ChapterSummary> {
+        Chapter::ALL
+            .iter()
+            .map(|&chapter| {
+                let lessons: Vec<_> = registry::by_chapter(chapter).collect();
+                let completed = lessons
+                    .iter()
+                    .filter(|lesson| self.is_lesson_completed(learner, lesson.id()))
+                    .count();
+                ChapterSummary { chapter, completed, total: lessons.len() }
+            })
+            .collect()
+    }
+
+    // Prints the per-chapter report shown by the `progress` command.
+    pub fn write_report(&self, learner: &str, out: &mut dyn Write) -> io::Result<()> {
+        let summary = self.chapter_summary(learner);
+        let completed: usize = summary.iter().map(|chapter| chapter.completed).sum();
+        let total: usize = summary.iter().map(|chapter| chapter.total).sum();
+        writeln!(out, "Progress for {}: {} of {} lessons completed", learner, completed, total)?;
+
+        for chapter in &summary {
+            writeln!(out)?;
+            if chapter.total == 0 {
+                writeln!(out, "{}  (to be added)", chapter.chapter)?;
+                continue;
+            }
+            writeln!(
+                out,
+                "{}  {}/{} {}",
+                chapter.chapter,
+                chapter.completed,
+                chapter.total,
+                progress_bar(chapter.completed, chapter.total)
+            )?;
+            for lesson in registry::by_chapter(chapter.chapter) {
+                match self.learner(learner).and_then(|progress| progress.lessons.get(lesson.id())) {
+                    Some(completion) => writeln!(
+                        out,
+                        "    [x] {:<30} completed {}",
+                        lesson.id(),
+                        storage::format_timestamp(completion.last_completed_at)
+                    )?,
+                    None => writeln!(out, "    [ ] {}", lesson.id())?,
+                }
+            }
+        }
+
+        if let Some(progress) = self.learner(learner).filter(|progress| !progress.exercises.is_empty()) {
+            writeln!(out, "\nExercises completed: {}", progress.exercises.len())?;
+            for (name, completion) in &progress.exercises {
+                writeln!(
+                    out,
+                    "    [x] {:<30} completed {}",
+                    name,
+                    storage::format_timestamp(completion.last_completed_at)
+                )?;
+            }
+        }
+
+        if let Some(progress) = self.learner(learner).filter(|progress| !progress.quizzes.is_empty()) {
+            writeln!(out, "\nQuizzes taken: {}", progress.quizzes.len())?;
+            for (lesson, record) in &progress.quizzes {
+                writeln!(
+                    out,
+                    "    {:<34} best {}/{}, last {}/{} ({} attempt{}, {})",
+                    lesson,
+                    record.best_score,
+                    record.total,
+                    record.last_score,
+                    record.total,
+                    record.attempts,
+                    if record.attempts == 1 { "" } else { "s" },
+                    storage::format_timestamp(record.last_taken_at)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// A ten-character bar such as "[######----]".
+fn progress_bar(completed: usize, total: usize) -> String {
+    let filled = (completed * 10).checked_div(total).unwrap_or(0);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(10 - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_lesson_keeps_first_and_last_completion() {
+        let mut progress = Progress::default();
+        progress.record_lesson("alice", "loops", 100);
+        progress.record_lesson("alice", "loops", 250);
+        let completion = progress.learner("alice").unwrap().lessons["loops"];
+        assert_eq!(completion, Completion { first_completed_at: 100, last_completed_at: 250 });
+        assert!(progress.is_lesson_completed("alice", "loops"));
+        assert!(!progress.is_lesson_completed("bob", "loops"));
+    }
+
+    #[test]
+    fn test_chapter_summary_follows_readme_outline() {
+        let mut progress = Progress::default();
+        progress.record_lesson("alice", "string_ownership", 1);
+        progress.record_lesson("alice", "references_borrowing", 2);
+        let summary = progress.chapter_summary("alice");
+        assert_eq!(summary.len(), 9);
+        assert_eq!(summary[1], ChapterSummary { chapter: Chapter::OwnershipAndBorrowing, completed: 2, total: 4 });
+        assert_eq!(summary[5], ChapterSummary { chapter: Chapter::StructsAndEnums, completed: 0, total: 0 });
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut progress = Progress::default();
+        progress.record_lesson("alice", "loops", 1);
+        progress.record_exercise("bob", "calculate_length", 2);
+        assert!(progress.reset_learner("alice"));
+        assert!(!progress.reset_learner("alice"));
+        assert!(progress.learner("bob").is_some());
+        progress.reset_all();
+        assert!(progress.learners.is_empty());
+    }
+
+    #[test]
+    fn test_record_quiz_keeps_best_and_last_score() {
+        let mut progress = Progress::default();
+        progress.record_quiz("alice", "string_ownership", 4, 5, 10);
+        progress.record_quiz("alice", "string_ownership", 2, 5, 20);
+        let record = progress.learner("alice").unwrap().quizzes["string_ownership"];
+        assert_eq!(record, QuizRecord { attempts: 2, best_score: 4, last_score: 2, total: 5, last_taken_at: 20 });
+
+        let mut output: Vec<u8> = Vec::new();
+        progress.write_report("alice", &mut output).unwrap();
+        let report = String::from_utf8(output).unwrap();
+        assert!(report.contains(
+            "\nQuizzes taken: 1\n    string_ownership                   best 4/5, last 2/5 (2 attempts, 1970-01-01 00:00 UTC)\n"
+        ));
+    }
+
+    #[test]
+    fn test_report() {
+        let mut progress = Progress::default();
+        progress.record_lesson("alice", "loops", 0);
+        let mut output: Vec<u8> = Vec::new();
+        progress.write_report("alice", &mut output).unwrap();
+        let report = String::from_utf8(output).unwrap();
+        let heading = format!("Progress for alice: 1 of {} lessons completed\n", registry::all().len());
+        assert!(report.starts_with(&heading));
+        assert!(report.contains("5. Control Flow  1/2 [#####-----]\n"));
+        assert!(report.contains("    [x] loops                          completed 1970-01-01 00:00 UTC\n"));
+        assert!(report.contains("    [ ] control_flow\n"));
+        assert!(report.contains("6. Structs and Enums  (to be added)\n"));
+    }
+
+    #[test]
+    fn test_missing_sections_default_to_empty() {
+        let progress: Progress =
+            serde_json::from_str(r#"{"learners":{"alice":{"lessons":{}}}}"#).unwrap();
+        assert!(progress.learner("alice").unwrap().exercises.is_empty());
+        assert!(progress.learner("alice").unwrap().quizzes.is_empty());
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(0, 4), "[----------]");
+        assert_eq!(progress_bar(4, 4), "[##########]");
+        assert_eq!(progress_bar(0, 0), "[----------]");
+    }
+}
@@ -0,0 +1,320 @@
+// Module: Quizzes
+// Short quizzes that check whether a lesson was understood. The questions live in data files
+// under `quizzes/` (one JSON file per lesson), so adding or fixing a question does not mean
+// touching Rust code. The files are built into the program with `include_str!`.
+//
+// Four kinds of questions are supported:
+//   multiple_choice     pick one of the options (answer with its number)
+//   will_it_compile     answer yes or no
+//   what_does_it_print  type the output
+//   fill_in_blank       type the code that replaces `____`
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Deserialize;
+use std::io::{self, BufRead, Write};
+
+// (lesson id, bank) for every lesson that has a quiz.
+const BANKS: &[(&str, &str)] = &[
+    ("string_ownership", include_str!("../quizzes/string_ownership.json")),
+    ("references_borrowing", include_str!("../quizzes/references_borrowing.json")),
+];
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct QuestionBank {
+    pub lesson: String,
+    pub questions: Vec<Question>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Question {
+    pub prompt: String,
+    // A snippet shown under the prompt (not used by every question).
+    #[serde(default)]
+    pub code: Option<String>,
+    pub explanation: String,
+    // `flatten` lets the kind-specific fields sit next to the common ones in the file.
+    #[serde(flatten)]
+    pub kind: QuestionKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QuestionKind {
+    // `answer` is the index of the correct option, counting from 0.
+    MultipleChoice { options: Vec<String>, answer: usize },
+    WillItCompile { compiles: bool },
+    WhatDoesItPrint { output: String },
+    // Every accepted answer for the blank.
+    FillInBlank { answers: Vec<String> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuizResult {
+    pub score: usize,
+    pub total: usize,
+}
+
+impl QuizResult {
+    pub fn percent(&self) -> usize {
+        (self.score * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+// The ids of the lessons that have a quiz, in the order they appear above.
+pub fn lessons_with_quizzes() -> impl Iterator<Item = &'static str> {
+    BANKS.iter().map(|(lesson, _)| *lesson)
+}
+
+pub fn has_quiz(lesson_id: &str) -> bool {
+    lessons_with_quizzes().any(|lesson| lesson == lesson_id)
+}
+
+// The question bank for a lesson, if it has one.
+// The built-in files are checked by the tests, so a broken one is a bug in the program.
+pub fn bank_for(lesson_id: &str) -> Option<QuestionBank> {
+    BANKS
+        .iter()
+        .find(|(lesson, _)| *lesson == lesson_id)
+        .map(|(lesson, text)| QuestionBank::parse(text).unwrap_or_else(|error| panic!("quizzes/{}.json: {}", lesson, error)))
+}
+
+impl QuestionBank {
+    // Reads a bank from JSON and checks that every question can actually be answered.
+    pub fn parse(text: &str) -> Result<QuestionBank, String> {
+        let bank: QuestionBank = serde_json::from_str(text).map_err(|error| error.to_string())?;
+        if bank.questions.is_empty() {
+            return Err(String::from("the bank has no questions"));
+        }
+        for (number, question) in bank.questions.iter().enumerate() {
+            question.validate().map_err(|problem| format!("question {}: {}", number + 1, problem))?;
+        }
+        Ok(bank)
+    }
+}
+
+impl Question {
+    fn validate(&self) -> Result<(), String> {
+        match &self.kind {
+            QuestionKind::MultipleChoice { options, .. } if options.len() < 2 => {
+                Err(format!("needs at least two options, found {}", options.len()))
+            }
+            QuestionKind::MultipleChoice { options, answer } if *answer >= options.len() => {
+                Err(format!("answer {} is not one of the {} options", answer, options.len()))
+            }
+            QuestionKind::FillInBlank { answers } if answers.is_empty() => Err(String::from("has no accepted answers")),
+            QuestionKind::FillInBlank { .. } if !self.code.as_deref().unwrap_or(&self.prompt).contains("____") => {
+                Err(String::from("has no `____` blank to fill in"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // A copy of the question with its options (if any) in random order.
+    pub fn shuffled<R: Rng>(&self, rng: &mut R) -> Question {
+        let mut question = self.clone();
+        if let QuestionKind::MultipleChoice { options, answer } = &mut question.kind {
+            let mut order: Vec<usize> = (0..options.len()).collect();
+            order.shuffle(rng);
+            let shuffled: Vec<String> = order.iter().map(|&index| options[index].clone()).collect();
+            *answer = order.iter().position(|&index| index == *answer).unwrap_or(0);
+            *options = shuffled;
+        }
+        question
+    }
+
+    // Checks a typed reply. Replies are trimmed; where it makes sense, case and
+    // extra spaces are ignored too.
+    pub fn is_correct(&self, reply: &str) -> bool {
+        let reply = reply.trim();
+        match &self.kind {
+            QuestionKind::MultipleChoice { options, answer } => match reply.parse::<usize>() {
+                Ok(number) => number == answer + 1,
+                Err(_) => options[*answer].eq_ignore_ascii_case(reply),
+            },
+            QuestionKind::WillItCompile { compiles } => match reply.to_lowercase().as_str() {
+                "y" | "yes" => *compiles,
+                "n" | "no" => !*compiles,
+                _ => false,
+            },
+            QuestionKind::WhatDoesItPrint { output } => collapse_spaces(reply) == collapse_spaces(output),
+            QuestionKind::FillInBlank { answers } => {
+                answers.iter().any(|accepted| collapse_spaces(accepted) == collapse_spaces(reply))
+            }
+        }
+    }
+
+    // The correct answer, as shown after a wrong reply.
+    pub fn correct_answer(&self) -> String {
+        match &self.kind {
+            QuestionKind::MultipleChoice { options, answer } => format!("{}) {}", answer + 1, options[*answer]),
+            QuestionKind::WillItCompile { compiles: true } => String::from("yes, it compiles"),
+            QuestionKind::WillItCompile { compiles: false } => String::from("no, it does not compile"),
+            QuestionKind::WhatDoesItPrint { output } => output.clone(),
+            QuestionKind::FillInBlank { answers } => answers.join(" or "),
+        }
+    }
+
+    fn write(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{}", self.prompt)?;
+        if let Some(code) = &self.code {
+            writeln!(out)?;
+            for line in code.lines() {
+                writeln!(out, "    {}", line)?;
+            }
+            writeln!(out)?;
+        }
+        match &self.kind {
+            QuestionKind::MultipleChoice { options, .. } => {
+                for (number, option) in options.iter().enumerate() {
+                    writeln!(out, "  {}) {}", number + 1, option)?;
+                }
+                write!(out, "Your answer (number): ")
+            }
+            QuestionKind::WillItCompile { .. } => write!(out, "Does it compile? (yes/no): "),
+            QuestionKind::WhatDoesItPrint { .. } => write!(out, "Output: "),
+            QuestionKind::FillInBlank { .. } => write!(out, "The blank is: "),
+        }
+    }
+}
+
+fn collapse_spaces(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Asks every question of the bank in random order, reading one line per answer from `input`,
+// and prints feedback with the explanation after each one.
+// Running out of input counts the remaining questions as unanswered (wrong).
+pub fn run_quiz<R: Rng>(
+    bank: &QuestionBank,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    rng: &mut R,
+) -> io::Result<QuizResult> {
+    let mut questions: Vec<Question> = bank.questions.iter().map(|question| question.shuffled(rng)).collect();
+    questions.shuffle(rng);
+
+    writeln!(out, "\n--- Quiz: {} ({} questions) ---", bank.lesson, questions.len())?;
+    let mut score = 0;
+    for (number, question) in questions.iter().enumerate() {
+        writeln!(out, "\nQuestion {} of {}", number + 1, questions.len())?;
+        question.write(out)?;
+        out.flush()?;
+
+        let mut reply = String::new();
+        let answered = input.read_line(&mut reply)? > 0;
+        if !answered {
+            writeln!(out)?;
+        }
+        if answered && question.is_correct(&reply) {
+            score += 1;
+            writeln!(out, "Correct!")?;
+        } else {
+            writeln!(out, "Not quite. The answer is: {}", question.correct_answer())?;
+        }
+        writeln!(out, "Why: {}", question.explanation)?;
+    }
+
+    let result = QuizResult { score, total: questions.len() };
+    writeln!(out, "\nScore: {}/{} ({}%)", result.score, result.total, result.percent())?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use rand::rngs::mock::StepRng;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn question(kind: QuestionKind) -> Question {
+        Question { prompt: String::from("?"), code: Some(String::from("x.____()")), explanation: String::new(), kind }
+    }
+
+    #[test]
+    fn test_every_built_in_bank_is_valid_and_belongs_to_a_lesson() {
+        for lesson in lessons_with_quizzes() {
+            assert!(registry::find(lesson).is_some(), "no lesson called {}", lesson);
+            let bank = bank_for(lesson).unwrap();
+            assert_eq!(bank.lesson, lesson);
+        }
+        assert!(has_quiz("string_ownership"));
+        assert!(!has_quiz("loops"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unanswerable_questions() {
+        let wrong_answer = r#"{"lesson": "x", "questions": [
+            {"kind": "multiple_choice", "prompt": "?", "options": ["a", "b"], "answer": 2, "explanation": ""}]}"#;
+        assert_eq!(QuestionBank::parse(wrong_answer).unwrap_err(), "question 1: answer 2 is not one of the 2 options");
+        let no_blank = r#"{"lesson": "x", "questions": [
+            {"kind": "fill_in_blank", "prompt": "?", "answers": ["a"], "explanation": ""}]}"#;
+        assert!(QuestionBank::parse(no_blank).is_err());
+        assert!(QuestionBank::parse(r#"{"lesson": "x", "questions": []}"#).is_err());
+        assert!(QuestionBank::parse(r#"{"lesson": "x", "questions": [{"kind": "essay"}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_is_correct_for_each_kind() {
+        let choice = question(QuestionKind::MultipleChoice { options: vec![String::from("Move"), String::from("Copy")], answer: 1 });
+        assert!(choice.is_correct("2\n"));
+        assert!(choice.is_correct(" copy "));
+        assert!(!choice.is_correct("1"));
+
+        let compiles = question(QuestionKind::WillItCompile { compiles: false });
+        assert!(compiles.is_correct("No"));
+        assert!(compiles.is_correct("n"));
+        assert!(!compiles.is_correct("yes"));
+        assert!(!compiles.is_correct("maybe"));
+
+        let prints = question(QuestionKind::WhatDoesItPrint { output: String::from("hello, world") });
+        assert!(prints.is_correct("  hello,   world "));
+        assert!(!prints.is_correct("Hello, world"));
+
+        let blank = question(QuestionKind::FillInBlank { answers: vec![String::from("&mut")] });
+        assert!(blank.is_correct("&mut"));
+        assert!(!blank.is_correct("&"));
+    }
+
+    #[test]
+    fn test_shuffled_options_keep_the_right_answer() {
+        let choice = question(QuestionKind::MultipleChoice {
+            options: vec![String::from("a"), String::from("b"), String::from("c"), String::from("d")],
+            answer: 2,
+        });
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..20 {
+            let shuffled = choice.shuffled(&mut rng);
+            assert!(shuffled.is_correct("c"));
+            assert!(shuffled.correct_answer().ends_with(") c"));
+        }
+    }
+
+    #[test]
+    fn test_run_quiz_scores_and_explains() {
+        // With a `StepRng` that always returns 0, every shuffle moves the first item to the end:
+        // the first question is asked last, and its correct option is listed first.
+        let bank = bank_for("string_ownership").unwrap();
+        let mut rng = StepRng::new(0, 0);
+        let mut output: Vec<u8> = Vec::new();
+        let answers = "no\nno\n9\nclone\n1\n";
+        let result = run_quiz(&bank, &mut answers.as_bytes(), &mut output, &mut rng).unwrap();
+        assert_eq!(result, QuizResult { score: 4, total: 5 });
+
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.contains("--- Quiz: string_ownership (5 questions) ---"));
+        assert!(text.contains("Not quite. The answer is: yes, it compiles"));
+        assert!(text.contains("Why: `i32` implements `Copy`"));
+        assert!(text.ends_with("Score: 4/5 (80%)\n"));
+    }
+
+    #[test]
+    fn test_run_quiz_without_answers_scores_zero() {
+        let bank = bank_for("references_borrowing").unwrap();
+        let mut output: Vec<u8> = Vec::new();
+        let result = run_quiz(&bank, &mut "".as_bytes(), &mut output, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert_eq!(result, QuizResult { score: 0, total: bank.questions.len() });
+        assert_eq!(result.percent(), 0);
+    }
+}
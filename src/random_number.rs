@@ -1,12 +1,490 @@
+// Module: Random Numbers
+// Helpers behind the `random_number` binary (src/bin/random_number.rs).
+// `rng.r#gen()` produces a random value of whatever type is asked for;
+// `gen_range` produces one inside a range. (`r#gen` is a "raw identifier":
+// `gen` is a reserved keyword in the 2024 edition, so the method name needs the `r#` prefix.)
+//
+// Without options the binary prints one value of a few types, like the original program.
+// With options it is a small generator for scripts and test data: `--type` picks one of
+// Rust's primitive types, `--range` limits the values, `--count` sets how many, and the
+// values are printed as plain text, a JSON array or CSV (see random_number/cli.rs).
+// `--dist` samples from a probability distribution instead (random_number/distribution.rs),
+// and `--summary` and `--histogram` describe the values rather than print them
+// (random_number/report.rs).
+
+pub mod cli;
+pub mod dice;
+pub mod distribution;
+pub mod password;
+pub mod report;
+
+use distribution::Distribution;
 use rand::Rng;
-fn main(){
-    let mut rng = rand::thread_rng();
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+// Prints one random value of each kind, the way the original random_number.rs did.
+pub fn write_random_values<R: Rng>(out: &mut dyn Write, rng: &mut R) -> io::Result<()> {
     let random_number:u32 = rng.gen_range(1..100);
-    println!("{}",random_number);
+    writeln!(out, "{}",random_number)?;
     let random_int:i32 = rng.r#gen();
-    println!("{}",random_int);
+    writeln!(out, "{}",random_int)?;
     let random_float:f64 = rng.r#gen();
-    println!("{}",random_float);
+    writeln!(out, "{}",random_float)?;
     let random_bool:bool = rng.r#gen();
-    println!("{}",random_bool);
-}
\ No newline at end of file
+    writeln!(out, "{}",random_bool)?;
+    Ok(())
+}
+
+// The types `--type` can generate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F32,
+    F64,
+    Bool,
+    Char,
+}
+
+impl ValueType {
+    pub const ALL: [ValueType; 14] = [
+        ValueType::U8,
+        ValueType::U16,
+        ValueType::U32,
+        ValueType::U64,
+        ValueType::U128,
+        ValueType::I8,
+        ValueType::I16,
+        ValueType::I32,
+        ValueType::I64,
+        ValueType::I128,
+        ValueType::F32,
+        ValueType::F64,
+        ValueType::Bool,
+        ValueType::Char,
+    ];
+
+    // The name of the Rust type.
+    pub fn name(self) -> &'static str {
+        match self {
+            ValueType::U8 => "u8",
+            ValueType::U16 => "u16",
+            ValueType::U32 => "u32",
+            ValueType::U64 => "u64",
+            ValueType::U128 => "u128",
+            ValueType::I8 => "i8",
+            ValueType::I16 => "i16",
+            ValueType::I32 => "i32",
+            ValueType::I64 => "i64",
+            ValueType::I128 => "i128",
+            ValueType::F32 => "f32",
+            ValueType::F64 => "f64",
+            ValueType::Bool => "bool",
+            ValueType::Char => "char",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ValueType> {
+        let name = name.trim().to_lowercase();
+        ValueType::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    // The smallest and largest value of an unsigned type.
+    fn unsigned_bounds(self) -> Option<RangeInclusive<u128>> {
+        match self {
+            ValueType::U8 => Some(0..=u128::from(u8::MAX)),
+            ValueType::U16 => Some(0..=u128::from(u16::MAX)),
+            ValueType::U32 => Some(0..=u128::from(u32::MAX)),
+            ValueType::U64 => Some(0..=u128::from(u64::MAX)),
+            ValueType::U128 => Some(0..=u128::MAX),
+            _ => None,
+        }
+    }
+
+    fn signed_bounds(self) -> Option<RangeInclusive<i128>> {
+        match self {
+            ValueType::I8 => Some(i128::from(i8::MIN)..=i128::from(i8::MAX)),
+            ValueType::I16 => Some(i128::from(i16::MIN)..=i128::from(i16::MAX)),
+            ValueType::I32 => Some(i128::from(i32::MIN)..=i128::from(i32::MAX)),
+            ValueType::I64 => Some(i128::from(i64::MIN)..=i128::from(i64::MAX)),
+            ValueType::I128 => Some(i128::MIN..=i128::MAX),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// One generated value. All integers are stored in the widest type, so one enum covers them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Unsigned(u128),
+    Signed(i128),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    Char(char),
+}
+
+impl Value {
+    // The value as a number for summaries and histograms; `true` is 1. Characters have none.
+    pub fn as_f64(self) -> Option<f64> {
+        match self {
+            Value::Unsigned(number) => Some(number as f64),
+            Value::Signed(number) => Some(number as f64),
+            Value::F32(number) => Some(f64::from(number)),
+            Value::F64(number) => Some(number),
+            Value::Bool(value) => Some(if value { 1.0 } else { 0.0 }),
+            Value::Char(_) => None,
+        }
+    }
+
+    // Numbers and booleans are JSON as they are; characters become strings.
+    pub fn to_json(self) -> String {
+        match self {
+            Value::Char(letter) => serde_json::to_string(&letter).unwrap_or_default(),
+            other => other.to_string(),
+        }
+    }
+
+    // Quotes the value if CSV needs it (a comma, quote or line break in a character).
+    pub fn to_csv(self) -> String {
+        match self {
+            Value::Char(letter) if matches!(letter, ',' | '"' | '\n' | '\r') => {
+                format!("\"{}\"", letter.to_string().replace('"', "\"\""))
+            }
+            other => other.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Unsigned(number) => write!(f, "{}", number),
+            Value::Signed(number) => write!(f, "{}", number),
+            Value::F32(number) => write!(f, "{}", number),
+            Value::F64(number) => write!(f, "{}", number),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Char(letter) => write!(f, "{}", letter),
+        }
+    }
+}
+
+// Which values a generator can produce.
+#[derive(Debug, Clone, PartialEq)]
+enum Bounds {
+    Unsigned(RangeInclusive<u128>),
+    Signed(RangeInclusive<i128>),
+    Float { low: f64, high: f64, inclusive: bool },
+    Bool,
+    Char(RangeInclusive<char>),
+}
+
+// Produces random values of one type inside a range that has been checked in advance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    kind: ValueType,
+    bounds: Bounds,
+}
+
+impl Generator {
+    // `range` is Rust range syntax: `1..100` leaves out 100, `1..=6` includes 6.
+    // Without a range, integers can take any value of their type, floats are in `0..1`
+    // (like `rng.r#gen()`) and characters are printable ASCII without the space (`!..=~`).
+    pub fn new(kind: ValueType, range: Option<&str>) -> Result<Generator, String> {
+        let bounds = match (kind, range) {
+            (ValueType::Bool, Some(_)) => return Err(String::from("`bool` does not take a range")),
+            (ValueType::Bool, None) => Bounds::Bool,
+            (ValueType::F32 | ValueType::F64, None) => Bounds::Float { low: 0.0, high: 1.0, inclusive: false },
+            (ValueType::F32 | ValueType::F64, Some(text)) => {
+                let (low, high, inclusive) = split_range(text)?;
+                let low = parse_bound::<f64>(kind, low)?;
+                let high = parse_bound::<f64>(kind, high)?;
+                if !low.is_finite() || !high.is_finite() {
+                    return Err(format!("the range '{}' must have finite bounds", text));
+                }
+                if kind == ValueType::F32 && (low.abs() > f64::from(f32::MAX) || high.abs() > f64::from(f32::MAX)) {
+                    return Err(format!("the range '{}' does not fit in f32", text));
+                }
+                // f32 values are drawn between the bounds rounded to f32, so check those.
+                let (low, high) = if kind == ValueType::F32 { (f64::from(low as f32), f64::from(high as f32)) } else { (low, high) };
+                if low > high || (low == high && !inclusive) {
+                    return Err(format!("the range '{}' is empty", text));
+                }
+                let width_is_finite = if kind == ValueType::F32 { (high as f32 - low as f32).is_finite() } else { (high - low).is_finite() };
+                if !width_is_finite {
+                    return Err(format!("the range '{}' is too wide for {}", text, kind));
+                }
+                Bounds::Float { low, high, inclusive }
+            }
+            (ValueType::Char, None) => Bounds::Char('!'..='~'),
+            (ValueType::Char, Some(text)) => {
+                let (low, high, inclusive) = split_range(text)?;
+                let single = |bound: &str| {
+                    let mut letters = bound.chars();
+                    match (letters.next(), letters.next()) {
+                        (Some(letter), None) => Ok(letter),
+                        _ => Err(format!("'{}' is not a single character", bound)),
+                    }
+                };
+                let (low, high) = (single(low)?, single(high)?);
+                // The character before an excluded end, stepping over the surrogates U+D800..=U+DFFF.
+                let high = if inclusive { Some(high) } else { (0..u32::from(high)).rev().find_map(char::from_u32) };
+                match high {
+                    Some(high) if low <= high => Bounds::Char(low..=high),
+                    _ => return Err(format!("the range '{}' is empty", text)),
+                }
+            }
+            (kind, range) => match (kind.unsigned_bounds(), kind.signed_bounds()) {
+                (Some(whole), _) => Bounds::Unsigned(integer_range(kind, range, whole)?),
+                (_, Some(whole)) => Bounds::Signed(integer_range(kind, range, whole)?),
+                (None, None) => unreachable!("{} is an integer type", kind),
+            },
+        };
+        Ok(Generator { kind, bounds })
+    }
+
+    pub fn kind(&self) -> ValueType {
+        self.kind
+    }
+
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Value {
+        match &self.bounds {
+            Bounds::Unsigned(range) => Value::Unsigned(rng.gen_range(range.clone())),
+            Bounds::Signed(range) => Value::Signed(rng.gen_range(range.clone())),
+            Bounds::Float { low, high, inclusive } => {
+                let (low, high) = (*low, *high);
+                match (self.kind, inclusive) {
+                    (ValueType::F32, true) => Value::F32(rng.gen_range(low as f32..=high as f32)),
+                    (ValueType::F32, false) => Value::F32(rng.gen_range(low as f32..high as f32)),
+                    (_, true) => Value::F64(rng.gen_range(low..=high)),
+                    (_, false) => Value::F64(rng.gen_range(low..high)),
+                }
+            }
+            Bounds::Bool => Value::Bool(rng.r#gen()),
+            Bounds::Char(range) => Value::Char(rng.gen_range(range.clone())),
+        }
+    }
+}
+
+// Where the values come from: evenly spread over a type's range, or a distribution.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sampler {
+    Values(Generator),
+    Distribution(Distribution),
+}
+
+impl Sampler {
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Value {
+        match self {
+            Sampler::Values(generator) => generator.generate(rng),
+            Sampler::Distribution(distribution) if distribution.is_discrete() => {
+                Value::Unsigned(distribution.sample(rng) as u128)
+            }
+            Sampler::Distribution(distribution) => Value::F64(distribution.sample(rng)),
+        }
+    }
+
+    // Whether every value is a whole number, so a histogram can give each its own line.
+    pub fn whole_numbers(&self) -> bool {
+        match self {
+            Sampler::Values(generator) => {
+                !matches!(generator.kind(), ValueType::F32 | ValueType::F64 | ValueType::Char)
+            }
+            Sampler::Distribution(distribution) => distribution.is_discrete(),
+        }
+    }
+}
+
+// "1..100" -> ("1", "100", false); "1..=6" -> ("1", "6", true).
+fn split_range(text: &str) -> Result<(&str, &str, bool), String> {
+    let text = text.trim();
+    let (low, high, inclusive) = match text.split_once("..=") {
+        Some((low, high)) => (low, high, true),
+        None => match text.split_once("..") {
+            Some((low, high)) => (low, high, false),
+            None => return Err(format!("'{}' is not a range; write it like 1..100 or 1..=6", text)),
+        },
+    };
+    Ok((low.trim(), high.trim(), inclusive))
+}
+
+fn parse_bound<T: std::str::FromStr>(kind: ValueType, bound: &str) -> Result<T, String> {
+    bound.parse().map_err(|_| format!("'{}' is not a valid {}", bound, kind))
+}
+
+// The inclusive range of integers meant by `range`, checked against the type's own bounds.
+fn integer_range<T>(kind: ValueType, range: Option<&str>, whole: RangeInclusive<T>) -> Result<RangeInclusive<T>, String>
+where
+    T: Copy + PartialOrd + fmt::Display + std::str::FromStr + std::ops::Sub<Output = T> + From<u8>,
+{
+    let Some(text) = range else {
+        return Ok(whole);
+    };
+    let (low, high, inclusive) = split_range(text)?;
+    let low: T = parse_bound(kind, low)?;
+    let high: T = parse_bound(kind, high)?;
+    for bound in [low, high] {
+        if !whole.contains(&bound) {
+            return Err(format!("{} does not fit in {} ({}..={})", bound, kind, whole.start(), whole.end()));
+        }
+    }
+    if low > high || (low == high && !inclusive) {
+        return Err(format!("the range '{}' is empty", text));
+    }
+    Ok(if inclusive { low..=high } else { low..=high - T::from(1) })
+}
+
+// How the values are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    // Separated by `--separator`, a new line by default.
+    #[default]
+    Plain,
+    // A JSON array.
+    Json,
+    // An `index,value` header, then one row per value.
+    Csv,
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 3] = [OutputFormat::Plain, OutputFormat::Json, OutputFormat::Csv];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Plain => "plain",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<OutputFormat> {
+        let name = name.trim().to_lowercase();
+        OutputFormat::ALL.into_iter().find(|format| format.name() == name)
+    }
+}
+
+pub fn write_values(out: &mut dyn Write, values: &[Value], format: OutputFormat, separator: &str) -> io::Result<()> {
+    match format {
+        OutputFormat::Plain => {
+            let texts: Vec<String> = values.iter().map(Value::to_string).collect();
+            writeln!(out, "{}", texts.join(separator))
+        }
+        OutputFormat::Json => {
+            let texts: Vec<String> = values.iter().map(|value| value.to_json()).collect();
+            writeln!(out, "[{}]", texts.join(", "))
+        }
+        OutputFormat::Csv => {
+            writeln!(out, "index,value")?;
+            for (index, value) in values.iter().enumerate() {
+                writeln!(out, "{},{}", index + 1, value.to_csv())?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_writes_one_value_of_each_type() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut output: Vec<u8> = Vec::new();
+        write_random_values(&mut output, &mut rng).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 4);
+        let number: u32 = lines[0].parse().unwrap();
+        assert!((1..100).contains(&number));
+        assert!(lines[1].parse::<i32>().is_ok());
+        let float: f64 = lines[2].parse().unwrap();
+        assert!((0.0..1.0).contains(&float));
+        assert!(lines[3] == "true" || lines[3] == "false");
+    }
+
+    fn sample(kind: ValueType, range: Option<&str>, count: usize) -> Vec<Value> {
+        let generator = Generator::new(kind, range).unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        (0..count).map(|_| generator.generate(&mut rng)).collect()
+    }
+
+    #[test]
+    fn test_ranges_are_respected() {
+        let dice = sample(ValueType::U8, Some("1..=6"), 600);
+        assert!(dice.iter().all(|value| matches!(value, Value::Unsigned(1..=6))));
+        // Each face shows up with 600 rolls.
+        for face in 1..=6 {
+            assert!(dice.contains(&Value::Unsigned(face)));
+        }
+        assert!(sample(ValueType::I64, Some("-3..3"), 300).iter().all(|value| matches!(value, Value::Signed(-3..=2))));
+        assert!(sample(ValueType::Char, Some("a..=c"), 50).iter().all(|value| matches!(value, Value::Char('a'..='c'))));
+        assert!(sample(ValueType::Char, None, 50).iter().all(|value| matches!(value, Value::Char('!'..='~'))));
+        assert!(sample(ValueType::Char, Some("\u{D7FE}..\u{E000}"), 50).iter().all(|value| matches!(value, Value::Char('\u{D7FE}'..='\u{D7FF}'))));
+        for value in sample(ValueType::F32, Some("-1.5..2.5"), 100) {
+            let Value::F32(number) = value else { panic!("{:?}", value) };
+            assert!((-1.5..2.5).contains(&number));
+        }
+        assert!(matches!(sample(ValueType::Bool, None, 1)[0], Value::Bool(_)));
+    }
+
+    #[test]
+    fn test_whole_type_ranges() {
+        assert!(Generator::new(ValueType::U128, None).is_ok());
+        assert!(Generator::new(ValueType::I128, Some("-170141183460469231731687303715884105728..=0")).is_ok());
+        assert!(sample(ValueType::U8, None, 100).iter().all(|value| matches!(value, Value::Unsigned(0..=255))));
+    }
+
+    #[test]
+    fn test_invalid_ranges() {
+        let error = |kind: ValueType, range: &str| Generator::new(kind, Some(range)).unwrap_err();
+        assert_eq!(error(ValueType::U8, "1-6"), "'1-6' is not a range; write it like 1..100 or 1..=6");
+        assert_eq!(error(ValueType::U8, "0..300"), "300 does not fit in u8 (0..=255)");
+        assert_eq!(error(ValueType::U32, "-1..5"), "'-1' is not a valid u32");
+        assert_eq!(error(ValueType::I32, "5..5"), "the range '5..5' is empty");
+        assert!(Generator::new(ValueType::I32, Some("5..=5")).is_ok());
+        assert_eq!(error(ValueType::F64, "2..1"), "the range '2..1' is empty");
+        assert_eq!(error(ValueType::F32, "0..1e40"), "the range '0..1e40' does not fit in f32");
+        assert_eq!(error(ValueType::F32, "0..1e-50"), "the range '0..1e-50' is empty");
+        assert_eq!(error(ValueType::F32, "1..1.00000001"), "the range '1..1.00000001' is empty");
+        assert_eq!(error(ValueType::F64, "-1e308..1e308"), "the range '-1e308..1e308' is too wide for f64");
+        assert_eq!(error(ValueType::F32, "-3e38..3e38"), "the range '-3e38..3e38' is too wide for f32");
+        assert_eq!(error(ValueType::Char, "a..a"), "the range 'a..a' is empty");
+        assert_eq!(error(ValueType::Char, "ab..=z"), "'ab' is not a single character");
+        assert_eq!(error(ValueType::Bool, "0..1"), "`bool` does not take a range");
+    }
+
+    #[test]
+    fn test_output_formats() {
+        let values = [Value::Unsigned(3), Value::F64(0.5), Value::Char(','), Value::Bool(true)];
+        let write = |format: OutputFormat, separator: &str| {
+            let mut output: Vec<u8> = Vec::new();
+            write_values(&mut output, &values, format, separator).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+        assert_eq!(write(OutputFormat::Plain, "\n"), "3\n0.5\n,\ntrue\n");
+        assert_eq!(write(OutputFormat::Plain, " "), "3 0.5 , true\n");
+        assert_eq!(write(OutputFormat::Json, ""), "[3, 0.5, \",\", true]\n");
+        assert_eq!(write(OutputFormat::Csv, ""), "index,value\n1,3\n2,0.5\n3,\",\"\n4,true\n");
+        let json: Vec<serde_json::Value> = serde_json::from_str(&write(OutputFormat::Json, "")).unwrap();
+        assert_eq!(json.len(), 4);
+    }
+}
@@ -0,0 +1,436 @@
+// Module: random_number Command Line
+// Parses the arguments of the `random_number` binary into a `Command`, like
+// guess_game/cli.rs does for the game, so the parsing can be unit-tested.
+
+use super::dice::Expr;
+use super::distribution::{Distribution, NAMES};
+use super::password::{CharClass, Recipe};
+use super::{Generator, OutputFormat, Sampler, ValueType};
+use crate::input;
+use crate::rng::{self, RngKind};
+
+pub const USAGE: &str = "\
+Usage: random_number [--seed <n>] [--rng <name>]  One value of a few types, like the original program
+       random_number [options]                    Values of one type, for scripts and test data
+       random_number roll <dice> [--seed <n>]     Roll dice like 3d6+2, 4d6kh3, 2d20kl1 or 3d6! (see below)
+       random_number password [options]           Passwords or passphrases with their entropy (see below)
+       random_number help                         Show this message
+
+Options:
+  --type <type>          u8, u16, u32, u64, u128, i8 ... i128, f32, f64, bool or char (default u32)
+  --range <range>        Rust range syntax: 1..100 leaves out 100, 1..=6 includes 6, a..=z for char
+                         (default: the whole type; 0..1 for floats, ! to ~ for char)
+  --count <n>            How many values (default 1)
+  --separator <text>     Between plain values (default a new line; \\t and \\n work)
+  --format <format>      plain, json (an array) or csv (index,value rows)
+  --dist <name[:params]> Draw numbers from a distribution instead of --type and --range
+                         (e.g. normal:10,2 or poisson:4; the distributions are listed below)
+  --summary              Print the count, mean, variance, min and max instead of the values
+  --histogram            Draw a histogram instead of printing the values
+  --bins <n>             How many bars the histogram has for fractions (default 10, at most 1000)
+  --seed <n>             The same values every time (or set RUST_PRACTICE_SEED)
+  --rng <name>           The generator: std (the default, rand's StdRng) or one written from
+                         scratch: lcg, xorshift64star, pcg32, splitmix64 or xoshiro256starstar
+                         (also for `roll`; `password` always uses std)
+With --summary or --histogram the default count is 1000.
+
+Dice: NdS rolls N dice with S sides (d% has 100), khK or kK keeps the highest K, klK the
+lowest K, and ! after the sides makes dice explode (roll again on the highest side).
+Combine them with numbers, + - * / and brackets; division rounds down.
+
+Password options:
+  --length <n>           How many characters (default 16)
+  --classes <list>       Any of lower,upper,digits,symbols (default all four)
+  --allow-missing        Do not require at least one character of every class
+  --no-ambiguous         Leave out characters that look alike (I l 1 | O 0 ` ' \")
+  --words <n>            A passphrase of n words from the bundled list instead
+  --separator <text>     Between the words (default -)
+  --capitalize           Start every word with a capital letter
+  --count <n>            How many (default 1)
+  --seed <n>             Repeatable results, for test fixtures only: anyone with the seed has them
+Each result is followed by a tab and its entropy in bits.";
+
+// The default number of histogram bars, and of values when they are only summarised.
+const DEFAULT_BINS: usize = 10;
+// One line per bin; more than this would not fit on any screen anyway.
+const MAX_BINS: usize = 1000;
+const REPORT_COUNT: usize = 1000;
+
+// The usage message with the distributions `--dist` knows appended.
+pub fn usage() -> String {
+    let mut text = format!("{}
+
+Distributions (parameters, default):", USAGE);
+    for (name, params, defaults) in NAMES {
+        text.push_str(&format!("\n  {:<23}{} (default {})", name, params, defaults));
+    }
+    text
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    // The original four values; the seed, if given, and the generator.
+    Sample(Option<u64>, RngKind),
+    Generate(GenerateOptions),
+    Roll(RollOptions),
+    Password(PasswordOptions),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GenerateOptions {
+    pub sampler: Sampler,
+    pub count: usize,
+    pub separator: String,
+    pub format: OutputFormat,
+    // Describe the values instead of printing them: a summary, and a histogram with this many bins.
+    pub summary: bool,
+    pub histogram: Option<usize>,
+    // `None` leaves the choice to `rng::choose_seed`.
+    pub seed: Option<u64>,
+    pub rng: RngKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RollOptions {
+    pub expr: Expr,
+    pub seed: Option<u64>,
+    pub rng: RngKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PasswordOptions {
+    pub recipe: Recipe,
+    pub count: usize,
+    pub seed: Option<u64>,
+}
+
+const DEFAULT_LENGTH: usize = 16;
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(String::as_str) {
+        Some("help" | "--help") => return Ok(Command::Help),
+        Some("roll") => return parse_roll(&args[1..]),
+        Some("password") => return parse_password(&args[1..]),
+        _ => {}
+    }
+    let mut kind = None;
+    let mut range = None;
+    let mut count = None;
+    let mut separator = None;
+    let mut format = None;
+    let mut dist = None;
+    let mut summary = false;
+    let mut histogram = false;
+    let mut bins = None;
+    let mut seed = None;
+    let mut kind_of_rng = RngKind::default();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("`{}` needs a value", flag));
+        match flag.as_str() {
+            "--type" => {
+                let name = value()?;
+                kind = Some(ValueType::from_name(name).ok_or_else(|| {
+                    let names: Vec<&str> = ValueType::ALL.iter().map(|kind| kind.name()).collect();
+                    format!("unknown type '{}' (choose from {})", name, names.join(", "))
+                })?);
+            }
+            "--range" => range = Some(value()?.clone()),
+            "--count" => {
+                let text = value()?;
+                count = Some(text.parse::<usize>().map_err(|_| format!("`--count` needs a number, not '{}'", text))?);
+            }
+            "--separator" => separator = Some(input::unescape(value()?)),
+            "--format" => {
+                let name = value()?;
+                format = Some(OutputFormat::from_name(name).ok_or_else(|| {
+                    let names: Vec<&str> = OutputFormat::ALL.iter().map(|format| format.name()).collect();
+                    format!("unknown format '{}' (choose from {})", name, names.join(", "))
+                })?);
+            }
+            "--dist" => dist = Some(Distribution::parse(value()?)?),
+            "--summary" => summary = true,
+            "--histogram" => histogram = true,
+            "--bins" => {
+                let text = value()?;
+                bins = Some(text.parse::<usize>().ok().filter(|bins| (1..=MAX_BINS).contains(bins)).ok_or_else(|| {
+                    format!("`--bins` needs a number from 1 to {}, not '{}'", MAX_BINS, text)
+                })?);
+            }
+            "--seed" => seed = Some(rng::parse_seed(value()?)?),
+            "--rng" => kind_of_rng = rng::parse_rng(value()?)?,
+            other if other.starts_with("--") => return Err(format!("unknown option `{}`", other)),
+            other => return Err(format!("unexpected argument `{}`", other)),
+        }
+    }
+    let report = summary || histogram;
+    if kind.is_none() && range.is_none() && count.is_none() && separator.is_none() && format.is_none()
+        && dist.is_none() && !report && bins.is_none()
+    {
+        return Ok(Command::Sample(seed, kind_of_rng));
+    }
+    if report && (separator.is_some() || format.is_some()) {
+        return Err(String::from("`--separator` and `--format` do not apply to --summary or --histogram"));
+    }
+    if bins.is_some() && !histogram {
+        return Err(String::from("`--bins` only applies to --histogram"));
+    }
+    let format = format.unwrap_or_default();
+    if separator.is_some() && format != OutputFormat::Plain {
+        return Err(format!("`--separator` only applies to the plain format, not {}", format.name()));
+    }
+    let sampler = match dist {
+        Some(_) if kind.is_some() || range.is_some() => {
+            return Err(String::from("`--dist` cannot be combined with --type or --range"));
+        }
+        Some(distribution) => Sampler::Distribution(distribution),
+        None => Sampler::Values(Generator::new(kind.unwrap_or(ValueType::U32), range.as_deref())?),
+    };
+    if report && matches!(sampler, Sampler::Values(ref generator) if generator.kind() == ValueType::Char) {
+        return Err(String::from("characters cannot be summarised or drawn as a histogram"));
+    }
+    Ok(Command::Generate(GenerateOptions {
+        sampler,
+        count: count.unwrap_or(if report { REPORT_COUNT } else { 1 }),
+        separator: separator.unwrap_or_else(|| String::from("\n")),
+        format,
+        summary,
+        histogram: histogram.then(|| bins.unwrap_or(DEFAULT_BINS)),
+        seed,
+        rng: kind_of_rng,
+    }))
+}
+
+// `roll 3d6 + 2 --seed 1`: the words that are not options make up the expression, so it
+// does not have to be quoted.
+fn parse_roll(args: &[String]) -> Result<Command, String> {
+    let mut words = Vec::new();
+    let mut seed = None;
+    let mut kind_of_rng = RngKind::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => seed = Some(rng::parse_seed(args.next().ok_or("`--seed` needs a value")?)?),
+            "--rng" => kind_of_rng = rng::parse_rng(args.next().ok_or("`--rng` needs a value")?)?,
+            other if other.starts_with("--") => return Err(format!("unknown option `{}`", other)),
+            word => words.push(word),
+        }
+    }
+    if words.is_empty() {
+        return Err(String::from("`roll` needs dice to roll, like 3d6+2"));
+    }
+    Ok(Command::Roll(RollOptions { expr: Expr::parse(&words.join(" "))?, seed, rng: kind_of_rng }))
+}
+
+fn parse_password(args: &[String]) -> Result<Command, String> {
+    let mut length = None;
+    let mut classes = None;
+    let mut allow_missing = false;
+    let mut no_ambiguous = false;
+    let mut words = None;
+    let mut separator = None;
+    let mut capitalize = false;
+    let mut count = 1;
+    let mut seed = None;
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("`{}` needs a value", flag));
+        let mut number = |flag: &str| -> Result<usize, String> {
+            let text = value()?;
+            text.parse::<usize>().map_err(|_| format!("`{}` needs a number, not '{}'", flag, text))
+        };
+        match flag.as_str() {
+            "--length" => length = Some(number("--length")?),
+            "--words" => words = Some(number("--words")?),
+            "--count" => count = number("--count")?,
+            "--classes" => {
+                let list = value()?;
+                let parsed: Result<Vec<CharClass>, String> = list
+                    .split(',')
+                    .map(|name| {
+                        CharClass::from_name(name).ok_or_else(|| {
+                            let names: Vec<&str> = CharClass::ALL.iter().map(|class| class.name()).collect();
+                            format!("unknown character class '{}' (choose from {})", name.trim(), names.join(", "))
+                        })
+                    })
+                    .collect();
+                classes = Some(parsed?);
+            }
+            "--allow-missing" => allow_missing = true,
+            "--no-ambiguous" => no_ambiguous = true,
+            "--separator" => separator = Some(input::unescape(value()?)),
+            "--capitalize" => capitalize = true,
+            "--seed" => seed = Some(rng::parse_seed(value()?)?),
+            other if other.starts_with("--") => return Err(format!("unknown option `{}`", other)),
+            other => return Err(format!("unexpected argument `{}`", other)),
+        }
+    }
+    let recipe = match words {
+        Some(_) if length.is_some() || classes.is_some() || allow_missing || no_ambiguous => {
+            return Err(String::from("`--words` makes a passphrase; it cannot be combined with character options"));
+        }
+        Some(words) => Recipe::passphrase(words, separator.as_deref().unwrap_or("-"), capitalize)?,
+        None if separator.is_some() || capitalize => {
+            return Err(String::from("`--separator` and `--capitalize` only apply to passphrases (`--words`)"));
+        }
+        None => Recipe::characters(
+            length.unwrap_or(DEFAULT_LENGTH),
+            classes.as_deref().unwrap_or(&CharClass::ALL),
+            !allow_missing,
+            no_ambiguous,
+        )?,
+    };
+    Ok(Command::Password(PasswordOptions { recipe, count, seed }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(list: &[&str]) -> Result<Command, String> {
+        let args: Vec<String> = list.iter().map(|arg| arg.to_string()).collect();
+        parse_args(&args)
+    }
+
+    #[test]
+    fn test_parse_sample() {
+        assert_eq!(parse(&[]), Ok(Command::Sample(None, RngKind::Std)));
+        assert_eq!(parse(&["--seed", "7"]), Ok(Command::Sample(Some(7), RngKind::Std)));
+        assert_eq!(parse(&["--rng", "pcg32", "--seed", "7"]), Ok(Command::Sample(Some(7), RngKind::Pcg32)));
+        assert!(parse(&["--rng", "mt19937"]).unwrap_err().starts_with("unknown generator 'mt19937'"));
+        assert!(parse(&["--seed"]).is_err());
+        assert!(parse(&["--seed", "seven"]).is_err());
+        assert_eq!(parse(&["help"]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        assert_eq!(
+            parse(&["--type", "i8", "--range", "-5..=5", "--count", "10", "--separator", "\\t", "--seed", "3"]),
+            Ok(Command::Generate(GenerateOptions {
+                sampler: Sampler::Values(Generator::new(ValueType::I8, Some("-5..=5")).unwrap()),
+                count: 10,
+                separator: String::from("\t"),
+                format: OutputFormat::Plain,
+                summary: false,
+                histogram: None,
+                seed: Some(3),
+                rng: RngKind::Std,
+            }))
+        );
+        match parse(&["--count", "3"]) {
+            Ok(Command::Generate(options)) => {
+                assert!(matches!(options.sampler, Sampler::Values(ref generator) if generator.kind() == ValueType::U32));
+                assert_eq!((options.count, options.separator.as_str(), options.format), (3, "\n", OutputFormat::Plain));
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(parse(&["--format", "CSV"]), Ok(Command::Generate(GenerateOptions { format: OutputFormat::Csv, .. }))));
+    }
+
+    #[test]
+    fn test_parse_distribution_reports() {
+        match parse(&["--dist", "normal:10,2", "--histogram", "--bins", "20", "--summary"]) {
+            Ok(Command::Generate(options)) => {
+                assert_eq!(options.sampler, Sampler::Distribution(Distribution::Normal { mean: 10.0, std_dev: 2.0 }));
+                assert_eq!((options.count, options.summary, options.histogram), (1000, true, Some(20)));
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(
+            parse(&["--dist", "poisson", "--count", "5"]),
+            Ok(Command::Generate(GenerateOptions { count: 5, summary: false, histogram: None, .. }))
+        ));
+        assert!(matches!(parse(&["--histogram"]), Ok(Command::Generate(GenerateOptions { histogram: Some(10), .. }))));
+        assert!(usage().contains("  binomial               n,p (default 10,0.5)"));
+    }
+
+    #[test]
+    fn test_parse_roll() {
+        assert_eq!(
+            parse(&["roll", "3d6", "+", "2", "--seed", "4"]),
+            Ok(Command::Roll(RollOptions { expr: Expr::parse("3d6+2").unwrap(), seed: Some(4), rng: RngKind::Std }))
+        );
+        assert!(matches!(parse(&["roll", "-1+d4"]), Ok(Command::Roll(RollOptions { seed: None, .. }))));
+        assert!(matches!(parse(&["roll", "d6", "--rng", "lcg"]), Ok(Command::Roll(RollOptions { rng: RngKind::Lcg, .. }))));
+        assert_eq!(parse(&["password", "--rng", "lcg"]).unwrap_err(), "unknown option `--rng`");
+        assert_eq!(parse(&["roll"]).unwrap_err(), "`roll` needs dice to roll, like 3d6+2");
+        assert_eq!(parse(&["roll", "2d6", "--count", "3"]).unwrap_err(), "unknown option `--count`");
+        assert!(parse(&["roll", "3d6", "+"]).unwrap_err().ends_with("3d6 +\n       ^"));
+    }
+
+    #[test]
+    fn test_parse_password() {
+        assert_eq!(
+            parse(&["password"]),
+            Ok(Command::Password(PasswordOptions {
+                recipe: Recipe::characters(16, &CharClass::ALL, true, false).unwrap(),
+                count: 1,
+                seed: None,
+            }))
+        );
+        assert_eq!(
+            parse(&["password", "--length", "8", "--classes", "digits, Lower", "--allow-missing", "--no-ambiguous", "--count", "3"]),
+            Ok(Command::Password(PasswordOptions {
+                recipe: Recipe::characters(8, &[CharClass::Digits, CharClass::Lower], false, true).unwrap(),
+                count: 3,
+                seed: None,
+            }))
+        );
+        assert_eq!(
+            parse(&["password", "--words", "5", "--separator", " ", "--capitalize", "--seed", "1"]),
+            Ok(Command::Password(PasswordOptions { recipe: Recipe::passphrase(5, " ", true).unwrap(), count: 1, seed: Some(1) }))
+        );
+        assert_eq!(
+            parse(&["password", "--classes", "lower,emoji"]).unwrap_err(),
+            "unknown character class 'emoji' (choose from lower, upper, digits, symbols)"
+        );
+        assert_eq!(
+            parse(&["password", "--words", "4", "--length", "10"]).unwrap_err(),
+            "`--words` makes a passphrase; it cannot be combined with character options"
+        );
+        assert_eq!(
+            parse(&["password", "--capitalize"]).unwrap_err(),
+            "`--separator` and `--capitalize` only apply to passphrases (`--words`)"
+        );
+        assert_eq!(parse(&["password", "--length", "ten"]).unwrap_err(), "`--length` needs a number, not 'ten'");
+        assert_eq!(parse(&["password", "--length", "2"]).unwrap_err(), "2 characters cannot include all 4 classes");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse(&["--type", "u7"]).unwrap_err(),
+            "unknown type 'u7' (choose from u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, bool, char)"
+        );
+        assert_eq!(parse(&["--format", "xml"]).unwrap_err(), "unknown format 'xml' (choose from plain, json, csv)");
+        assert_eq!(parse(&["--count", "-1"]).unwrap_err(), "`--count` needs a number, not '-1'");
+        assert_eq!(
+            parse(&["--format", "json", "--separator", ","]).unwrap_err(),
+            "`--separator` only applies to the plain format, not json"
+        );
+        assert_eq!(parse(&["--type", "u8", "--range", "1..1000"]).unwrap_err(), "1000 does not fit in u8 (0..=255)");
+        assert_eq!(parse(&["--colour"]).unwrap_err(), "unknown option `--colour`");
+        assert_eq!(parse(&["5"]).unwrap_err(), "unexpected argument `5`");
+        assert_eq!(
+            parse(&["--dist", "normal", "--type", "f32"]).unwrap_err(),
+            "`--dist` cannot be combined with --type or --range"
+        );
+        assert_eq!(
+            parse(&["--summary", "--format", "json"]).unwrap_err(),
+            "`--separator` and `--format` do not apply to --summary or --histogram"
+        );
+        assert_eq!(parse(&["--bins", "5"]).unwrap_err(), "`--bins` only applies to --histogram");
+        assert_eq!(parse(&["--histogram", "--bins", "0"]).unwrap_err(), "`--bins` needs a number from 1 to 1000, not '0'");
+        assert_eq!(
+            parse(&["--histogram", "--bins", "100000000000000"]).unwrap_err(),
+            "`--bins` needs a number from 1 to 1000, not '100000000000000'"
+        );
+        assert!(parse(&["--histogram", "--bins", "1000"]).is_ok());
+        assert_eq!(
+            parse(&["--type", "char", "--summary"]).unwrap_err(),
+            "characters cannot be summarised or drawn as a histogram"
+        );
+    }
+}
@@ -0,0 +1,565 @@
+// Module: Dice Expressions
+// `random_number roll 4d6kh3+2` rolls dice written in the notation of tabletop games:
+// - `3d6`      three six-sided dice, added up (`d6` is one die, `d%` a hundred-sided one)
+// - `4d6kh3`   keep the highest 3 of the 4 dice (`k3` means the same); `2d20kl1` keeps the lowest
+// - `3d6!`     exploding dice: every die that shows its highest side adds another die
+// - `+ - * /` and brackets combine dice and numbers; division rounds down.
+// The text is first parsed into a tree of `Expr` values (an abstract syntax tree) by a
+// recursive-descent parser: one function per precedence level, each calling the next.
+// Rolling walks the tree and keeps every die, so the output can show how the total came about.
+
+use rand::Rng;
+use std::fmt;
+use std::io::{self, Write};
+
+// Limits that keep a typo like `1000000d6` or `d1!` from running forever.
+const MAX_DICE: u32 = 1000;
+const MAX_SIDES: u32 = 1_000_000;
+// An exploding group stops adding dice once it has this many.
+const MAX_POOL: usize = 10_000;
+// How deeply brackets and minus signs may nest, and how many `+ - * /` an expression may
+// have, so the recursive parser (and the recursive evaluation, printing and dropping of
+// what it builds) cannot overflow the stack.
+const MAX_DEPTH: usize = 64;
+const MAX_OPERATORS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keep {
+    Highest(u32),
+    Lowest(u32),
+}
+
+// One group of dice like `4d6kh3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dice {
+    pub count: u32,
+    pub sides: u32,
+    pub explode: bool,
+    pub keep: Option<Keep>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Op {
+    fn symbol(self) -> char {
+        match self {
+            Op::Add => '+',
+            Op::Subtract => '-',
+            Op::Multiply => '*',
+            Op::Divide => '/',
+        }
+    }
+
+    // `*` and `/` bind more tightly than `+` and `-`.
+    fn precedence(self) -> u8 {
+        match self {
+            Op::Add | Op::Subtract => 1,
+            Op::Multiply | Op::Divide => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Number(i64),
+    Dice(Dice),
+    Negate(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn parse(text: &str) -> Result<Expr, String> {
+        let mut parser = Parser { text: text.chars().collect(), position: 0, depth: 0, operators: 0 };
+        let expr = parser.expression()?;
+        parser.skip_spaces();
+        match parser.peek() {
+            None => Ok(expr),
+            Some(')') => Err(parser.error(parser.position, "')' without a matching '('")),
+            Some(other) => Err(parser.error(parser.position, &format!("unexpected '{}'", other))),
+        }
+    }
+
+    // Rolls every group of dice, left to right, and works out the total.
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Roll, String> {
+        let mut groups = Vec::new();
+        let total = self.evaluate(rng, &mut groups)?;
+        Ok(Roll { groups, total })
+    }
+
+    fn evaluate<R: Rng + ?Sized>(&self, rng: &mut R, groups: &mut Vec<DiceRoll>) -> Result<i64, String> {
+        let too_large = || String::from("the total is too large");
+        match self {
+            Expr::Number(number) => Ok(*number),
+            Expr::Dice(dice) => {
+                let group = dice.roll(rng);
+                let total = group.total;
+                groups.push(group);
+                Ok(total)
+            }
+            Expr::Negate(inner) => inner.evaluate(rng, groups)?.checked_neg().ok_or_else(too_large),
+            Expr::Binary(op, left, right) => {
+                let left = left.evaluate(rng, groups)?;
+                let right = right.evaluate(rng, groups)?;
+                match op {
+                    Op::Add => left.checked_add(right).ok_or_else(too_large),
+                    Op::Subtract => left.checked_sub(right).ok_or_else(too_large),
+                    Op::Multiply => left.checked_mul(right).ok_or_else(too_large),
+                    Op::Divide if right == 0 => Err(String::from("division by zero")),
+                    Op::Divide => {
+                        // Rust's `/` rounds towards zero; round down instead, so -7 / 2 is -4.
+                        let quotient = left.checked_div(right).ok_or_else(too_large)?;
+                        let inexact = left % right != 0;
+                        Ok(if inexact && (left < 0) != (right < 0) { quotient - 1 } else { quotient })
+                    }
+                }
+            }
+        }
+    }
+
+    // Writes the expression with brackets only where the precedence needs them.
+    fn write(&self, f: &mut fmt::Formatter<'_>, parent: u8) -> fmt::Result {
+        match self {
+            Expr::Number(number) => write!(f, "{}", number),
+            Expr::Dice(dice) => write!(f, "{}", dice),
+            Expr::Negate(inner) => {
+                f.write_str("-")?;
+                inner.write(f, 3)
+            }
+            Expr::Binary(op, left, right) => {
+                let precedence = op.precedence();
+                if precedence < parent {
+                    f.write_str("(")?;
+                }
+                left.write(f, precedence)?;
+                write!(f, " {} ", op.symbol())?;
+                // `a - (b - c)` needs its brackets, `a + (b + c)` does not.
+                right.write(f, precedence + 1)?;
+                if precedence < parent {
+                    f.write_str(")")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}d{}", self.count, self.sides)?;
+        if self.explode {
+            f.write_str("!")?;
+        }
+        match self.keep {
+            Some(Keep::Highest(keep)) => write!(f, "kh{}", keep),
+            Some(Keep::Lowest(keep)) => write!(f, "kl{}", keep),
+            None => Ok(()),
+        }
+    }
+}
+
+// One die of a roll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Die {
+    pub value: u32,
+    // Whether it counts towards the total (see `Keep`).
+    pub kept: bool,
+    // Whether it showed the highest side and added another die.
+    pub exploded: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceRoll {
+    pub dice: Dice,
+    pub dice_rolled: Vec<Die>,
+    pub total: i64,
+}
+
+impl Dice {
+    fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> DiceRoll {
+        let mut dice_rolled = Vec::new();
+        let mut left = self.count;
+        while left > 0 {
+            left -= 1;
+            let value = rng.gen_range(1..=self.sides);
+            let exploded = self.explode && value == self.sides && dice_rolled.len() + 1 < MAX_POOL;
+            if exploded {
+                left += 1;
+            }
+            dice_rolled.push(Die { value, kept: true, exploded });
+        }
+        if let Some(keep) = self.keep {
+            // Sort the positions by value, lowest first, and drop from the end that is not kept.
+            let mut order: Vec<usize> = (0..dice_rolled.len()).collect();
+            order.sort_by_key(|&index| dice_rolled[index].value);
+            let kept = match keep {
+                Keep::Highest(kept) => kept,
+                Keep::Lowest(kept) => {
+                    order.reverse();
+                    kept
+                }
+            };
+            let dropped = dice_rolled.len().saturating_sub(kept as usize);
+            for &index in order.iter().take(dropped) {
+                dice_rolled[index].kept = false;
+            }
+        }
+        let total = dice_rolled.iter().filter(|die| die.kept).map(|die| i64::from(die.value)).sum();
+        DiceRoll { dice: *self, dice_rolled, total }
+    }
+}
+
+// The result of rolling an expression: every group of dice and the total.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Roll {
+    pub groups: Vec<DiceRoll>,
+    pub total: i64,
+}
+
+// One line per group of dice, then the total. Dropped dice are shown in brackets and
+// exploding ones with a `!`:
+//     4d6kh3: 6, 5, 3, (2) = 14
+//     Total: 16
+pub fn write_roll(out: &mut dyn Write, expr: &Expr, roll: &Roll) -> io::Result<()> {
+    writeln!(out, "Rolling {}", expr)?;
+    for group in &roll.groups {
+        let dice: Vec<String> = group
+            .dice_rolled
+            .iter()
+            .map(|die| {
+                let marker = if die.exploded { "!" } else { "" };
+                if die.kept { format!("{}{}", die.value, marker) } else { format!("({}{})", die.value, marker) }
+            })
+            .collect();
+        writeln!(out, "{}: {} = {}", group.dice, dice.join(", "), group.total)?;
+    }
+    writeln!(out, "Total: {}", roll.total)
+}
+
+struct Parser {
+    text: Vec<char>,
+    position: usize,
+    // How many brackets and minus signs are open.
+    depth: usize,
+    // How many `+ - * /` have been read so far.
+    operators: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.text.get(self.position).copied()
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    // The message, then the expression with a `^` under the character at `at`.
+    fn error(&self, at: usize, message: &str) -> String {
+        let text: String = self.text.iter().collect();
+        format!("{} at column {}\n  {}\n  {}^", message, at + 1, text, " ".repeat(at))
+    }
+
+    // Steps over the operator at the current position, if the expression may have one more.
+    fn count_operator(&mut self) -> Result<(), String> {
+        if self.operators == MAX_OPERATORS {
+            return Err(self.error(self.position, &format!("an expression can have at most {} operators", MAX_OPERATORS)));
+        }
+        self.operators += 1;
+        self.position += 1;
+        Ok(())
+    }
+
+    // expression = term (("+" | "-") term)*
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut expr = self.term()?;
+        loop {
+            self.skip_spaces();
+            let op = match self.peek() {
+                Some('+') => Op::Add,
+                Some('-') => Op::Subtract,
+                _ => return Ok(expr),
+            };
+            self.count_operator()?;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.term()?));
+        }
+    }
+
+    // term = factor (("*" | "/") factor)*
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut expr = self.factor()?;
+        loop {
+            self.skip_spaces();
+            let op = match self.peek() {
+                Some('*') => Op::Multiply,
+                Some('/') => Op::Divide,
+                _ => return Ok(expr),
+            };
+            self.count_operator()?;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.factor()?));
+        }
+    }
+
+    // factor = "-" factor | "(" expression ")" | dice | number
+    fn factor(&mut self) -> Result<Expr, String> {
+        self.skip_spaces();
+        let start = self.position;
+        match self.peek() {
+            Some('-') => {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error(start, "too many minus signs in a row"));
+                }
+                self.position += 1;
+                self.depth += 1;
+                let expr = self.factor()?;
+                self.depth -= 1;
+                Ok(Expr::Negate(Box::new(expr)))
+            }
+            Some('(') => {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error(start, "brackets are nested too deeply"));
+                }
+                self.position += 1;
+                self.depth += 1;
+                let expr = self.expression()?;
+                self.depth -= 1;
+                self.skip_spaces();
+                if self.peek() != Some(')') {
+                    return Err(self.error(start, "'(' without a matching ')'"));
+                }
+                self.position += 1;
+                Ok(expr)
+            }
+            Some('d' | 'D') => self.dice(start, 1),
+            Some(digit) if digit.is_ascii_digit() => {
+                let number = self.number()?;
+                if matches!(self.peek(), Some('d' | 'D')) {
+                    let count = u32::try_from(number).ok().filter(|&count| count <= MAX_DICE);
+                    let count = count.ok_or_else(|| self.error(start, &format!("at most {} dice can be rolled at once", MAX_DICE)))?;
+                    self.dice(start, count)
+                } else {
+                    let number = i64::try_from(number).map_err(|_| self.error(start, "the number is too large"))?;
+                    Ok(Expr::Number(number))
+                }
+            }
+            Some(other) => Err(self.error(start, &format!("expected a number, dice or '(' but found '{}'", other))),
+            None => Err(self.error(start, "expected a number, dice or '(' but the expression ended")),
+        }
+    }
+
+    fn number(&mut self) -> Result<u64, String> {
+        let start = self.position;
+        let mut number: u64 = 0;
+        while let Some(digit) = self.peek().and_then(|letter| letter.to_digit(10)) {
+            number = number
+                .checked_mul(10)
+                .and_then(|number| number.checked_add(u64::from(digit)))
+                .ok_or_else(|| self.error(start, "the number is too large"))?;
+            self.position += 1;
+        }
+        Ok(number)
+    }
+
+    // dice = [count] "d" (sides | "%") ["!"] [("kh" | "kl" | "k") number]; `position` is at the "d".
+    fn dice(&mut self, start: usize, count: u32) -> Result<Expr, String> {
+        if count == 0 {
+            return Err(self.error(start, "roll at least one die"));
+        }
+        self.position += 1;
+        let sides_at = self.position;
+        let sides = match self.peek() {
+            Some('%') => {
+                self.position += 1;
+                100
+            }
+            Some(digit) if digit.is_ascii_digit() => {
+                let sides = self.number()?;
+                match u32::try_from(sides) {
+                    Ok(sides) if (1..=MAX_SIDES).contains(&sides) => sides,
+                    _ => return Err(self.error(sides_at, &format!("a die has 1 to {} sides", MAX_SIDES))),
+                }
+            }
+            _ => return Err(self.error(sides_at, "expected the number of sides after 'd'")),
+        };
+        let explode = self.peek() == Some('!');
+        if explode {
+            if sides == 1 {
+                return Err(self.error(self.position, "a one-sided die cannot explode"));
+            }
+            self.position += 1;
+        }
+        let mut keep = None;
+        if matches!(self.peek(), Some('k' | 'K')) {
+            let keep_at = self.position;
+            self.position += 1;
+            // A plain `k` keeps the highest dice, like `kh`.
+            let lowest = matches!(self.peek(), Some('l' | 'L'));
+            if matches!(self.peek(), Some('l' | 'L' | 'h' | 'H')) {
+                self.position += 1;
+            }
+            if !self.peek().is_some_and(|letter| letter.is_ascii_digit()) {
+                return Err(self.error(self.position, "expected how many dice to keep"));
+            }
+            let kept = self.number()?;
+            if kept == 0 || kept > u64::from(count) {
+                return Err(self.error(keep_at, &format!("can only keep 1 to {} of {} dice", count, count)));
+            }
+            keep = Some(if lowest { Keep::Lowest(kept as u32) } else { Keep::Highest(kept as u32) });
+        }
+        Ok(Expr::Dice(Dice { count, sides, explode, keep }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+
+    fn dice(count: u32, sides: u32) -> Expr {
+        Expr::Dice(Dice { count, sides, explode: false, keep: None })
+    }
+
+    fn total(text: &str) -> Result<i64, String> {
+        Expr::parse(text)?.roll(&mut rng::seeded_rng(1)).map(|roll| roll.total)
+    }
+
+    #[test]
+    fn test_parse_builds_the_tree() {
+        assert_eq!(
+            Expr::parse("3d6+2"),
+            Ok(Expr::Binary(Op::Add, Box::new(dice(3, 6)), Box::new(Expr::Number(2))))
+        );
+        assert_eq!(Expr::parse("d%"), Ok(dice(1, 100)));
+        assert_eq!(
+            Expr::parse("4d6kh3"),
+            Ok(Expr::Dice(Dice { count: 4, sides: 6, explode: false, keep: Some(Keep::Highest(3)) }))
+        );
+        assert_eq!(
+            Expr::parse("2D20KL1"),
+            Ok(Expr::Dice(Dice { count: 2, sides: 20, explode: false, keep: Some(Keep::Lowest(1)) }))
+        );
+        assert_eq!(Expr::parse("5d10!k2").unwrap().to_string(), "5d10!kh2");
+        // `*` before `+`, left to right within a level, and brackets only where needed.
+        assert_eq!(Expr::parse(" 1 + 2*3 - (4 - 5) ").unwrap().to_string(), "1 + 2 * 3 - (4 - 5)");
+        assert_eq!(Expr::parse("(1+2)*-d4").unwrap().to_string(), "(1 + 2) * -1d4");
+        assert_eq!(Expr::parse("2*(d8+1)/3").unwrap().to_string(), "2 * (1d8 + 1) / 3");
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_problem() {
+        assert_eq!(
+            Expr::parse("3d6+").unwrap_err(),
+            "expected a number, dice or '(' but the expression ended at column 5\n  3d6+\n      ^"
+        );
+        assert_eq!(Expr::parse("3d+2").unwrap_err(), "expected the number of sides after 'd' at column 3\n  3d+2\n    ^");
+        assert_eq!(Expr::parse("2d6 3").unwrap_err(), "unexpected '3' at column 5\n  2d6 3\n      ^");
+        assert_eq!(Expr::parse("(1+2").unwrap_err(), "'(' without a matching ')' at column 1\n  (1+2\n  ^");
+        assert!(Expr::parse("1+2)").unwrap_err().starts_with("')' without a matching '(' at column 4"));
+        assert!(Expr::parse("4d6kh5").unwrap_err().starts_with("can only keep 1 to 4 of 4 dice at column 4"));
+        assert!(Expr::parse("4d6k").unwrap_err().starts_with("expected how many dice to keep at column 5"));
+        assert!(Expr::parse("0d6").unwrap_err().starts_with("roll at least one die at column 1"));
+        assert!(Expr::parse("2d0").unwrap_err().starts_with("a die has 1 to 1000000 sides at column 3"));
+        assert!(Expr::parse("1001d6").unwrap_err().starts_with("at most 1000 dice can be rolled at once"));
+        assert!(Expr::parse("d1!").unwrap_err().starts_with("a one-sided die cannot explode at column 3"));
+        assert!(Expr::parse("2 ? 3").unwrap_err().starts_with("unexpected '?' at column 3"));
+        assert!(Expr::parse("99999999999999999999").unwrap_err().starts_with("the number is too large at column 1"));
+        assert!(Expr::parse(&"(".repeat(100)).unwrap_err().starts_with("brackets are nested too deeply"));
+        assert!(Expr::parse(&format!("1+{}1", "-".repeat(100_000))).unwrap_err().starts_with("too many minus signs in a row at column 67"));
+        assert!(Expr::parse(&format!("{}1", "-(".repeat(50))).is_err());
+        assert!(Expr::parse(&"1+".repeat(300_000)).unwrap_err().starts_with("an expression can have at most 1000 operators at column 2002"));
+        assert!(Expr::parse(&format!("1{}", "*1".repeat(1000))).is_ok());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(total("2+3*4"), Ok(14));
+        assert_eq!(total("(2+3)*4"), Ok(20));
+        assert_eq!(total("10-4-3"), Ok(3));
+        assert_eq!(total("7/2"), Ok(3));
+        assert_eq!(total("-7/2"), Ok(-4));
+        assert_eq!(total("7/-2"), Ok(-4));
+        assert_eq!(total("-6/2"), Ok(-3));
+        assert_eq!(total("--5"), Ok(5));
+        assert_eq!(total("1/(2-2)"), Err(String::from("division by zero")));
+        assert_eq!(total("9223372036854775807+1"), Err(String::from("the total is too large")));
+    }
+
+    #[test]
+    fn test_rolls_stay_in_range_and_add_up() {
+        let expr = Expr::parse("3d6+2").unwrap();
+        for seed in 0..200 {
+            let roll = expr.roll(&mut rng::seeded_rng(seed)).unwrap();
+            let group = &roll.groups[0];
+            assert_eq!(group.dice_rolled.len(), 3);
+            assert!(group.dice_rolled.iter().all(|die| (1..=6).contains(&die.value) && die.kept && !die.exploded));
+            assert_eq!(group.total, group.dice_rolled.iter().map(|die| i64::from(die.value)).sum::<i64>());
+            assert_eq!(roll.total, group.total + 2);
+        }
+        // The same seed rolls the same dice.
+        let expr = Expr::parse("10d20").unwrap();
+        assert_eq!(expr.roll(&mut rng::seeded_rng(5)), expr.roll(&mut rng::seeded_rng(5)));
+    }
+
+    #[test]
+    fn test_keep_highest_and_lowest() {
+        for (text, highest) in [("4d6kh3", true), ("2d20kl1", false)] {
+            let expr = Expr::parse(text).unwrap();
+            for seed in 0..100 {
+                let group = &expr.roll(&mut rng::seeded_rng(seed)).unwrap().groups[0];
+                let kept: Vec<u32> = group.dice_rolled.iter().filter(|die| die.kept).map(|die| die.value).collect();
+                let dropped: Vec<u32> = group.dice_rolled.iter().filter(|die| !die.kept).map(|die| die.value).collect();
+                assert_eq!(kept.len() + 1, group.dice_rolled.len());
+                if highest {
+                    assert!(dropped.iter().all(|low| kept.iter().all(|high| low <= high)));
+                } else {
+                    assert!(dropped.iter().all(|high| kept.iter().all(|low| low <= high)));
+                }
+                assert_eq!(group.total, kept.iter().map(|&value| i64::from(value)).sum::<i64>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_exploding_dice_add_a_die_for_each_highest_side() {
+        let expr = Expr::parse("10d2!").unwrap();
+        let group = &expr.roll(&mut rng::seeded_rng(3)).unwrap().groups[0];
+        let exploded = group.dice_rolled.iter().filter(|die| die.exploded).count();
+        assert!(exploded > 0);
+        assert_eq!(group.dice_rolled.len(), 10 + exploded);
+        assert!(group.dice_rolled.iter().all(|die| die.exploded == (die.value == 2)));
+    }
+
+    #[test]
+    fn test_write_roll() {
+        let expr = Expr::parse("4d6kh3+1").unwrap();
+        let roll = Roll {
+            groups: vec![DiceRoll {
+                dice: Dice { count: 4, sides: 6, explode: false, keep: Some(Keep::Highest(3)) },
+                dice_rolled: vec![
+                    Die { value: 6, kept: true, exploded: false },
+                    Die { value: 2, kept: false, exploded: false },
+                    Die { value: 5, kept: true, exploded: false },
+                    Die { value: 3, kept: true, exploded: false },
+                ],
+                total: 14,
+            }],
+            total: 15,
+        };
+        let mut output: Vec<u8> = Vec::new();
+        write_roll(&mut output, &expr, &roll).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "Rolling 4d6kh3 + 1\n4d6kh3: 6, (2), 5, 3 = 14\nTotal: 15\n");
+    }
+}
@@ -0,0 +1,294 @@
+// Module: Distributions
+// `--dist` draws numbers from a probability distribution instead of spreading them evenly
+// over a range. Each one is built from `rng.r#gen::<f64>()`, a uniform number in [0, 1),
+// with a textbook method, so the code shows how the shapes come about:
+// - uniform(a, b):      stretch [0, 1) to [a, b).
+// - normal(mean, sd):   the Box-Muller transform turns two uniform numbers into a normal one.
+// - lognormal(mu, s):   e to the power of a normal number.
+// - exponential(rate):  inverse transform sampling: -ln(1 - u) / rate.
+// - poisson(lambda):    Knuth's method: count uniform numbers until their product drops below e^-lambda.
+// - binomial(n, p):     the number of successes in n Bernoulli trials.
+// - bernoulli(p):       1 with probability p, else 0.
+// `mean` and `variance` give the exact values from the formulas, which the tests compare
+// with the averages of many samples.
+
+use rand::Rng;
+use std::f64::consts::PI;
+use std::fmt;
+
+// Knuth's method multiplies up to e^-lambda, which underflows for large lambda, so large
+// rates are drawn as a sum of smaller Poisson numbers. Both methods take time proportional
+// to lambda (or to n for the binomial), hence the upper limits.
+const POISSON_STEP: f64 = 500.0;
+const MAX_LAMBDA: f64 = 1_000_000.0;
+const MAX_TRIALS: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    Uniform { low: f64, high: f64 },
+    Normal { mean: f64, std_dev: f64 },
+    LogNormal { mu: f64, sigma: f64 },
+    Exponential { rate: f64 },
+    Poisson { lambda: f64 },
+    Binomial { trials: u64, p: f64 },
+    Bernoulli { p: f64 },
+}
+
+// (name, parameters, the parameters used when none are given) for the usage message and `parse`.
+pub const NAMES: &[(&str, &str, &str)] = &[
+    ("uniform", "low,high", "0,1"),
+    ("normal", "mean,sd", "0,1"),
+    ("lognormal", "mu,sigma", "0,1"),
+    ("exponential", "rate", "1"),
+    ("poisson", "lambda", "1"),
+    ("binomial", "n,p", "10,0.5"),
+    ("bernoulli", "p", "0.5"),
+];
+
+impl Distribution {
+    // "normal" or "normal:10,2": the name, then the parameters in the order of `NAMES`.
+    pub fn parse(text: &str) -> Result<Distribution, String> {
+        let (name, parameters) = text.split_once(':').unwrap_or((text, ""));
+        let name = name.trim().to_lowercase().replace('-', "");
+        let Some((name, expected, defaults)) = NAMES.iter().find(|(known, _, _)| *known == name) else {
+            let names: Vec<&str> = NAMES.iter().map(|(name, _, _)| *name).collect();
+            return Err(format!("unknown distribution '{}' (choose from {})", text, names.join(", ")));
+        };
+        let parameters = if parameters.trim().is_empty() { defaults } else { parameters };
+        let numbers: Vec<f64> = parameters
+            .split(',')
+            .map(|number| number.trim().parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("the parameters of {} must be numbers ({}), not '{}'", name, expected, parameters))?;
+        let wanted = expected.split(',').count();
+        if numbers.len() != wanted {
+            return Err(format!("{} takes {} parameter{} ({}), found {}", name, wanted, if wanted == 1 { "" } else { "s" }, expected, numbers.len()));
+        }
+        if numbers.iter().any(|number| !number.is_finite()) {
+            return Err(format!("the parameters of {} must be finite numbers", name));
+        }
+        let distribution = match *name {
+            "uniform" => Distribution::Uniform { low: numbers[0], high: numbers[1] },
+            "normal" => Distribution::Normal { mean: numbers[0], std_dev: numbers[1] },
+            "lognormal" => Distribution::LogNormal { mu: numbers[0], sigma: numbers[1] },
+            "exponential" => Distribution::Exponential { rate: numbers[0] },
+            "poisson" => Distribution::Poisson { lambda: numbers[0] },
+            "binomial" => {
+                if numbers[0] < 0.0 || numbers[0].fract() != 0.0 {
+                    return Err(format!("n of binomial must be a whole number, not {}", numbers[0]));
+                }
+                Distribution::Binomial { trials: numbers[0] as u64, p: numbers[1] }
+            }
+            _ => Distribution::Bernoulli { p: numbers[0] },
+        };
+        distribution.validate()?;
+        Ok(distribution)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let probability = |p: f64| {
+            if (0.0..=1.0).contains(&p) { Ok(()) } else { Err(format!("p must be between 0 and 1, not {}", p)) }
+        };
+        match *self {
+            Distribution::Uniform { low, high } if low >= high => {
+                Err(format!("uniform needs low < high, not {} and {}", low, high))
+            }
+            Distribution::Uniform { low, high } if !(high - low).is_finite() => {
+                Err(String::from("uniform needs high - low to fit in f64"))
+            }
+            Distribution::Normal { std_dev: spread, .. } | Distribution::LogNormal { sigma: spread, .. } if spread <= 0.0 => {
+                Err(format!("the standard deviation must be positive, not {}", spread))
+            }
+            Distribution::Exponential { rate } if rate <= 0.0 => Err(format!("the rate must be positive, not {}", rate)),
+            Distribution::Poisson { lambda } if lambda <= 0.0 || lambda > MAX_LAMBDA => {
+                Err(format!("lambda must be above 0 and at most {}, not {}", MAX_LAMBDA, lambda))
+            }
+            Distribution::Binomial { trials, .. } if trials > MAX_TRIALS => {
+                Err(format!("n must be at most {}, not {}", MAX_TRIALS, trials))
+            }
+            Distribution::Binomial { p, .. } | Distribution::Bernoulli { p } => probability(p),
+            _ => Ok(()),
+        }?;
+        // Otherwise the samples or the `--summary` would be infinite or NaN.
+        if !self.mean().is_finite() || !self.variance().is_finite() {
+            return Err(format!("the mean or variance of {} is too large for f64", self));
+        }
+        Ok(())
+    }
+
+    // Whether the samples are whole numbers (counts).
+    pub fn is_discrete(&self) -> bool {
+        matches!(self, Distribution::Poisson { .. } | Distribution::Binomial { .. } | Distribution::Bernoulli { .. })
+    }
+
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match *self {
+            Distribution::Uniform { low, high } => low + (high - low) * rng.r#gen::<f64>(),
+            Distribution::Normal { mean, std_dev } => mean + std_dev * standard_normal(rng),
+            Distribution::LogNormal { mu, sigma } => (mu + sigma * standard_normal(rng)).exp(),
+            // 1 - u is in (0, 1], so the logarithm is never infinite.
+            Distribution::Exponential { rate } => -(1.0 - rng.r#gen::<f64>()).ln() / rate,
+            Distribution::Poisson { lambda } => {
+                let mut left = lambda;
+                let mut count = 0;
+                while left > 0.0 {
+                    let step = left.min(POISSON_STEP);
+                    count += knuth_poisson(step, rng);
+                    left -= step;
+                }
+                count as f64
+            }
+            Distribution::Binomial { trials, p } => (0..trials).filter(|_| rng.r#gen::<f64>() < p).count() as f64,
+            Distribution::Bernoulli { p } => {
+                if rng.r#gen::<f64>() < p { 1.0 } else { 0.0 }
+            }
+        }
+    }
+
+    // The exact mean of the distribution.
+    pub fn mean(&self) -> f64 {
+        match *self {
+            Distribution::Uniform { low, high } => (low + high) / 2.0,
+            Distribution::Normal { mean, .. } => mean,
+            Distribution::LogNormal { mu, sigma } => (mu + sigma * sigma / 2.0).exp(),
+            Distribution::Exponential { rate } => 1.0 / rate,
+            Distribution::Poisson { lambda } => lambda,
+            Distribution::Binomial { trials, p } => trials as f64 * p,
+            Distribution::Bernoulli { p } => p,
+        }
+    }
+
+    // The exact variance of the distribution.
+    pub fn variance(&self) -> f64 {
+        match *self {
+            Distribution::Uniform { low, high } => (high - low).powi(2) / 12.0,
+            Distribution::Normal { std_dev, .. } => std_dev * std_dev,
+            Distribution::LogNormal { mu, sigma } => ((sigma * sigma).exp() - 1.0) * (2.0 * mu + sigma * sigma).exp(),
+            Distribution::Exponential { rate } => 1.0 / (rate * rate),
+            Distribution::Poisson { lambda } => lambda,
+            Distribution::Binomial { trials, p } => trials as f64 * p * (1.0 - p),
+            Distribution::Bernoulli { p } => p * (1.0 - p),
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Distribution::Uniform { low, high } => write!(f, "uniform(low {}, high {})", low, high),
+            Distribution::Normal { mean, std_dev } => write!(f, "normal(mean {}, sd {})", mean, std_dev),
+            Distribution::LogNormal { mu, sigma } => write!(f, "lognormal(mu {}, sigma {})", mu, sigma),
+            Distribution::Exponential { rate } => write!(f, "exponential(rate {})", rate),
+            Distribution::Poisson { lambda } => write!(f, "poisson(lambda {})", lambda),
+            Distribution::Binomial { trials, p } => write!(f, "binomial(n {}, p {})", trials, p),
+            Distribution::Bernoulli { p } => write!(f, "bernoulli(p {})", p),
+        }
+    }
+}
+
+// Box-Muller: for independent uniform u1 in (0, 1] and u2 in [0, 1),
+// sqrt(-2 ln u1) * cos(2 pi u2) has the standard normal distribution.
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1 = 1.0 - rng.r#gen::<f64>();
+    let u2 = rng.r#gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+fn knuth_poisson<R: Rng + ?Sized>(lambda: f64, rng: &mut R) -> u64 {
+    let limit = (-lambda).exp();
+    let mut product = rng.r#gen::<f64>();
+    let mut count = 0;
+    while product > limit {
+        product *= rng.r#gen::<f64>();
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random_number::report::Summary;
+    use crate::rng;
+
+    const SAMPLES: usize = 40_000;
+
+    // Draws many samples and checks their mean and variance against the formulas. The mean
+    // of n samples varies by about sd / sqrt(n), so 5 of those is a safe margin; the sample
+    // variance is allowed 5% (the lognormal, with its long tail, 15%).
+    fn check_moments(distribution: Distribution, count: usize) {
+        let mut rng = rng::seeded_rng(12);
+        let samples: Vec<f64> = (0..count).map(|_| distribution.sample(&mut rng)).collect();
+        let summary = Summary::of(&samples).unwrap();
+        let sd = distribution.variance().sqrt();
+        let margin = 5.0 * sd / (count as f64).sqrt();
+        assert!((summary.mean - distribution.mean()).abs() < margin, "{}: mean {}", distribution, summary.mean);
+        let tolerance = if matches!(distribution, Distribution::LogNormal { .. }) { 0.15 } else { 0.05 };
+        let error = (summary.variance - distribution.variance()).abs() / distribution.variance();
+        assert!(error < tolerance, "{}: variance {}", distribution, summary.variance);
+        if distribution.is_discrete() {
+            assert!(samples.iter().all(|sample| sample.fract() == 0.0 && *sample >= 0.0), "{}", distribution);
+        }
+    }
+
+    #[test]
+    fn test_samples_match_the_known_moments() {
+        for distribution in [
+            Distribution::Uniform { low: -2.0, high: 6.0 },
+            Distribution::Normal { mean: 10.0, std_dev: 3.0 },
+            Distribution::LogNormal { mu: 0.0, sigma: 0.5 },
+            Distribution::Exponential { rate: 2.0 },
+            Distribution::Poisson { lambda: 4.0 },
+            Distribution::Binomial { trials: 20, p: 0.3 },
+            Distribution::Bernoulli { p: 0.2 },
+        ] {
+            check_moments(distribution, SAMPLES);
+        }
+        // Large rates are drawn in steps (see `POISSON_STEP`); each sample costs about lambda draws.
+        check_moments(Distribution::Poisson { lambda: 1234.5 }, 5_000);
+    }
+
+    #[test]
+    fn test_known_moments() {
+        let lognormal = Distribution::LogNormal { mu: 0.0, sigma: 1.0 };
+        assert!((lognormal.mean() - 1.0_f64.exp().sqrt()).abs() < 1e-12);
+        assert_eq!(Distribution::Uniform { low: 0.0, high: 12.0 }.variance(), 12.0);
+        assert_eq!(Distribution::Binomial { trials: 10, p: 0.5 }.variance(), 2.5);
+    }
+
+    #[test]
+    fn test_normal_samples_are_bell_shaped() {
+        // About 68% of a normal distribution lies within one standard deviation of the mean.
+        let normal = Distribution::Normal { mean: 0.0, std_dev: 1.0 };
+        let mut rng = rng::seeded_rng(3);
+        let inside = (0..SAMPLES).filter(|_| normal.sample(&mut rng).abs() < 1.0).count();
+        assert!((inside as f64 / SAMPLES as f64 - 0.6827).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Distribution::parse("normal"), Ok(Distribution::Normal { mean: 0.0, std_dev: 1.0 }));
+        assert_eq!(Distribution::parse("Normal:10, 2"), Ok(Distribution::Normal { mean: 10.0, std_dev: 2.0 }));
+        assert_eq!(Distribution::parse("log-normal:0,0.5"), Ok(Distribution::LogNormal { mu: 0.0, sigma: 0.5 }));
+        assert_eq!(Distribution::parse("binomial:20,0.3"), Ok(Distribution::Binomial { trials: 20, p: 0.3 }));
+        assert_eq!(Distribution::parse("poisson:4").unwrap().to_string(), "poisson(lambda 4)");
+        assert_eq!(
+            Distribution::parse("gamma").unwrap_err(),
+            "unknown distribution 'gamma' (choose from uniform, normal, lognormal, exponential, poisson, binomial, bernoulli)"
+        );
+        assert_eq!(Distribution::parse("normal:1").unwrap_err(), "normal takes 2 parameters (mean,sd), found 1");
+        assert_eq!(Distribution::parse("poisson:x").unwrap_err(), "the parameters of poisson must be numbers (lambda), not 'x'");
+        assert_eq!(Distribution::parse("normal:0,0").unwrap_err(), "the standard deviation must be positive, not 0");
+        assert_eq!(Distribution::parse("bernoulli:1.5").unwrap_err(), "p must be between 0 and 1, not 1.5");
+        assert_eq!(Distribution::parse("binomial:2.5,0.5").unwrap_err(), "n of binomial must be a whole number, not 2.5");
+        assert_eq!(Distribution::parse("uniform:3,1").unwrap_err(), "uniform needs low < high, not 3 and 1");
+        assert!(Distribution::parse("exponential:inf").is_err());
+        assert_eq!(Distribution::parse("uniform:-1e308,1e308").unwrap_err(), "uniform needs high - low to fit in f64");
+        assert_eq!(
+            Distribution::parse("lognormal:800,1").unwrap_err(),
+            "the mean or variance of lognormal(mu 800, sigma 1) is too large for f64"
+        );
+        assert!(Distribution::parse("lognormal:1,30").is_err(), "the variance overflows");
+        assert!(Distribution::parse("lognormal:1,2").is_ok());
+    }
+}
@@ -0,0 +1,250 @@
+// Module: Passwords and Passphrases
+// `random_number password` makes test credentials locally, with code short enough to review:
+// - a password of random characters from the chosen classes (lower and upper case letters,
+//   digits, symbols), optionally without look-alikes such as `l`, `1` and `I`. By default
+//   every chosen class appears at least once: passwords are drawn until one has them all
+//   (rejection sampling), which keeps every allowed password equally likely.
+// - a diceware-style passphrase: words from a bundled list of 6^4 = 1296 words, so each word
+//   can be picked with four six-sided dice, one digit of a base-6 number per die.
+// The entropy is log2 of the number of results the settings allow, all equally likely: how
+// many yes/no questions an attacker who knows the settings needs to guess one.
+
+use rand::Rng;
+use std::fmt;
+
+// Characters that are easy to mix up when read or typed.
+const AMBIGUOUS: &str = "Il1|O0`'\"";
+const SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+// The bundled word list: one lowercase word per line, sorted.
+const WORD_LIST: &str = include_str!("../../wordlists/diceware.txt");
+const DICE_PER_WORD: u32 = 4;
+pub const MAX_LENGTH: usize = 1024;
+pub const MAX_WORDS: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Digits,
+    Symbols,
+}
+
+impl CharClass {
+    pub const ALL: [CharClass; 4] = [CharClass::Lower, CharClass::Upper, CharClass::Digits, CharClass::Symbols];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CharClass::Lower => "lower",
+            CharClass::Upper => "upper",
+            CharClass::Digits => "digits",
+            CharClass::Symbols => "symbols",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<CharClass> {
+        let name = name.trim().to_lowercase();
+        CharClass::ALL.into_iter().find(|class| class.name() == name)
+    }
+
+    fn chars(self, exclude_ambiguous: bool) -> Vec<char> {
+        let all: Vec<char> = match self {
+            CharClass::Lower => ('a'..='z').collect(),
+            CharClass::Upper => ('A'..='Z').collect(),
+            CharClass::Digits => ('0'..='9').collect(),
+            CharClass::Symbols => SYMBOLS.chars().collect(),
+        };
+        all.into_iter().filter(|letter| !(exclude_ambiguous && AMBIGUOUS.contains(*letter))).collect()
+    }
+}
+
+impl fmt::Display for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// What to generate; `characters` and `passphrase` check the settings, so generating cannot fail.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Recipe {
+    Characters { length: usize, classes: Vec<Vec<char>>, require_each: bool },
+    Passphrase { words: usize, separator: String, capitalize: bool },
+}
+
+impl Recipe {
+    pub fn characters(length: usize, classes: &[CharClass], require_each: bool, exclude_ambiguous: bool) -> Result<Recipe, String> {
+        if classes.is_empty() {
+            return Err(String::from("choose at least one character class"));
+        }
+        if !(1..=MAX_LENGTH).contains(&length) {
+            return Err(format!("the length must be 1 to {}, not {}", MAX_LENGTH, length));
+        }
+        let mut unique: Vec<CharClass> = Vec::new();
+        for class in classes {
+            if !unique.contains(class) {
+                unique.push(*class);
+            }
+        }
+        if require_each && length < unique.len() {
+            return Err(format!("{} characters cannot include all {} classes", length, unique.len()));
+        }
+        let classes = unique.iter().map(|class| class.chars(exclude_ambiguous)).collect();
+        Ok(Recipe::Characters { length, classes, require_each })
+    }
+
+    pub fn passphrase(words: usize, separator: &str, capitalize: bool) -> Result<Recipe, String> {
+        if !(1..=MAX_WORDS).contains(&words) {
+            return Err(format!("a passphrase has 1 to {} words, not {}", MAX_WORDS, words));
+        }
+        Ok(Recipe::Passphrase { words, separator: separator.to_string(), capitalize })
+    }
+
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        match self {
+            Recipe::Characters { length, classes, require_each } => {
+                let alphabet: Vec<char> = classes.concat();
+                loop {
+                    let password: Vec<char> = (0..*length).map(|_| alphabet[rng.gen_range(0..alphabet.len())]).collect();
+                    if !require_each || classes.iter().all(|class| password.iter().any(|letter| class.contains(letter))) {
+                        return password.into_iter().collect();
+                    }
+                }
+            }
+            Recipe::Passphrase { words, separator, capitalize } => {
+                let list = word_list();
+                let chosen: Vec<String> = (0..*words)
+                    .map(|_| {
+                        let word = list[roll_word_index(rng)];
+                        if *capitalize { capitalized(word) } else { word.to_string() }
+                    })
+                    .collect();
+                chosen.join(separator)
+            }
+        }
+    }
+
+    // log2 of how many different results these settings can give.
+    pub fn entropy_bits(&self) -> f64 {
+        match self {
+            Recipe::Characters { length, classes, require_each } => {
+                let size = classes.iter().map(Vec::len).sum::<usize>() as f64;
+                let all = *length as f64 * size.log2();
+                if !*require_each {
+                    return all;
+                }
+                // Inclusion-exclusion: all strings, minus those missing one class, plus those
+                // missing two, ... Each term is divided by size^length to stay in range.
+                let mut share = 0.0;
+                for missing in 0..(1_usize << classes.len()) {
+                    let left: usize = classes
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, _)| missing & (1 << index) == 0)
+                        .map(|(_, class)| class.len())
+                        .sum();
+                    let sign = if missing.count_ones() % 2 == 0 { 1.0 } else { -1.0 };
+                    share += sign * (left as f64 / size).powi(*length as i32);
+                }
+                all + share.log2()
+            }
+            Recipe::Passphrase { words, .. } => *words as f64 * (word_list().len() as f64).log2(),
+        }
+    }
+}
+
+pub fn word_list() -> Vec<&'static str> {
+    WORD_LIST.lines().collect()
+}
+
+// Four dice, each a digit of a base-6 number from 0 to 1295.
+fn roll_word_index<R: Rng + ?Sized>(rng: &mut R) -> usize {
+    (0..DICE_PER_WORD).fold(0, |index, _| index * 6 + rng.gen_range(0..6))
+}
+
+fn capitalized(word: &str) -> String {
+    let mut letters = word.chars();
+    match letters.next() {
+        Some(first) => first.to_uppercase().chain(letters).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+
+    #[test]
+    fn test_word_list() {
+        let words = word_list();
+        assert_eq!(words.len(), 6_usize.pow(DICE_PER_WORD));
+        assert!(words.iter().all(|word| !word.is_empty() && word.chars().all(|letter| letter.is_ascii_lowercase())));
+        assert!(words.windows(2).all(|pair| pair[0] < pair[1]), "sorted and without repeats");
+        let mut rng = rng::seeded_rng(2);
+        assert!((0..1000).all(|_| roll_word_index(&mut rng) < words.len()));
+    }
+
+    #[test]
+    fn test_passwords_use_the_chosen_classes() {
+        let recipe = Recipe::characters(12, &[CharClass::Lower, CharClass::Digits], true, false).unwrap();
+        let mut rng = rng::seeded_rng(4);
+        for _ in 0..200 {
+            let password = recipe.generate(&mut rng);
+            assert_eq!(password.chars().count(), 12);
+            assert!(password.chars().all(|letter| letter.is_ascii_lowercase() || letter.is_ascii_digit()));
+            assert!(password.chars().any(|letter| letter.is_ascii_lowercase()));
+            assert!(password.chars().any(|letter| letter.is_ascii_digit()));
+        }
+    }
+
+    #[test]
+    fn test_every_class_is_included_even_when_short() {
+        let recipe = Recipe::characters(4, &CharClass::ALL, true, true).unwrap();
+        let mut rng = rng::seeded_rng(6);
+        for _ in 0..200 {
+            let password = recipe.generate(&mut rng);
+            for class in CharClass::ALL {
+                assert!(password.chars().any(|letter| class.chars(true).contains(&letter)), "{} in {}", class, password);
+            }
+            assert!(!password.chars().any(|letter| AMBIGUOUS.contains(letter)));
+        }
+    }
+
+    #[test]
+    fn test_character_entropy() {
+        // 10 digits, 4 of them: 10^4 passwords.
+        let digits = Recipe::characters(4, &[CharClass::Digits], true, false).unwrap();
+        assert!((digits.entropy_bits() - 10_000_f64.log2()).abs() < 1e-9);
+        // Lower case letters and digits, length 2, both required: 26 * 10 * 2 orders.
+        let mixed = Recipe::characters(2, &[CharClass::Lower, CharClass::Digits], true, false).unwrap();
+        assert!((mixed.entropy_bits() - 520_f64.log2()).abs() < 1e-9);
+        let free = Recipe::characters(2, &[CharClass::Lower, CharClass::Digits], false, false).unwrap();
+        assert!((free.entropy_bits() - 1296_f64.log2()).abs() < 1e-9);
+        // Without look-alikes the alphabet shrinks: 94 - 9 = 85 characters.
+        let long = Recipe::characters(20, &CharClass::ALL, false, true).unwrap();
+        assert!((long.entropy_bits() - 20.0 * 85_f64.log2()).abs() < 1e-9);
+        // Requiring every class rules out some passwords (mostly those without a digit).
+        let required = Recipe::characters(20, &CharClass::ALL, true, true).unwrap();
+        assert!(required.entropy_bits() < long.entropy_bits() && required.entropy_bits() > long.entropy_bits() - 0.5);
+    }
+
+    #[test]
+    fn test_passphrases() {
+        let recipe = Recipe::passphrase(5, "-", true).unwrap();
+        let words = word_list();
+        let phrase = recipe.generate(&mut rng::seeded_rng(8));
+        let parts: Vec<&str> = phrase.split('-').collect();
+        assert_eq!(parts.len(), 5);
+        assert!(parts.iter().all(|part| words.contains(&part.to_lowercase().as_str()) && part.starts_with(char::is_uppercase)));
+        assert!((recipe.entropy_bits() - 5.0 * 1296_f64.log2()).abs() < 1e-9);
+        assert_eq!(recipe.generate(&mut rng::seeded_rng(8)), phrase);
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        assert_eq!(Recipe::characters(8, &[], true, false).unwrap_err(), "choose at least one character class");
+        assert_eq!(Recipe::characters(0, &[CharClass::Lower], true, false).unwrap_err(), "the length must be 1 to 1024, not 0");
+        assert_eq!(Recipe::characters(3, &CharClass::ALL, true, false).unwrap_err(), "3 characters cannot include all 4 classes");
+        assert!(Recipe::characters(3, &CharClass::ALL, false, false).is_ok());
+        assert_eq!(Recipe::passphrase(0, " ", false).unwrap_err(), "a passphrase has 1 to 64 words, not 0");
+    }
+}
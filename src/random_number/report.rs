@@ -0,0 +1,176 @@
+// Module: Summaries and Histograms
+// Instead of printing every value, `--summary` describes them (how many, mean, variance,
+// smallest and largest) and `--histogram` draws their shape with `#` bars, one line per
+// group of values. Together with `--dist` they show what a distribution looks like.
+
+use std::io::{self, Write};
+
+// The widest bar, in characters.
+const BAR_WIDTH: usize = 50;
+// Whole numbers get one line each if there are at most this many different ones.
+const MAX_VALUE_ROWS: f64 = 40.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    // The sample variance: squared distances from the mean, divided by count - 1.
+    pub variance: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Summary {
+    // `None` for no values. Uses Welford's method, which updates the mean and the sum of
+    // squared distances one value at a time without losing precision on large numbers.
+    pub fn of(values: &[f64]) -> Option<Summary> {
+        let (&first, _) = values.split_first()?;
+        let mut mean = 0.0;
+        let mut squares = 0.0;
+        let (mut min, mut max) = (first, first);
+        for (index, &value) in values.iter().enumerate() {
+            let delta = value - mean;
+            mean += delta / (index + 1) as f64;
+            squares += delta * (value - mean);
+            min = min.min(value);
+            max = max.max(value);
+        }
+        let variance = if values.len() > 1 { squares / (values.len() - 1) as f64 } else { 0.0 };
+        Some(Summary { count: values.len(), mean, variance, min, max })
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    // `expected` is the exact (mean, variance) of the distribution, if there is one.
+    pub fn write(&self, out: &mut dyn Write, expected: Option<(f64, f64)>) -> io::Result<()> {
+        let with_expected = |value: f64, exact: Option<f64>| match exact {
+            Some(exact) => format!("{:<14}(expected {})", round(value), round(exact)),
+            None => round(value),
+        };
+        writeln!(out, "Count:     {}", self.count)?;
+        writeln!(out, "Mean:      {}", with_expected(self.mean, expected.map(|(mean, _)| mean)))?;
+        writeln!(out, "Variance:  {}", with_expected(self.variance, expected.map(|(_, variance)| variance)))?;
+        writeln!(out, "Std dev:   {}", with_expected(self.std_dev(), expected.map(|(_, variance)| variance.sqrt())))?;
+        writeln!(out, "Min:       {}", round(self.min))?;
+        writeln!(out, "Max:       {}", round(self.max))
+    }
+}
+
+// Four decimals are plenty to compare with the expected values, and trailing zeros go.
+fn round(value: f64) -> String {
+    let text = format!("{:.4}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { String::from("0") } else { text.to_string() }
+}
+
+// Draws one bar per bin. If `whole_numbers` is set and there are few different values,
+// every value gets its own line; otherwise the range is cut into `bins` equal parts.
+pub fn write_histogram(out: &mut dyn Write, values: &[f64], bins: usize, whole_numbers: bool) -> io::Result<()> {
+    let Some(summary) = Summary::of(values) else {
+        return writeln!(out, "(no values)");
+    };
+    let (min, max) = (summary.min, summary.max);
+    let rows: Vec<(String, usize)> = if whole_numbers && max - min < MAX_VALUE_ROWS {
+        let mut counts = vec![0; (max - min) as usize + 1];
+        for value in values {
+            counts[(value - min) as usize] += 1;
+        }
+        counts.into_iter().enumerate().map(|(index, count)| (round(min + index as f64), count)).collect()
+    } else if max == min {
+        vec![(round(min), values.len())]
+    } else {
+        let bins = bins.max(1);
+        let width = (max - min) / bins as f64;
+        let mut counts = vec![0; bins];
+        for value in values {
+            // The largest value belongs in the last bin, not in one after it.
+            let bin = (((value - min) / width) as usize).min(bins - 1);
+            counts[bin] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(index, count)| {
+                let low = min + width * index as f64;
+                let closing = if index + 1 == bins { ']' } else { ')' };
+                (format!("[{}, {}{}", round(low), round(low + width), closing), count)
+            })
+            .collect()
+    };
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let largest = rows.iter().map(|(_, count)| *count).max().unwrap_or(0).max(1);
+    for (label, count) in rows {
+        // Every bin with values gets at least one `#`, so rare values stay visible.
+        let bar = if count == 0 { 0 } else { (count * BAR_WIDTH / largest).max(1) };
+        writeln!(out, "{:>label_width$} | {:<BAR_WIDTH$} {}", label, "#".repeat(bar), count)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(write: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+        let mut output: Vec<u8> = Vec::new();
+        write(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_summary_of_known_values() {
+        let summary = Summary::of(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!((summary.count, summary.mean, summary.min, summary.max), (8, 5.0, 2.0, 9.0));
+        assert!((summary.variance - 32.0 / 7.0).abs() < 1e-12);
+        assert_eq!(Summary::of(&[3.0]).unwrap().variance, 0.0);
+        assert_eq!(Summary::of(&[]), None);
+        // Welford's method keeps its precision far away from zero.
+        let shifted: Vec<f64> = [1.0, 2.0, 3.0].iter().map(|value| value + 1e9).collect();
+        assert!((Summary::of(&shifted).unwrap().variance - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_summary_output() {
+        let summary = Summary::of(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(
+            text(|out| summary.write(out, Some((2.5, 1.25)))),
+            "Count:     4\n\
+             Mean:      2.5           (expected 2.5)\n\
+             Variance:  1.6667        (expected 1.25)\n\
+             Std dev:   1.291         (expected 1.118)\n\
+             Min:       1\n\
+             Max:       4\n"
+        );
+        assert!(text(|out| summary.write(out, None)).contains("Mean:      2.5\n"));
+    }
+
+    #[test]
+    fn test_histogram_of_whole_numbers() {
+        let values = [1.0, 2.0, 2.0, 4.0, 2.0, 1.0];
+        assert_eq!(
+            text(|out| write_histogram(out, &values, 10, true)),
+            format!(
+                "1 | {:<50} 2\n2 | {:<50} 3\n3 | {:<50} 0\n4 | {:<50} 1\n",
+                "#".repeat(33),
+                "#".repeat(50),
+                "",
+                "#".repeat(16)
+            )
+        );
+    }
+
+    #[test]
+    fn test_histogram_bins() {
+        let values: Vec<f64> = (0..100).map(|value| value as f64 / 10.0).collect();
+        let output = text(|out| write_histogram(out, &values, 4, false));
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("   [0, 2.475) | "));
+        assert!(lines[3].starts_with(" [7.425, 9.9] | ") && lines[3].ends_with(" 25"));
+        assert_eq!(text(|out| write_histogram(out, &[], 4, false)), "(no values)\n");
+        // All values equal: one full bar.
+        assert_eq!(text(|out| write_histogram(out, &[0.5, 0.5], 3, false)), format!("0.5 | {} 2\n", "#".repeat(50)));
+    }
+}
@@ -1,17 +1,22 @@
 // Module: References and Dereferencing
 // This module explains how to use references to access values and the dereference operator (*).
 
-pub fn demonstrate_references_and_dereferencing() {
-    println!("\n--- References and Dereferencing ---");
+use crate::lesson::{Chapter, Lesson};
+use std::io::{self, BufRead, Write};
+
+// `*w = *w + 1` below could be written `*w += 1`; the long form shows each dereference.
+#[allow(clippy::assign_op_pattern)]
+pub fn demonstrate_references_and_dereferencing(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n--- References and Dereferencing ---")?;
 
     let x: u8 = 5;
     let y: &u8 = &x; // y is an immutable reference to x. It holds the memory address of x.
 
-    println!("Value of x: {}", x);
-    println!("Value pointed to by y: {}", *y); // Use '*' to dereference y and get the value it points to.
-    println!("Value of y itself (memory address of x): {:p}", y);
-    println!("Memory address of x: {:p}", &x);
-    println!("Memory address of y (where the reference itself is stored): {:p}", &y);
+    writeln!(out, "Value of x: {}", x)?;
+    writeln!(out, "Value pointed to by y: {}", *y)?; // Use '*' to dereference y and get the value it points to.
+    writeln!(out, "Value of y itself (memory address of x): {:p}", y)?;
+    writeln!(out, "Memory address of x: {:p}", &x)?;
+    writeln!(out, "Memory address of y (where the reference itself is stored): {:p}", &y)?;
 
     // Comparing the value and the pointed-to value
     assert_eq!(5, x);
@@ -20,29 +25,29 @@ pub fn demonstrate_references_and_dereferencing() {
 
     // Modifying a value through a mutable reference using dereferencing
     let mut z: i32 = 16;
-    println!("Initial value of z: {}", z);
+    writeln!(out, "Initial value of z: {}", z)?;
 
     let w: &mut i32 = &mut z; // w is a mutable reference to z.
     *w = *w + 1; // Dereference w to get the value, add 1, then assign back to the dereferenced w.
                  // This modifies the value stored in z.
 
     // Print the value using *w. This uses the mutable borrow.
-    println!("Value pointed to by w after modification: {}", *w);
+    writeln!(out, "Value pointed to by w after modification: {}", *w)?;
     // Now that w is not used in a subsequent line, its borrow might be considered ended by NLL,
     // allowing z to be immutably borrowed for printing.
-    println!("Value of z after modification (should be same as *w): {}", z);
+    writeln!(out, "Value of z after modification (should be same as *w): {}", z)?;
 
 
     // Dereferencing also works with other types, like String
     let s1 = String::from("hello");
     let s2 = &s1; // s2 is an immutable reference to s1
 
-    println!("s1: {}", s1);
-    println!("*s2 (dereferenced): {}", *s2); // Dereferencing a &String gives a &str (due to Deref coercion)
+    writeln!(out, "s1: {}", s1)?;
+    writeln!(out, "*s2 (dereferenced): {}", *s2)?; // Dereferencing a &String gives a &str (due to Deref coercion)
                                        // but for basic understanding, it "accesses" the string data.
-    println!("Length of s1: {}", s1.len());
-    println!("Length using (*s2): {}", (*s2).len()); // Explicit dereference then method call
-    println!("Length using s2: {}", s2.len());     // Implicit dereference coercion for method calls
+    writeln!(out, "Length of s1: {}", s1.len())?;
+    writeln!(out, "Length using (*s2): {}", (*s2).len())?; // Explicit dereference then method call
+    writeln!(out, "Length using s2: {}", s2.len())?;     // Implicit dereference coercion for method calls
 
     // Original prac5.rs code:
     // fn main(){
@@ -57,4 +62,33 @@ pub fn demonstrate_references_and_dereferencing() {
     //     println!("z = {}", z);
     // }
     // The refactored code explains these concepts more thoroughly.
+    Ok(())
+}
+
+pub struct ReferencesAndDereferencingLesson;
+
+impl Lesson for ReferencesAndDereferencingLesson {
+    fn id(&self) -> &'static str {
+        "references_and_dereferencing"
+    }
+
+    fn title(&self) -> &'static str {
+        "References and Dereferencing"
+    }
+
+    fn chapter(&self) -> Chapter {
+        Chapter::OwnershipAndBorrowing
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["references_borrowing"]
+    }
+
+    fn run(&self, _input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        demonstrate_references_and_dereferencing(out)
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("references_and_dereferencing.rs")
+    }
 }
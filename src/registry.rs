@@ -0,0 +1,145 @@
+// Module: Lesson Registry
+// The single list of all lessons, in the order they should be studied.
+// To add a topic: create the module, implement `Lesson` for it and add it to `LESSONS` below.
+
+use crate::lesson::{Chapter, Lesson};
+use crate::{
+    array_type, basic_syntax, boolean_type, char_type, control_flow, floating_point_types, loops,
+    more_borrowing_rules, original_main, references_and_dereferencing, references_borrowing,
+    string_ownership, user_input, vector_type,
+};
+
+static LESSONS: &[&dyn Lesson] = &[
+    &basic_syntax::BasicSyntaxLesson,
+    &string_ownership::StringOwnershipLesson,
+    &references_borrowing::ReferencesBorrowingLesson,
+    &more_borrowing_rules::MoreBorrowingRulesLesson,
+    &references_and_dereferencing::ReferencesAndDereferencingLesson,
+    &floating_point_types::FloatingPointTypesLesson,
+    &boolean_type::BooleanTypeLesson,
+    &char_type::CharTypeLesson,
+    &array_type::ArrayTypeLesson,
+    &vector_type::VectorTypeLesson,
+    &control_flow::ControlFlowLesson,
+    &loops::LoopsLesson,
+    &user_input::UserInputLesson,
+    &original_main::OriginalMainLesson,
+];
+
+// All lessons, in the order they are listed above.
+pub fn all() -> &'static [&'static dyn Lesson] {
+    LESSONS
+}
+
+pub fn find(id: &str) -> Option<&'static dyn Lesson> {
+    all().iter().copied().find(|lesson| lesson.id() == id)
+}
+
+// Case-insensitive search over each lesson's id, title and chapter.
+pub fn search(keyword: &str) -> Vec<&'static dyn Lesson> {
+    let keyword = keyword.trim().to_lowercase();
+    all()
+        .iter()
+        .copied()
+        .filter(|lesson| {
+            let chapter = lesson.chapter();
+            [lesson.id(), lesson.title(), chapter.title(), chapter.slug()]
+                .iter()
+                .any(|text| text.to_lowercase().contains(&keyword))
+        })
+        .collect()
+}
+
+pub fn by_chapter(chapter: Chapter) -> impl Iterator<Item = &'static dyn Lesson> {
+    all().iter().copied().filter(move |lesson| lesson.chapter() == chapter)
+}
+
+// Returns all lessons so that every lesson comes after its prerequisites.
+// Lessons keep their registry order unless a prerequisite forces them later.
+pub fn study_order() -> Vec<&'static dyn Lesson> {
+    let mut ordered: Vec<&'static dyn Lesson> = Vec::new();
+    let mut remaining: Vec<&'static dyn Lesson> = all().to_vec();
+
+    while !remaining.is_empty() {
+        let is_done = |id: &&str| ordered.iter().any(|lesson| lesson.id() == *id);
+        let next = remaining
+            .iter()
+            .position(|lesson| lesson.prerequisites().iter().all(is_done));
+        match next {
+            Some(index) => ordered.push(remaining.remove(index)),
+            None => {
+                // A prerequisite is missing or there is a cycle. Keep the registry order
+                // for the rest instead of dropping lessons (the tests catch this case).
+                ordered.append(&mut remaining);
+            }
+        }
+    }
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_lesson_ids_are_unique() {
+        let mut seen = HashSet::new();
+        for lesson in all() {
+            assert!(seen.insert(lesson.id()), "duplicate lesson id: {}", lesson.id());
+        }
+    }
+
+    #[test]
+    fn test_prerequisites_refer_to_known_lessons() {
+        for lesson in all() {
+            for id in lesson.prerequisites() {
+                assert!(
+                    all().iter().any(|other| other.id() == *id),
+                    "{} depends on unknown lesson {}",
+                    lesson.id(),
+                    id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_study_order_puts_prerequisites_first() {
+        let order: Vec<&str> = study_order().iter().map(|lesson| lesson.id()).collect();
+        assert_eq!(order.len(), all().len());
+        for lesson in all() {
+            let position = order.iter().position(|id| *id == lesson.id()).unwrap();
+            for prerequisite in lesson.prerequisites() {
+                let prerequisite_position = order.iter().position(|id| id == prerequisite).unwrap();
+                assert!(prerequisite_position < position, "{} runs before {}", lesson.id(), prerequisite);
+            }
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("loops").map(|lesson| lesson.id()), Some("loops"));
+        assert!(find("closures").is_none());
+    }
+
+    #[test]
+    fn test_search_matches_id_title_and_chapter() {
+        let ids = |keyword: &str| -> Vec<&str> { search(keyword).iter().map(|lesson| lesson.id()).collect() };
+        assert_eq!(ids("FLOAT"), ["floating_point_types"]);
+        assert_eq!(ids("match"), ["control_flow"]);
+        // "ownership" is also the chapter slug, so every lesson in that chapter matches.
+        assert_eq!(ids("ownership").len(), 4);
+        assert!(ids("closures").is_empty());
+    }
+
+    #[test]
+    fn test_by_chapter() {
+        let ownership: Vec<&str> = by_chapter(Chapter::OwnershipAndBorrowing).map(|lesson| lesson.id()).collect();
+        assert_eq!(
+            ownership,
+            ["string_ownership", "references_borrowing", "more_borrowing_rules", "references_and_dereferencing"]
+        );
+        assert_eq!(by_chapter(Chapter::StructsAndEnums).count(), 0);
+    }
+}
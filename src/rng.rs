@@ -0,0 +1,235 @@
+// Module: Reproducible Random Numbers
+// The games and the random number demo get their randomness from here instead of calling
+// `rand::thread_rng()`. Every run uses a seedable generator (`StdRng`), started from a
+// 64-bit seed: the same seed always produces the same sequence, so a game or a demo can be
+// replayed exactly. The seed comes from, in order:
+//   1. the `--seed <n>` option,
+//   2. the `RUST_PRACTICE_SEED` environment variable,
+//   3. the operating system's random source, when neither is set.
+// (`StdRng` only promises the same sequence for the same version of the `rand` crate.)
+// `--rng <name>` swaps `StdRng` for one of the generators written from scratch in
+// rng/generators.rs; `RngKind::seeded` starts the chosen one from the seed.
+
+pub mod generators;
+
+use generators::{Lcg, Pcg32, SplitMix64, Xorshift64Star, Xoshiro256StarStar};
+use rand::rngs::StdRng;
+use rand::{Error, RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt;
+
+pub const SEED_VAR: &str = "RUST_PRACTICE_SEED";
+
+// Reads a seed written as a decimal number (`--seed 42`).
+pub fn parse_seed(text: &str) -> Result<u64, String> {
+    text.trim()
+        .parse::<u64>()
+        .map_err(|_| format!("a seed must be a whole number from 0 to {}, not '{}'", u64::MAX, text))
+}
+
+// The seed for this run: `explicit` (from `--seed`), then `RUST_PRACTICE_SEED`, then a fresh random one.
+pub fn choose_seed(explicit: Option<u64>) -> Result<u64, String> {
+    choose_seed_from(explicit, env::var(SEED_VAR).ok())
+}
+
+// Same as `choose_seed`, but with the environment variable passed in so the rules can be unit-tested.
+fn choose_seed_from(explicit: Option<u64>, variable: Option<String>) -> Result<u64, String> {
+    Ok(given_seed_from(explicit, variable)?.unwrap_or_else(rand::random))
+}
+
+// The seed from `--seed` or `RUST_PRACTICE_SEED`, or `None` if neither is set.
+fn given_seed_from(explicit: Option<u64>, variable: Option<String>) -> Result<Option<u64>, String> {
+    if explicit.is_some() {
+        return Ok(explicit);
+    }
+    match variable.filter(|value| !value.trim().is_empty()) {
+        Some(value) => parse_seed(&value).map(Some).map_err(|error| format!("{}: {}", SEED_VAR, error)),
+        None => Ok(None),
+    }
+}
+
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+// The generators `--rng` can choose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RngKind {
+    #[default]
+    Std,
+    Lcg,
+    Xorshift64Star,
+    Pcg32,
+    SplitMix64,
+    Xoshiro256StarStar,
+}
+
+impl RngKind {
+    pub const ALL: [RngKind; 6] = [
+        RngKind::Std,
+        RngKind::Lcg,
+        RngKind::Xorshift64Star,
+        RngKind::Pcg32,
+        RngKind::SplitMix64,
+        RngKind::Xoshiro256StarStar,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RngKind::Std => "std",
+            RngKind::Lcg => "lcg",
+            RngKind::Xorshift64Star => "xorshift64star",
+            RngKind::Pcg32 => "pcg32",
+            RngKind::SplitMix64 => "splitmix64",
+            RngKind::Xoshiro256StarStar => "xoshiro256starstar",
+        }
+    }
+
+    // Also takes the names with stars, `xorshift64*` and `xoshiro256**`.
+    pub fn from_name(name: &str) -> Option<RngKind> {
+        let name = name.trim().to_lowercase().replace('*', "star");
+        RngKind::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    pub fn is_std(&self) -> bool {
+        *self == RngKind::Std
+    }
+
+    pub fn seeded(self, seed: u64) -> AnyRng {
+        match self {
+            RngKind::Std => AnyRng::Std(Box::new(seeded_rng(seed))),
+            RngKind::Lcg => AnyRng::Lcg(Lcg::seed_from_u64(seed)),
+            RngKind::Xorshift64Star => AnyRng::Xorshift64Star(Xorshift64Star::seed_from_u64(seed)),
+            RngKind::Pcg32 => AnyRng::Pcg32(Pcg32::seed_from_u64(seed)),
+            RngKind::SplitMix64 => AnyRng::SplitMix64(SplitMix64::seed_from_u64(seed)),
+            RngKind::Xoshiro256StarStar => AnyRng::Xoshiro256StarStar(Xoshiro256StarStar::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl fmt::Display for RngKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// Reads the name given to `--rng`.
+pub fn parse_rng(name: &str) -> Result<RngKind, String> {
+    RngKind::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = RngKind::ALL.iter().map(|kind| kind.name()).collect();
+        format!("unknown generator '{}' (choose from {})", name, names.join(", "))
+    })
+}
+
+// One of the generators, chosen while the program runs; it passes every call on.
+// (`StdRng` is boxed: its 320 bytes would make every variant that big.)
+#[derive(Debug, Clone)]
+pub enum AnyRng {
+    Std(Box<StdRng>),
+    Lcg(Lcg),
+    Xorshift64Star(Xorshift64Star),
+    Pcg32(Pcg32),
+    SplitMix64(SplitMix64),
+    Xoshiro256StarStar(Xoshiro256StarStar),
+}
+
+impl AnyRng {
+    fn inner(&mut self) -> &mut dyn RngCore {
+        match self {
+            AnyRng::Std(rng) => rng.as_mut(),
+            AnyRng::Lcg(rng) => rng,
+            AnyRng::Xorshift64Star(rng) => rng,
+            AnyRng::Pcg32(rng) => rng,
+            AnyRng::SplitMix64(rng) => rng,
+            AnyRng::Xoshiro256StarStar(rng) => rng,
+        }
+    }
+}
+
+impl RngCore for AnyRng {
+    fn next_u32(&mut self) -> u32 {
+        self.inner().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.inner().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner().fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.inner().try_fill_bytes(dest)
+    }
+}
+
+// For secrets like passwords. A 64-bit seed allows at most 2^64 different sequences, so
+// without `--seed` or `RUST_PRACTICE_SEED` the generator is filled with a full 256-bit
+// seed from the operating system instead of going through `choose_seed`.
+pub fn strong_rng(explicit: Option<u64>) -> Result<StdRng, String> {
+    match given_seed_from(explicit, env::var(SEED_VAR).ok())? {
+        Some(seed) => Ok(seeded_rng(seed)),
+        None => Ok(StdRng::from_entropy()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_parse_seed() {
+        assert_eq!(parse_seed("42"), Ok(42));
+        assert_eq!(parse_seed(" 18446744073709551615 "), Ok(u64::MAX));
+        assert!(parse_seed("-1").is_err());
+        assert!(parse_seed("forty-two").is_err());
+    }
+
+    #[test]
+    fn test_seed_precedence() {
+        assert_eq!(choose_seed_from(Some(1), Some(String::from("2"))), Ok(1));
+        assert_eq!(choose_seed_from(None, Some(String::from("2"))), Ok(2));
+        assert!(choose_seed_from(None, Some(String::from("abc"))).unwrap_err().starts_with("RUST_PRACTICE_SEED: "));
+        // Without a seed, each run gets its own (two random u64s are as good as never equal).
+        assert_ne!(choose_seed_from(None, Some(String::new())), choose_seed_from(None, None));
+        assert_eq!(given_seed_from(None, Some(String::from(" "))), Ok(None));
+        assert_eq!(given_seed_from(None, Some(String::from("5"))), Ok(Some(5)));
+    }
+
+    #[test]
+    fn test_rng_names() {
+        for kind in RngKind::ALL {
+            assert_eq!(parse_rng(kind.name()), Ok(kind));
+        }
+        assert_eq!(parse_rng(" PCG32 "), Ok(RngKind::Pcg32));
+        assert_eq!(parse_rng("xoshiro256**"), Ok(RngKind::Xoshiro256StarStar));
+        assert_eq!(
+            parse_rng("mt19937").unwrap_err(),
+            "unknown generator 'mt19937' (choose from std, lcg, xorshift64star, pcg32, splitmix64, xoshiro256starstar)"
+        );
+        assert_eq!(serde_json::to_string(&RngKind::SplitMix64).unwrap(), r#""splitmix64""#);
+    }
+
+    #[test]
+    fn test_any_rng_passes_calls_on() {
+        // `--rng pcg32 --seed 42` gives the outputs of the PCG reference demo.
+        assert_eq!(RngKind::Pcg32.seeded(42).next_u32(), 0xa15c02b7);
+        assert_eq!(RngKind::Std.seeded(9).next_u64(), seeded_rng(9).next_u64());
+        for kind in RngKind::ALL {
+            let draw = |seed: u64| -> Vec<u32> { kind.seeded(seed).sample_iter(rand::distributions::Standard).take(5).collect() };
+            assert_eq!(draw(7), draw(7), "{}", kind);
+            assert_ne!(draw(7), draw(8), "{}", kind);
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let draw = |seed: u64| -> Vec<u32> { seeded_rng(seed).sample_iter(rand::distributions::Standard).take(5).collect() };
+        assert_eq!(draw(7), draw(7));
+        assert_ne!(draw(7), draw(8));
+    }
+}
@@ -0,0 +1,385 @@
+// Module: Random Number Generators From Scratch
+// `rand`'s `StdRng` is a black box; these five generators are small enough to read in full.
+// Each keeps a little state, changes it with a few arithmetic steps per number, and mixes
+// the state into the output. They implement `rand`'s `RngCore` and `SeedableRng`, so
+// `gen_range`, `shuffle` and everything else in `rand` works on top of them, and the games
+// can use them with `--rng <name>` (see rng.rs). None of them is fit for secrets.
+// - Lcg:          MINSTD, the "minimal standard" of Park and Miller: state = state * 48271 mod (2^31 - 1).
+// - Xorshift64Star: three shifts and XORs scramble 64 bits, then a multiplication mixes them (Marsaglia, Vigna).
+// - Pcg32:        a 64-bit LCG whose state is shifted and rotated into a 32-bit output (O'Neill).
+// - SplitMix64:   adds a constant to a counter and hashes the result (Steele, Lea and Flood).
+// - Xoshiro256StarStar: 256 bits of XOR/shift/rotate state with a multiply-rotate-multiply output (Blackman and Vigna).
+// The tests compare each one with the outputs published for its reference implementation.
+
+use rand::{Error, RngCore, SeedableRng};
+
+// `fill_bytes` for all of them: the little-endian bytes of 64-bit numbers, the same way
+// `rand`'s own generators do.
+fn fill_bytes_via_u64<R: RngCore + ?Sized>(rng: &mut R, dest: &mut [u8]) {
+    for chunk in dest.chunks_mut(8) {
+        let bytes = rng.next_u64().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+// MINSTD: a linear congruential generator (LCG), the oldest design still in use.
+// The state is 1 to 2^31 - 2 and never 0 (0 would stay 0 forever).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lcg {
+    state: u32,
+}
+
+impl Lcg {
+    const MULTIPLIER: u64 = 48271;
+    const MODULUS: u64 = (1 << 31) - 1;
+
+    // The next raw value, from 1 to 2^31 - 2.
+    pub fn next_raw(&mut self) -> u32 {
+        self.state = (u64::from(self.state) * Lcg::MULTIPLIER % Lcg::MODULUS) as u32;
+        self.state
+    }
+}
+
+impl RngCore for Lcg {
+    // The low bits of an LCG repeat in short cycles, so each half comes from the top 16
+    // of the 31 bits.
+    fn next_u32(&mut self) -> u32 {
+        let high = self.next_raw() >> 15;
+        let low = self.next_raw() >> 15;
+        (high << 16) | low
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        (u64::from(self.next_u32()) << 32) | u64::from(self.next_u32())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_u64(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for Lcg {
+    type Seed = [u8; 4];
+
+    fn from_seed(seed: [u8; 4]) -> Lcg {
+        Lcg::seed_from_u64(u64::from(u32::from_le_bytes(seed)))
+    }
+
+    // Like C++'s `std::minstd_rand`: the seed modulo 2^31 - 1, and 1 instead of 0.
+    fn seed_from_u64(seed: u64) -> Lcg {
+        let state = seed % Lcg::MODULUS;
+        Lcg { state: if state == 0 { 1 } else { state as u32 } }
+    }
+}
+
+// xorshift64*: 64 bits of state; every state except 0 comes round once in 2^64 - 1 steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xorshift64Star {
+    state: u64,
+}
+
+impl Xorshift64Star {
+    // Any state but 0 works; 0 is replaced by this one.
+    const NONZERO: u64 = 0x9E37_79B9_7F4A_7C15;
+}
+
+impl RngCore for Xorshift64Star {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // The high bits of the product are the best mixed.
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_u64(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for Xorshift64Star {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: [u8; 8]) -> Xorshift64Star {
+        let state = u64::from_le_bytes(seed);
+        Xorshift64Star { state: if state == 0 { Xorshift64Star::NONZERO } else { state } }
+    }
+
+    // Similar seeds (1, 2, 3...) would start with similar states, so SplitMix64 spreads
+    // them out first, as Vigna recommends.
+    fn seed_from_u64(seed: u64) -> Xorshift64Star {
+        Xorshift64Star::from_seed(SplitMix64::seed_from_u64(seed).next_u64().to_le_bytes())
+    }
+}
+
+// PCG32 ("permuted congruential generator"): the state moves like a 64-bit LCG, and the
+// output is a 32-bit XOR-shift of it rotated by its own top 5 bits. `increment` picks one
+// of 2^63 independent streams.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+    // The stream the reference demo (pcg32-demo.c) uses, so `--seed 42` gives its outputs.
+    const DEFAULT_STREAM: u64 = 54;
+
+    // `pcg32_srandom(state, stream)` from the reference C code.
+    pub fn new(state: u64, stream: u64) -> Pcg32 {
+        let mut pcg = Pcg32 { state: 0, increment: (stream << 1) | 1 };
+        pcg.next_u32();
+        pcg.state = pcg.state.wrapping_add(state);
+        pcg.next_u32();
+        pcg
+    }
+}
+
+impl RngCore for Pcg32 {
+    fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(Pcg32::MULTIPLIER).wrapping_add(self.increment);
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let low = u64::from(self.next_u32());
+        (u64::from(self.next_u32()) << 32) | low
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_u64(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for Pcg32 {
+    // The state, then the stream, little-endian.
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: [u8; 16]) -> Pcg32 {
+        let (state, stream) = seed.split_at(8);
+        Pcg32::new(u64::from_le_bytes(state.try_into().unwrap()), u64::from_le_bytes(stream.try_into().unwrap()))
+    }
+
+    fn seed_from_u64(seed: u64) -> Pcg32 {
+        Pcg32::new(seed, Pcg32::DEFAULT_STREAM)
+    }
+}
+
+// SplitMix64: a counter that goes up by the golden ratio times 2^64, hashed by two
+// multiply-and-shift rounds. Any 64-bit state works, so it also seeds the others.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl RngCore for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_u64(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for SplitMix64 {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: [u8; 8]) -> SplitMix64 {
+        SplitMix64 { state: u64::from_le_bytes(seed) }
+    }
+
+    fn seed_from_u64(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+}
+
+// xoshiro256**: four 64-bit words of state, a period of 2^256 - 1 and, for its size,
+// about the best statistical quality; the state must not be all zeros.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xoshiro256StarStar {
+    state: [u64; 4],
+}
+
+impl RngCore for Xoshiro256StarStar {
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_u64(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for Xoshiro256StarStar {
+    // The four words of the state, little-endian.
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: [u8; 32]) -> Xoshiro256StarStar {
+        if seed.iter().all(|&byte| byte == 0) {
+            return Xoshiro256StarStar::seed_from_u64(0);
+        }
+        let mut state = [0; 4];
+        for (word, bytes) in state.iter_mut().zip(seed.chunks_exact(8)) {
+            *word = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        Xoshiro256StarStar { state }
+    }
+
+    // The authors' advice: fill the state from SplitMix64 (which never gives four zeros).
+    fn seed_from_u64(seed: u64) -> Xoshiro256StarStar {
+        let mut splitmix = SplitMix64::seed_from_u64(seed);
+        Xoshiro256StarStar { state: [(); 4].map(|_| splitmix.next_u64()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    #[test]
+    fn test_lcg_matches_minstd() {
+        // The C++ standard ([rand.predef]): the 10000th value of a `minstd_rand` seeded with 1.
+        let mut lcg = Lcg::seed_from_u64(1);
+        let value = (0..10_000).map(|_| lcg.next_raw()).last();
+        assert_eq!(value, Some(399_268_537));
+        assert_eq!(Lcg::seed_from_u64(0), Lcg::seed_from_u64(1));
+    }
+
+    #[test]
+    fn test_xorshift64star_follows_its_definition() {
+        // No list of outputs is published for xorshift64*, so this is the first step by
+        // hand from state 1: 1 ^ (1 >> 12) = 1, 1 ^ (1 << 25) = 33554433, 33554433 >> 27 = 0,
+        // and 33554433 * 0x2545F4914F6CDD1D mod 2^64.
+        let mut rng = Xorshift64Star::from_seed(1_u64.to_le_bytes());
+        assert_eq!(rng.next_u64(), 0x47E4_CE4B_896C_DD1D);
+        assert_eq!(rng.state, 33_554_433);
+        assert_eq!(rng.next_u64(), 0xABCF_A6A8_E079_651D);
+        assert_ne!(Xorshift64Star::from_seed([0; 8]).next_u64(), 0);
+    }
+
+    #[test]
+    fn test_pcg32_matches_the_reference_demo() {
+        // pcg32-demo.c from pcg-c-basic: pcg32_srandom(42, 54), "Round 1".
+        let mut rng = Pcg32::new(42, 54);
+        let outputs: Vec<u32> = (0..6).map(|_| rng.next_u32()).collect();
+        assert_eq!(outputs, [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e]);
+        assert_eq!(Pcg32::seed_from_u64(42), Pcg32::new(42, 54));
+    }
+
+    #[test]
+    fn test_splitmix64_matches_the_reference() {
+        // splitmix64.c by Sebastiano Vigna, starting from state 0.
+        let mut rng = SplitMix64::seed_from_u64(0);
+        let outputs: Vec<u64> = (0..4).map(|_| rng.next_u64()).collect();
+        assert_eq!(outputs, [0xe220a8397b1dcdaf, 0x6e789e6aa1b965f4, 0x06c45d188009454f, 0xf88bb8a8724c81ec]);
+    }
+
+    #[test]
+    fn test_xoshiro256starstar_matches_the_reference() {
+        // xoshiro256starstar.c by Blackman and Vigna with the state [1, 2, 3, 4], the test
+        // vector `rand_xoshiro` uses.
+        let mut seed = [0; 32];
+        for (index, bytes) in seed.chunks_exact_mut(8).enumerate() {
+            bytes.copy_from_slice(&(index as u64 + 1).to_le_bytes());
+        }
+        let mut rng = Xoshiro256StarStar::from_seed(seed);
+        let outputs: Vec<u64> = (0..10).map(|_| rng.next_u64()).collect();
+        assert_eq!(
+            outputs,
+            [
+                11520,
+                0,
+                1509978240,
+                1215971899390074240,
+                1216172134540287360,
+                607988272756665600,
+                16172922978634559625,
+                8476171486693032832,
+                10595114339597558777,
+                2904607092377533576,
+            ]
+        );
+    }
+
+    // What `rand` builds on top works with every generator: bytes, ranges and shuffles.
+    fn check_rng_core(mut rng: impl RngCore) {
+        let mut bytes = [0_u8; 13];
+        rng.fill_bytes(&mut bytes);
+        assert!(bytes.iter().any(|&byte| byte != 0));
+        let rolls: Vec<u32> = (0..600).map(|_| rng.gen_range(1..=6)).collect();
+        for side in 1..=6 {
+            let count = rolls.iter().filter(|&&roll| roll == side).count();
+            assert!((60..=140).contains(&count), "side {} came up {} times", side, count);
+        }
+        let mut cards: Vec<u32> = (0..20).collect();
+        cards.shuffle(&mut rng);
+        assert_ne!(cards, (0..20).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_generators_work_with_rand() {
+        check_rng_core(Lcg::seed_from_u64(7));
+        check_rng_core(Xorshift64Star::seed_from_u64(7));
+        check_rng_core(Pcg32::seed_from_u64(7));
+        check_rng_core(SplitMix64::seed_from_u64(7));
+        check_rng_core(Xoshiro256StarStar::seed_from_u64(7));
+    }
+}
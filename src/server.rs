@@ -0,0 +1,485 @@
+// Module: Lesson Server
+// `RustPractice serve` hosts the lesson pages (the same HTML as `export --format html`)
+// on localhost, and adds a playground to every page: an editable code box whose code is
+// compiled and run on this machine with the local `rustc`, under time and memory limits.
+//
+// The server is a small HTTP/1.1 implementation on top of `std::net`, enough for a browser
+// on the same machine: one thread per connection, and every response closes the connection.
+//
+//   GET  /                 the index of all lessons
+//   GET  /<lesson id>.html a lesson page with a playground
+//   POST /run              compile and run the request body, answer with JSON
+//
+// Running code sent over HTTP needs care even on localhost: any web page open in the browser
+// could try to send code to /run. So /run only accepts requests carrying the
+// `X-Rust-Practice: run` header (browsers do not let other sites add custom headers without
+// asking the server first, and this server never agrees), and every request must be
+// addressed to localhost, which stops pages that point their own domain name at 127.0.0.1.
+
+use crate::export::{self, Block};
+use crate::lesson::Lesson;
+use crate::registry;
+use crate::toolchain::{self, RunOutput};
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// Requests bigger than this are refused; nobody types 64 KiB into a code box.
+const MAX_BODY: usize = 64 * 1024;
+// Output beyond this is cut off, so `loop { println!() }` does not produce a huge response.
+const MAX_OUTPUT: usize = 64 * 1024;
+
+const DEFAULT_PROGRAM: &str = "fn main() {\n    println!(\"Hello, Rust!\");\n}\n";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits {
+    pub compile_time: Duration,
+    pub run_time: Duration,
+    pub memory_bytes: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            compile_time: Duration::from_secs(30),
+            run_time: Duration::from_secs(5),
+            memory_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    // Header names are stored in lowercase.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(header, _)| header.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    fn html(body: String) -> Response {
+        Response { status: 200, content_type: "text/html; charset=utf-8", body: body.into_bytes() }
+    }
+
+    fn error(status: u16, message: &str) -> Response {
+        Response { status, content_type: "text/plain; charset=utf-8", body: format!("{}\n", message).into_bytes() }
+    }
+}
+
+// What `POST /run` answers, as JSON.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct PlaygroundResult {
+    // False if `rustc` rejected the code; `compiler_output` then holds the errors.
+    pub compiled: bool,
+    pub compiler_output: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub success: bool,
+    pub timed_out: bool,
+}
+
+// Reads one request: the request line, the headers, and a body of `Content-Length` bytes.
+pub fn read_request(reader: &mut dyn BufRead) -> io::Result<Request> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(invalid("malformed request line"));
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+    let content_length = match headers.iter().find(|(name, _)| name == "content-length") {
+        Some((_, value)) => value.parse().map_err(|_| invalid("bad Content-Length"))?,
+        None => 0,
+    };
+    if content_length > MAX_BODY {
+        return Err(invalid("request body too large"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request { method: method.to_string(), path: path.to_string(), headers, body })
+}
+
+pub fn write_response(out: &mut dyn Write, response: &Response) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    write!(
+        out,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason,
+        response.content_type,
+        response.body.len()
+    )?;
+    out.write_all(&response.body)?;
+    out.flush()
+}
+
+// Decides what to answer. `run` compiles and runs playground code; the tests pass a fake one.
+pub fn handle(request: &Request, run: &dyn Fn(&str) -> io::Result<PlaygroundResult>) -> Response {
+    if !is_local_host(request.header("host")) {
+        return Response::error(403, "this server only answers requests for localhost");
+    }
+    let path = request.path.split('?').next().unwrap_or("");
+    match (request.method.as_str(), path) {
+        ("GET", "/") | ("GET", "/index.html") => Response::html(export::html_index(&registry::study_order())),
+        ("GET", page) => {
+            let lesson = page.strip_prefix('/').and_then(|name| name.strip_suffix(".html")).and_then(registry::find);
+            match lesson {
+                Some(lesson) => match lesson_page(lesson) {
+                    Ok(html) => Response::html(html),
+                    Err(error) => Response::error(500, &format!("cannot build the page: {}", error)),
+                },
+                None => Response::error(404, "no such page"),
+            }
+        }
+        ("POST", "/run") => {
+            if request.header(RUN_HEADER) != Some("run") {
+                return Response::error(403, "requests to /run must come from the playground");
+            }
+            let Ok(code) = std::str::from_utf8(&request.body) else {
+                return Response::error(400, "the code must be UTF-8 text");
+            };
+            match run(code) {
+                Ok(result) => Response {
+                    status: 200,
+                    content_type: "application/json",
+                    body: serde_json::to_vec(&result).unwrap_or_default(),
+                },
+                Err(error) => Response::error(500, &format!("cannot run the code: {} (is `rustc` installed?)", error)),
+            }
+        }
+        _ => Response::error(405, "only GET and POST /run are supported"),
+    }
+}
+
+const RUN_HEADER: &str = "X-Rust-Practice";
+
+// Whether the Host header names this machine (a missing header is accepted, as in HTTP/1.0).
+fn is_local_host(host: Option<&str>) -> bool {
+    let Some(host) = host else { return true };
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    };
+    matches!(name.to_lowercase().as_str(), "localhost" | "127.0.0.1" | "[::1]")
+}
+
+// The exported HTML page with a playground added at the end.
+fn lesson_page(lesson: &dyn Lesson) -> io::Result<String> {
+    let page = export::page_for(lesson)?;
+    // Start from the lesson's original `pracN.rs` program when there is one: unlike the
+    // lesson's own functions, it runs on its own.
+    let starter = page
+        .blocks
+        .iter()
+        .find_map(|block| match block {
+            Block::Code(code) if code.starts_with("fn main()") => Some(format!("{}\n", code)),
+            _ => None,
+        })
+        .unwrap_or_else(|| DEFAULT_PROGRAM.to_string());
+    let playground = PLAYGROUND_HTML.replace("{code}", &export::escape_html(&starter));
+    Ok(export::render_html(&page).replace("</body>", &format!("{}</body>", playground)))
+}
+
+const PLAYGROUND_HTML: &str = r#"<h2>Playground</h2>
+<p>Edit the program and run it. It is compiled and run on this machine.</p>
+<textarea id="code" rows="16" style="width: 100%; font-family: monospace;" spellcheck="false">{code}</textarea>
+<p><button id="run">Run</button> <span id="status"></span></p>
+<pre class="output"><code id="result"></code></pre>
+<script>
+document.getElementById("run").onclick = async () => {
+  const status = document.getElementById("status");
+  const result = document.getElementById("result");
+  status.textContent = "Compiling...";
+  result.textContent = "";
+  try {
+    const response = await fetch("/run", {
+      method: "POST",
+      headers: { "X-Rust-Practice": "run" },
+      body: document.getElementById("code").value,
+    });
+    if (!response.ok) {
+      status.textContent = "Error";
+      result.textContent = await response.text();
+      return;
+    }
+    const run = await response.json();
+    if (!run.compiled) {
+      status.textContent = "Compilation failed";
+      result.textContent = run.compiler_output;
+    } else {
+      status.textContent = run.timed_out ? "Stopped: time limit reached" : (run.success ? "Finished" : "Exited with an error");
+      result.textContent = run.stdout + run.stderr;
+    }
+  } catch (error) {
+    status.textContent = "Error";
+    result.textContent = String(error);
+  }
+};
+</script>
+"#;
+
+// Compiles and runs `code` in `work_dir` (which is removed afterwards).
+pub fn run_code(code: &str, limits: &Limits, work_dir: &std::path::Path) -> io::Result<PlaygroundResult> {
+    fs::create_dir_all(work_dir)?;
+    let source = work_dir.join("main.rs");
+    let binary = work_dir.join("main");
+    fs::write(&source, code)?;
+
+    let result = toolchain::compile_program(&source, &binary, limits.compile_time).and_then(|compiled| {
+        if !compiled.success || compiled.timed_out {
+            let mut compiler_output = compiled.stderr.replace(&source.display().to_string(), "main.rs");
+            if compiled.timed_out {
+                compiler_output.push_str("\n(compilation stopped: time limit reached)");
+            }
+            return Ok(PlaygroundResult { compiled: false, compiler_output, ..PlaygroundResult::empty() });
+        }
+        let ran = toolchain::run_limited(&binary, limits.run_time, limits.memory_bytes)?;
+        Ok(PlaygroundResult::from_run(compiled.stderr, ran))
+    });
+    let _ = fs::remove_dir_all(work_dir);
+    result
+}
+
+impl PlaygroundResult {
+    fn empty() -> PlaygroundResult {
+        PlaygroundResult {
+            compiled: false,
+            compiler_output: String::new(),
+            stdout: String::new(),
+            stderr: String::new(),
+            success: false,
+            timed_out: false,
+        }
+    }
+
+    fn from_run(compiler_output: String, ran: RunOutput) -> PlaygroundResult {
+        PlaygroundResult {
+            compiled: true,
+            compiler_output,
+            stdout: truncate(ran.stdout),
+            stderr: truncate(ran.stderr),
+            success: ran.success,
+            timed_out: ran.timed_out,
+        }
+    }
+}
+
+fn truncate(mut text: String) -> String {
+    if text.len() > MAX_OUTPUT {
+        let mut end = MAX_OUTPUT;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        text.push_str("\n(output cut off)");
+    }
+    text
+}
+
+// Answers requests on `listener` until the program is stopped.
+pub fn serve(listener: TcpListener, limits: Limits) -> io::Result<()> {
+    let runs = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                eprintln!("connection failed: {}", error);
+                continue;
+            }
+        };
+        let runs = Arc::clone(&runs);
+        thread::spawn(move || {
+            if let Err(error) = handle_connection(stream, &limits, &runs) {
+                eprintln!("request failed: {}", error);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, limits: &Limits, runs: &AtomicUsize) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let response = match read_request(&mut reader) {
+        Ok(request) => handle(&request, &|code| {
+            // Every run gets its own directory, so two browser tabs do not overwrite each other.
+            let number = runs.fetch_add(1, Ordering::SeqCst);
+            let work_dir = env::temp_dir().join(format!("rust_practice_playground_{}_{}", std::process::id(), number));
+            run_code(code, limits, &work_dir)
+        }),
+        Err(error) => Response::error(400, &error.to_string()),
+    };
+    write_response(&mut &stream, &response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(path: &str) -> Request {
+        Request {
+            method: String::from("GET"),
+            path: path.to_string(),
+            headers: vec![(String::from("host"), String::from("localhost:8000"))],
+            body: Vec::new(),
+        }
+    }
+
+    fn no_runs(_code: &str) -> io::Result<PlaygroundResult> {
+        panic!("nothing should be run")
+    }
+
+    fn text(response: &Response) -> String {
+        String::from_utf8(response.body.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_read_request() {
+        let raw = "POST /run HTTP/1.1\r\nHost: localhost\r\ncontent-length: 5\r\n\r\nhello and more";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/run");
+        assert_eq!(request.header("Host"), Some("localhost"));
+        assert_eq!(request.body, b"hello");
+
+        assert!(read_request(&mut "\r\n".as_bytes()).is_err());
+        let too_big = format!("POST /run HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY + 1);
+        assert!(read_request(&mut too_big.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_write_response() {
+        let mut out: Vec<u8> = Vec::new();
+        write_response(&mut out, &Response::error(404, "no such page")).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: 13\r\nConnection: close\r\n\r\nno such page\n"
+        );
+    }
+
+    #[test]
+    fn test_pages() {
+        let index = handle(&get("/"), &no_runs);
+        assert_eq!(index.status, 200);
+        assert!(text(&index).contains("<a href=\"loops.html\">"));
+
+        let page = text(&handle(&get("/references_borrowing.html"), &no_runs));
+        assert!(page.contains("<h1>References and Borrowing</h1>"));
+        assert!(page.contains("<textarea id=\"code\""));
+        // The original prac3.rs program is the starting point of the playground.
+        assert!(page.contains("fn main(){\n    let mut var1:String = String::from(&quot;Hello&quot;);"));
+        assert!(page.ends_with("</script>\n</body>\n</html>\n"));
+
+        assert_eq!(handle(&get("/nothing.html"), &no_runs).status, 404);
+        let delete = Request { method: String::from("DELETE"), ..get("/") };
+        assert_eq!(handle(&delete, &no_runs).status, 405);
+    }
+
+    #[test]
+    fn test_run_request_answers_json() {
+        let mut request = Request { method: String::from("POST"), body: b"fn main() {}".to_vec(), ..get("/run") };
+        request.headers.push((String::from("x-rust-practice"), String::from("run")));
+        let response = handle(&request, &|code| {
+            assert_eq!(code, "fn main() {}");
+            Ok(PlaygroundResult { compiled: true, stdout: String::from("hi\n"), success: true, ..PlaygroundResult::empty() })
+        });
+        assert_eq!(response.content_type, "application/json");
+        let json: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(json["stdout"], "hi\n");
+        assert_eq!(json["compiled"], true);
+    }
+
+    #[test]
+    fn test_requests_from_other_sites_are_refused() {
+        // A plain form post from another page cannot add the X-Rust-Practice header.
+        let post = Request { method: String::from("POST"), body: b"fn main() {}".to_vec(), ..get("/run") };
+        assert_eq!(handle(&post, &no_runs).status, 403);
+
+        let mut rebound = get("/");
+        rebound.headers = vec![(String::from("host"), String::from("evil.example:8000"))];
+        assert_eq!(handle(&rebound, &no_runs).status, 403);
+
+        assert!(is_local_host(Some("127.0.0.1:8000")));
+        assert!(is_local_host(Some("LOCALHOST")));
+        assert!(is_local_host(Some("[::1]:8000")));
+        assert!(!is_local_host(Some("localhost.evil.example")));
+    }
+
+    fn run(code: &str, test: &str, limits: &Limits) -> PlaygroundResult {
+        let work_dir = env::temp_dir().join(format!("rust_practice_server_test_{}_{}", test, std::process::id()));
+        run_code(code, limits, &work_dir).unwrap()
+    }
+
+    #[test]
+    fn test_run_code_with_the_local_toolchain() {
+        let limits = Limits::default();
+        let hello = run("fn main() { println!(\"hello\"); eprintln!(\"oops\"); }", "hello", &limits);
+        assert!(hello.compiled && hello.success);
+        assert_eq!((hello.stdout.as_str(), hello.stderr.as_str()), ("hello\n", "oops\n"));
+
+        let broken = run("fn main() { let x: u8 = \"no\"; }", "broken", &limits);
+        assert!(!broken.compiled);
+        assert!(broken.compiler_output.contains("error[E0308]: mismatched types"));
+        assert!(broken.compiler_output.contains("--> main.rs:1:"));
+    }
+
+    #[test]
+    fn test_run_code_enforces_limits() {
+        let limits = Limits { run_time: Duration::from_millis(500), memory_bytes: 64 * 1024 * 1024, ..Limits::default() };
+        let endless = run("fn main() { loop {} }", "endless", &limits);
+        assert!(endless.compiled && endless.timed_out && !endless.success);
+
+        // 512 MiB is far more than the 64 MiB limit, so the allocation fails.
+        let greedy = run("fn main() { let v = vec![1u8; 512 << 20]; println!(\"{}\", v.len()); }", "greedy", &limits);
+        assert!(greedy.compiled && !greedy.success);
+        assert!(greedy.stderr.contains("memory allocation of 536870912 bytes failed"), "{}", greedy.stderr);
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate(String::from("short")), "short");
+        let long = truncate("é".repeat(MAX_OUTPUT));
+        assert!(long.len() < MAX_OUTPUT + 20);
+        assert!(long.ends_with("(output cut off)"));
+    }
+}
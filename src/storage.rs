@@ -0,0 +1,150 @@
+// Module: Local Storage
+// Where the tutorial keeps files between runs (learner progress, and anything else that
+// needs to survive the program exiting), plus small helpers to read and write them as JSON.
+//
+// Files go into the XDG data directory: `$XDG_DATA_HOME/rust_practice`, which defaults to
+// `~/.local/share/rust_practice`. Set `RUST_PRACTICE_DATA_DIR` to use another directory
+// (the tests do this so they never touch your real data).
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DATA_DIR_VAR: &str = "RUST_PRACTICE_DATA_DIR";
+const APP_DIR: &str = "rust_practice";
+
+pub fn data_dir() -> io::Result<PathBuf> {
+    data_dir_from(|name| env::var_os(name))
+}
+
+// Same as `data_dir`, but with the environment passed in so the rules can be unit-tested.
+fn data_dir_from(var: impl Fn(&str) -> Option<OsString>) -> io::Result<PathBuf> {
+    // Empty values are treated as unset, as the XDG specification asks.
+    let non_empty = |name: &str| var(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+
+    if let Some(dir) = non_empty(DATA_DIR_VAR) {
+        return Ok(dir);
+    }
+    if let Some(dir) = non_empty("XDG_DATA_HOME") {
+        return Ok(dir.join(APP_DIR));
+    }
+    if let Some(home) = non_empty("HOME").or_else(|| non_empty("USERPROFILE")) {
+        return Ok(home.join(".local").join("share").join(APP_DIR));
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("cannot find a data directory; set {} or XDG_DATA_HOME", DATA_DIR_VAR),
+    ))
+}
+
+// Reads a JSON file. A missing file is not an error: it just means nothing was saved yet,
+// so the type's default (usually "empty") value is returned.
+pub fn load_json<T: DeserializeOwned + Default>(path: &Path) -> io::Result<T> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(error) => return Err(error),
+    };
+    serde_json::from_str(&text).map_err(|error| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("{} is not valid: {}", path.display(), error))
+    })
+}
+
+// Writes `value` as pretty-printed JSON, creating the directory if needed.
+// The data goes to a temporary file first and is then renamed over the old file,
+// so an interrupted write never leaves a half-written file behind. The temporary name
+// includes the process id, so two programs saving at once do not trip over each other.
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let text = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    let temporary = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    fs::write(&temporary, text + "\n")?;
+    fs::rename(&temporary, path)
+}
+
+// Seconds since 1970-01-01 00:00:00 UTC.
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0)
+}
+
+// Formats a Unix timestamp as "YYYY-MM-DD HH:MM UTC" without pulling in a date library.
+pub fn format_timestamp(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let minutes_today = (seconds % 86_400) / 60;
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, minutes_today / 60, minutes_today % 60)
+}
+
+// Converts days since 1970-01-01 into a (year, month, day) date in the Gregorian calendar.
+// This is Howard Hinnant's well-known `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn dir_with(vars: &[(&str, &str)]) -> io::Result<PathBuf> {
+        let vars: HashMap<String, OsString> =
+            vars.iter().map(|(name, value)| (name.to_string(), OsString::from(value))).collect();
+        data_dir_from(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_data_dir_precedence() {
+        assert_eq!(
+            dir_with(&[(DATA_DIR_VAR, "/tmp/custom"), ("XDG_DATA_HOME", "/xdg"), ("HOME", "/home/a")]).unwrap(),
+            PathBuf::from("/tmp/custom")
+        );
+        assert_eq!(
+            dir_with(&[("XDG_DATA_HOME", "/xdg"), ("HOME", "/home/a")]).unwrap(),
+            PathBuf::from("/xdg/rust_practice")
+        );
+        assert_eq!(
+            dir_with(&[("XDG_DATA_HOME", ""), ("HOME", "/home/a")]).unwrap(),
+            PathBuf::from("/home/a/.local/share/rust_practice")
+        );
+        assert!(dir_with(&[]).is_err());
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00 UTC");
+        assert_eq!(format_timestamp(1_792_335_645), "2026-10-18 15:00 UTC");
+    }
+
+    #[test]
+    fn test_json_round_trip_and_missing_file() {
+        let dir = env::temp_dir().join(format!("rust_practice_storage_test_{}", std::process::id()));
+        let path = dir.join("numbers.json");
+        let missing: Vec<u32> = load_json(&path).unwrap();
+        assert!(missing.is_empty());
+
+        save_json(&path, &vec![1_u32, 2, 3]).unwrap();
+        let loaded: Vec<u32> = load_json(&path).unwrap();
+        assert_eq!(loaded, [1, 2, 3]);
+
+        fs::write(&path, "not json").unwrap();
+        assert_eq!(load_json::<Vec<u32>>(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
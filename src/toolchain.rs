@@ -0,0 +1,205 @@
+// Module: Rust Toolchain
+// Runs the locally installed `rustc` on code written by the learner, and runs the
+// resulting programs with a time limit so an endless loop cannot hang the tutorial, and a
+// cap on the output kept so `loop { println!() }` cannot fill the memory either.
+// Set `RUSTC` to use a different compiler (the same variable Cargo understands).
+
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How much of each of stdout and stderr is kept; the rest is read and thrown away.
+pub const MAX_CAPTURE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RunOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+    // The program was stopped because it ran longer than the limit.
+    pub timed_out: bool,
+}
+
+pub fn rustc() -> OsString {
+    env::var_os("RUSTC").filter(|value| !value.is_empty()).unwrap_or_else(|| OsString::from("rustc"))
+}
+
+// Compiles `source` into a test binary (`rustc --test`) at `binary`.
+// Integer overflow checks are turned on, as in a debug build.
+pub fn compile_tests(source: &Path, binary: &Path, limit: Duration) -> io::Result<RunOutput> {
+    let mut command = Command::new(rustc());
+    command
+        .args(["--edition", "2024", "--test", "--color", "never", "-C", "overflow-checks=on", "-o"])
+        .arg(binary)
+        .arg(source);
+    run_with_timeout(&mut command, limit)
+}
+
+// Compiles a program with a `main` function into `binary`.
+pub fn compile_program(source: &Path, binary: &Path, limit: Duration) -> io::Result<RunOutput> {
+    let mut command = Command::new(rustc());
+    command
+        .args(["--edition", "2024", "--color", "never", "-C", "overflow-checks=on", "-o"])
+        .arg(binary)
+        .arg(source);
+    run_with_timeout(&mut command, limit)
+}
+
+// Runs a compiled program with a time limit and, on Unix, a memory limit: the program is
+// started from `sh` after `ulimit -v`, so allocating more than `memory_bytes` fails.
+pub fn run_limited(binary: &Path, limit: Duration, memory_bytes: u64) -> io::Result<RunOutput> {
+    if cfg!(unix) {
+        let script = format!("ulimit -v {} && exec \"$0\"", memory_bytes / 1024);
+        run_with_timeout(Command::new("sh").arg("-c").arg(script).arg(binary), limit)
+    } else {
+        run_with_timeout(&mut Command::new(binary), limit)
+    }
+}
+
+// Runs `command`, collecting what it prints (up to `MAX_CAPTURE` bytes of each stream). If it
+// is still running after `limit`, it is killed and the output so far is returned with
+// `timed_out` set. Anything it started in the background is killed when it ends either way.
+pub fn run_with_timeout(command: &mut Command, limit: Duration) -> io::Result<RunOutput> {
+    // On Unix the program gets a process group of its own, so everything it started can be
+    // stopped with it: a process left behind (or `sh` from `run_limited`) could otherwise
+    // keep running and keep the pipes open and the reading threads waiting.
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+    let mut child = command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+    // The pipes are read on their own threads: a program that prints a lot would otherwise
+    // block once the pipe is full, and look as if it never finished.
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let (success, timed_out) = wait_with_limit(&mut child, limit)?;
+    kill_group(&child);
+    Ok(RunOutput {
+        success,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+        timed_out,
+    })
+}
+
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let mut cut_off = false;
+        if let Some(pipe) = pipe {
+            let mut kept = pipe.take(MAX_CAPTURE as u64);
+            let _ = kept.read_to_end(&mut bytes);
+            // Keep reading so the program does not block on a full pipe.
+            cut_off = io::copy(&mut kept.into_inner(), &mut io::sink()).is_ok_and(|rest| rest > 0);
+        }
+        if cut_off {
+            // Drop a character that the cut split in two.
+            if let Err(error) = std::str::from_utf8(&bytes)
+                && error.error_len().is_none()
+            {
+                bytes.truncate(error.valid_up_to());
+            }
+        }
+        let mut text = String::from_utf8_lossy(&bytes).into_owned();
+        if cut_off {
+            text.push_str("\n(output cut off)");
+        }
+        text
+    })
+}
+
+// Returns (exited successfully, timed out).
+fn wait_with_limit(child: &mut Child, limit: Duration) -> io::Result<(bool, bool)> {
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok((status.success(), false));
+        }
+        if started.elapsed() >= limit {
+            // The child may exit on its own between the check and the kill; that is fine.
+            let _ = child.kill();
+            child.wait()?;
+            return Ok((false, true));
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+// Kills every process left in the child's group, after the child itself has been waited for.
+// The group has the child's id, and Unix does not hand out an id again while a group with
+// that id still has members; without members there is nothing to kill and `kill` fails.
+#[cfg(unix)]
+fn kill_group(child: &Child) {
+    unsafe extern "C" {
+        fn kill(pid: i32, signal: i32) -> i32;
+    }
+    const SIGKILL: i32 = 9;
+    // A negative id sends the signal to the whole process group.
+    let group = -(child.id() as i32);
+    unsafe {
+        kill(group, SIGKILL);
+    }
+}
+
+// Elsewhere only the child is stopped, and it has ended already.
+#[cfg(not(unix))]
+fn kill_group(_child: &Child) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_collects_output_and_status() {
+        let output = run_with_timeout(Command::new("sh").args(["-c", "echo out; echo err >&2; exit 3"]), Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(
+            output,
+            RunOutput { success: false, stdout: String::from("out\n"), stderr: String::from("err\n"), timed_out: false }
+        );
+    }
+
+    #[test]
+    fn test_run_stops_programs_that_take_too_long() {
+        let started = Instant::now();
+        let output = run_with_timeout(Command::new("sleep").arg("10"), Duration::from_millis(100)).unwrap();
+        assert!(output.timed_out);
+        assert!(!output.success);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_timeout_stops_what_the_program_started() {
+        // The background `sleep` holds the pipes open after `sh` is killed.
+        let started = Instant::now();
+        let output = run_with_timeout(Command::new("sh").args(["-c", "sleep 10 & sleep 10"]), Duration::from_millis(100)).unwrap();
+        assert!(output.timed_out);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_background_processes_do_not_outlive_the_program() {
+        // `sh` exits at once, but the `sleep` it leaves behind holds the pipes open.
+        let started = Instant::now();
+        let output = run_with_timeout(Command::new("sh").args(["-c", "sleep 4 & exit 0"]), Duration::from_millis(300)).unwrap();
+        assert!(output.success && !output.timed_out);
+        assert!(started.elapsed() < Duration::from_secs(2), "{:?}", started.elapsed());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_endless_output_is_capped() {
+        let output = run_with_timeout(Command::new("yes").arg("é"), Duration::from_millis(500)).unwrap();
+        assert!(output.timed_out);
+        assert!(output.stdout.len() <= MAX_CAPTURE + 20, "{}", output.stdout.len());
+        assert!(output.stdout.ends_with("\n(output cut off)"));
+        // 2^20 bytes end in the middle of an `é`, which is left out rather than garbled.
+        assert!(!output.stdout.contains(char::REPLACEMENT_CHARACTER));
+    }
+}
@@ -1,30 +1,35 @@
 // Module: User Input
 // This module demonstrates how to read basic user input from the console.
 
+use crate::lesson::{Chapter, Lesson};
+
 // Import the `io` (input/output) module from the standard library.
-use std::io;
+// `Write` is the trait behind `writeln!`, which this lesson uses to print.
+// `BufRead` is the trait for readers that can read a line at a time.
+use std::io::{self, BufRead, Write};
 
-pub fn demonstrate_user_input() {
-    println!("\n--- User Input ---");
+// `input` is where the answers come from. When you run the lesson it is standard input
+// (the keyboard, `io::stdin().lock()`); tests and `--input "Alice\n30\n"` pass scripted text.
+pub fn demonstrate_user_input(input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n--- User Input ---")?;
 
     // Note: For a real application, robust error handling and input validation are crucial.
     // This example keeps it simple to demonstrate the core mechanism.
 
-    println!("Please enter your name:");
+    writeln!(out, "Please enter your name:")?;
 
     // Create a mutable String to store the user's input.
     // `String::new()` creates an empty string.
     let mut name_input = String::new();
 
-    // `io::stdin()` returns a handle to the standard input stream of the current process.
-    // `.read_line(&mut name_input)` reads a line of text from standard input
+    // `.read_line(&mut name_input)` reads a line of text from the input
     // and appends it to the `name_input` string.
     // It returns an `io::Result<usize>`, which is an enum that can be `Ok(number_of_bytes_read)`
     // or `Err(error_details)`.
     // `.expect("Failed to read line")` is a shortcut to handle the Result.
     // If it's an `Err`, the program will panic and display the provided message.
     // If it's `Ok`, it will unwrap the value (number of bytes read) and proceed.
-    io::stdin()
+    input
         .read_line(&mut name_input)
         .expect("Failed to read line. Please try again.");
 
@@ -32,13 +37,13 @@ pub fn demonstrate_user_input() {
     // We often want to remove this. `trim()` removes leading and trailing whitespace.
     let name = name_input.trim();
 
-    println!("Hello, {}! Nice to meet you.", name);
+    writeln!(out, "Hello, {}! Nice to meet you.", name)?;
 
     // Reading numbers requires parsing the String input.
-    println!("\nPlease enter your age:");
+    writeln!(out, "\nPlease enter your age:")?;
     let mut age_input = String::new();
 
-    io::stdin()
+    input
         .read_line(&mut age_input)
         .expect("Failed to read age. Please enter a valid number.");
 
@@ -46,23 +51,22 @@ pub fn demonstrate_user_input() {
     // `parse()` returns a `Result` because the input might not be a valid number.
     match age_input.trim().parse::<u32>() {
         Ok(age) => {
-            println!("You are {} years old.", age);
+            writeln!(out, "You are {} years old.", age)?;
             if age >= 18 {
-                println!("You are an adult.");
+                writeln!(out, "You are an adult.")?;
             } else {
-                println!("You are a minor.");
+                writeln!(out, "You are a minor.")?;
             }
         }
         Err(parse_error) => {
             // If parsing fails, print an error message.
-            println!("Invalid age entered. That doesn't look like a number. Error: {}", parse_error);
+            writeln!(out, "Invalid age entered. That doesn't look like a number. Error: {}", parse_error)?;
         }
     }
 
-    // To make this module runnable without actual user input during automated tests or
-    // if the user just wants to see the output without typing, we could add a non-interactive
-    // part or use conditional compilation, but for this tutorial, direct input is shown.
-    // For the `cargo run` in this tutorial, the user will be prompted.
+    // Because the lesson reads from `input` instead of `io::stdin()` directly, it can also run
+    // without anyone typing: the tests below and `run user_input --input "Alice\n30\n"`
+    // provide the answers up front. For a plain `cargo run -- run user_input` you will be prompted.
 
     // Original prac14.rs:
     // use std::io;
@@ -73,4 +77,82 @@ pub fn demonstrate_user_input() {
     //     println!("Hello, !{}", input); // Note: original had an extra '!' and didn't trim.
     // }
     // This refactored module provides a more complete example including number parsing.
+    Ok(())
+}
+
+pub struct UserInputLesson;
+
+impl Lesson for UserInputLesson {
+    fn id(&self) -> &'static str {
+        "user_input"
+    }
+
+    fn title(&self) -> &'static str {
+        "User Input"
+    }
+
+    fn chapter(&self) -> Chapter {
+        Chapter::UserInput
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["control_flow"]
+    }
+
+    fn run(&self, input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+        demonstrate_user_input(input, out)
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("user_input.rs")
+    }
+
+    fn example_input(&self) -> &'static str {
+        "Alice\n30\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs the lesson with scripted answers and returns what it printed.
+    // A byte slice (`&[u8]`) implements `BufRead`, so it can stand in for the keyboard.
+    fn run_with_answers(answers: &str) -> String {
+        let mut output: Vec<u8> = Vec::new();
+        demonstrate_user_input(&mut answers.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_name_is_trimmed() {
+        assert!(run_with_answers("  Alice  \n30\n").contains("Hello, Alice! Nice to meet you."));
+    }
+
+    #[test]
+    fn test_adult_age() {
+        let output = run_with_answers("Alice\n30\n");
+        assert!(output.contains("You are 30 years old.\nYou are an adult."));
+    }
+
+    #[test]
+    fn test_minor_age() {
+        assert!(run_with_answers("Bob\n12\n").contains("You are a minor."));
+    }
+
+    #[test]
+    fn test_invalid_age() {
+        let output = run_with_answers("Carol\nthirty\n");
+        assert!(output.contains("Invalid age entered. That doesn't look like a number. Error: invalid digit found in string"));
+        assert!(!output.contains("years old"));
+    }
+
+    #[test]
+    fn test_negative_age_is_invalid_for_u32() {
+        assert!(run_with_answers("Dave\n-5\n").contains("Invalid age entered."));
+    }
 }
@@ -0,0 +1,478 @@
+// End-to-end tests for the three binaries. Cargo builds them before running these tests
+// and tells us where they are through the `CARGO_BIN_EXE_<name>` environment variables.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// Progress and other saved files go to a scratch directory, never the real data directory.
+fn data_dir() -> PathBuf {
+    env::temp_dir().join(format!("rust_practice_cli_test_{}", std::process::id()))
+}
+
+fn run(binary: &str, args: &[&str]) -> Output {
+    run_in(&data_dir(), binary, args)
+}
+
+// Tests that check what was saved use a directory of their own, so other tests running
+// at the same time cannot change the files under them.
+fn run_in(dir: &Path, binary: &str, args: &[&str]) -> Output {
+    Command::new(binary)
+        .args(args)
+        .env("RUST_PRACTICE_DATA_DIR", dir)
+        .output()
+        .expect("failed to start the binary")
+}
+
+fn stdout_of(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_tutorial_list_shows_every_chapter() {
+    let output = run(env!("CARGO_BIN_EXE_RustPractice"), &["list"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("1. Getting Started (getting-started)"));
+    assert!(stdout.contains("9. User Input (user-input)"));
+    assert!(stdout.contains("user_input"));
+}
+
+#[test]
+fn test_tutorial_runs_interactive_lesson_with_scripted_input() {
+    let output = run(env!("CARGO_BIN_EXE_RustPractice"), &["run", "user_input", "--input", "Alice\\n30\\n"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("Hello, Alice! Nice to meet you."));
+    assert!(stdout.contains("You are an adult."));
+}
+
+#[test]
+fn test_tutorial_skip_interactive_does_not_wait_for_input() {
+    let output = run(env!("CARGO_BIN_EXE_RustPractice"), &["run", "--all", "--skip-interactive"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("--- Loops: loop, while, for ---"));
+    assert!(stdout.contains("(Skipping interactive lesson 'user_input')"));
+    assert!(!stdout.contains("--- User Input ---"));
+}
+
+#[test]
+fn test_tutorial_reports_unknown_topic() {
+    let output = run(env!("CARGO_BIN_EXE_RustPractice"), &["run", "closures"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown topic 'closures'"));
+}
+
+#[test]
+fn test_guess_game_plays_a_scripted_round() {
+    let output = run(env!("CARGO_BIN_EXE_guess_game"), &["--input", "Alice\\nrock\\n"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("Please enter your name:\nHello, Alice! Nice to meet you.\n"));
+    assert!(stdout.contains("You chose: rock"));
+    assert!(stdout.contains("You win!") || stdout.contains("You lose!") || stdout.contains("It's a tie!"));
+}
+
+#[test]
+fn test_guess_game_plays_a_match_with_a_scoreboard() {
+    let output = run(env!("CARGO_BIN_EXE_guess_game"), &["--best-of", "3", "--player", "tester", "--input", "rock\\npaper\\n"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("Best of 3: the first to win 2 rounds wins the match."));
+    assert!(stdout.contains("Score: you "));
+    assert!(stdout.ends_with("Thanks for playing!\n"));
+
+    let output = run(env!("CARGO_BIN_EXE_guess_game"), &["--best-of", "zero"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_guess_game_loads_a_rule_file() {
+    let dir = data_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("coin.json");
+    std::fs::write(&path, r#"{"name": "Coin", "moves": ["heads", "tails"], "beats": {"heads": ["tails"]}}"#).unwrap();
+    let rules = path.to_str().unwrap();
+    let output = run(env!("CARGO_BIN_EXE_guess_game"), &["--rules", rules, "--player", "tester", "--input", "heads\\n"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("Rules: Coin\n  heads beats tails\n  tails beats nothing\n"));
+    assert!(stdout.contains("You chose: heads"));
+
+    std::fs::write(&path, r#"{"name": "Coin", "moves": ["heads", "tails"], "beats": {"heads": ["heads"]}}"#).unwrap();
+    let output = run(env!("CARGO_BIN_EXE_guess_game"), &["--rules", rules]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'heads' beats itself"));
+}
+
+#[test]
+fn test_guess_game_records_stats_and_leaderboard() {
+    let guess_game = env!("CARGO_BIN_EXE_guess_game");
+    let dir = data_dir().join("guess_game_stats");
+    let output = run_in(&dir, guess_game, &["leaderboard"]);
+    assert!(stdout_of(&output).starts_with("No games recorded yet."));
+
+    for player in ["alice", "bob", "alice"] {
+        let output = run_in(&dir, guess_game, &["--seed", "1", "--input", &format!("{}\\nrock\\nrock\\nrock\\n", player)]);
+        assert!(output.status.success());
+    }
+    let output = run_in(&dir, guess_game, &["stats", "alice"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("Statistics for alice\n"));
+    assert!(stdout.contains("  Moves:    rock "));
+    let bob = stdout_of(&run_in(&dir, guess_game, &["stats", "bob"]));
+    // alice played the same seeded game twice, so she has twice as many rounds as bob.
+    let rounds = |report: &str| -> u32 {
+        let line = report.lines().find(|line| line.contains("Rounds:")).unwrap();
+        line.split_whitespace().nth(1).unwrap().parse().unwrap()
+    };
+    assert_eq!(rounds(&stdout), 2 * rounds(&bob));
+
+    let table = stdout_of(&run_in(&dir, guess_game, &["leaderboard"]));
+    assert!(table.starts_with("Rank  Player"));
+    assert!(table.contains("  alice ") && table.contains("  bob "));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_guess_game_log_replays() {
+    let guess_game = env!("CARGO_BIN_EXE_guess_game");
+    let dir = data_dir().join("guess_game_log");
+    std::fs::create_dir_all(&dir).unwrap();
+    let log = dir.join("session.jsonl");
+    let log = log.to_str().unwrap();
+    let moves = "rock\\nhammer\\npaper\\nscissors\\nrock\\npaper\\nscissors\\n";
+    let args = ["--best-of", "3", "--strategy", "random", "--seed", "8", "--player", "tester", "--log", log, "--input", moves];
+    assert!(run_in(&dir, guess_game, &args).status.success());
+    assert!(std::fs::read_to_string(log).unwrap().starts_with(r#"{"type":"session","player":"tester","seed":8,"#));
+
+    let output = run_in(&dir, guess_game, &["replay", log]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("Replay of tester's session (seed 8)\nRock-Paper-Scissors, Best of 3, computer strategy: random.\nMatch 1:\n"));
+    assert!(stdout.contains("  Round 1: you rock, computer "));
+    assert!(stdout.lines().last().unwrap().starts_with("Verified: seed 8 gives the same "));
+
+    // With another seed the same log no longer replays.
+    let text = std::fs::read_to_string(log).unwrap();
+    std::fs::write(log, text.replacen(r#""seed":8"#, r#""seed":9"#, 1)).unwrap();
+    let output = run_in(&dir, guess_game, &["replay", log]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("error: the log does not replay: round "));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_hand_written_generators_can_be_chosen() {
+    let random_number = env!("CARGO_BIN_EXE_random_number");
+    let values = |generator: &str| stdout_of(&run(random_number, &["--type", "u64", "--count", "5", "--seed", "1", "--rng", generator]));
+    let pcg32 = values("pcg32");
+    assert_eq!(pcg32.lines().count(), 5);
+    assert_eq!(values("pcg32"), pcg32);
+    assert_ne!(values("xoshiro256**"), pcg32);
+    assert_ne!(values("std"), pcg32);
+    let output = run(random_number, &["--rng", "mt19937"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown generator 'mt19937'"));
+
+    // guess_game records the generator in its log, and the replay uses it again.
+    let guess_game = env!("CARGO_BIN_EXE_guess_game");
+    let dir = data_dir().join("guess_game_rng");
+    std::fs::create_dir_all(&dir).unwrap();
+    let log = dir.join("session.jsonl");
+    let log = log.to_str().unwrap();
+    let args = ["--first-to", "2", "--rng", "lcg", "--seed", "4", "--player", "tester", "--log", log, "--input", "rock\n"];
+    assert!(run_in(&dir, guess_game, &args).status.success());
+    let output = run_in(&dir, guess_game, &["replay", log]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("Replay of tester's session (seed 4, generator lcg)\n"), "{}", stdout);
+    assert!(stdout.lines().last().unwrap().starts_with("Verified: seed 4 gives the same "));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_guess_game_tournament() {
+    let guess_game = env!("CARGO_BIN_EXE_guess_game");
+    let dir = data_dir().join("guess_game_tournament");
+    std::fs::create_dir_all(&dir).unwrap();
+    let csv = dir.join("results.csv");
+    let output = run(guess_game, &["tournament", "--games", "10", "--seed", "5", "--csv", csv.to_str().unwrap()]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("Tournament: 4 strategies, 10 matches per pair (Best of 5, Rock-Paper-Scissors)\n"));
+    assert!(stdout.contains("\nSeed: 5 "));
+    // The same seed plays the same tournament.
+    let again = run(guess_game, &["tournament", "--games", "10", "--seed", "5"]);
+    assert_eq!(stdout_of(&again), stdout.split("Saved the results").next().unwrap());
+
+    let csv = std::fs::read_to_string(&csv).unwrap();
+    assert!(csv.starts_with("rank,strategy,elo,won,lost,drawn,score_percent,vs_random,vs_frequency,vs_markov,vs_wsls\n1,"));
+    assert_eq!(csv.lines().count(), 5);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_guess_game_host_and_join_on_localhost() {
+    use std::io::{BufRead, BufReader, Read};
+    use std::process::Stdio;
+
+    // Port 0 lets the system pick a free port; the first line printed tells us which.
+    let mut host = Command::new(env!("CARGO_BIN_EXE_guess_game"))
+        .args(["host", "--port", "0", "--player", "alice", "--input", "rock\\nrock\\n"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start the host");
+    let mut host_output = BufReader::new(host.stdout.take().unwrap());
+    let mut first_line = String::new();
+    host_output.read_line(&mut first_line).unwrap();
+    let port = first_line
+        .split("port ")
+        .nth(1)
+        .and_then(|rest| rest.split(' ').next())
+        .expect("the host did not print its port")
+        .to_string();
+
+    let guest = run(
+        env!("CARGO_BIN_EXE_guess_game"),
+        &["join", &format!("127.0.0.1:{}", port), "--input", "bob\\nrock\\npaper\\n"],
+    );
+    let mut rest = String::new();
+    host_output.read_to_string(&mut rest).unwrap();
+    assert!(host.wait().unwrap().success());
+    assert!(guest.status.success());
+
+    let guest = stdout_of(&guest);
+    assert!(guest.contains("Connected to alice.\n"));
+    assert!(guest.contains("alice chose: rock\nIt's a tie!\n"));
+    assert!(guest.ends_with("You won the match 1-0 (2 rounds, 1 tie).\n"));
+    assert!(rest.contains("Connected to bob.\n"));
+    assert!(rest.ends_with("bob won the match 0-1 (2 rounds, 1 tie).\n"));
+}
+
+#[test]
+fn test_guess_game_rejects_invalid_choice() {
+    let output = run(env!("CARGO_BIN_EXE_guess_game"), &["--player", "tester", "--input", "lizard\\n"]);
+    assert!(output.status.success());
+    assert!(stdout_of(&output).contains("Invalid choice. Please choose rock, paper, or scissors."));
+}
+
+#[test]
+fn test_random_number_prints_four_values() {
+    let output = run(env!("CARGO_BIN_EXE_random_number"), &[]);
+    assert!(output.status.success());
+    assert_eq!(stdout_of(&output).lines().count(), 4);
+}
+
+#[test]
+fn test_random_number_generates_values_of_one_type() {
+    let random_number = env!("CARGO_BIN_EXE_random_number");
+    let output = run(random_number, &["--type", "u8", "--range", "1..=6", "--count", "20", "--separator", ",", "--seed", "1"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    let rolls: Vec<u8> = stdout.trim_end().split(',').map(|roll| roll.parse().unwrap()).collect();
+    assert_eq!(rolls.len(), 20);
+    assert!(rolls.iter().all(|roll| (1..=6).contains(roll)));
+
+    let stdout = stdout_of(&run(random_number, &["--type", "bool", "--count", "3", "--format", "json"]));
+    let values: Vec<bool> = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(values.len(), 3);
+
+    let output = run(random_number, &["--type", "u8", "--range", "0..256"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("256 does not fit in u8"));
+}
+
+#[test]
+fn test_random_number_describes_a_distribution() {
+    let random_number = env!("CARGO_BIN_EXE_random_number");
+    let output = run(random_number, &["--dist", "poisson:4", "--summary", "--histogram", "--count", "500", "--seed", "5"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("Distribution: poisson(lambda 4)\nCount:     500\n"));
+    assert!(stdout.contains("(expected 4)"));
+    // Poisson numbers are whole, so each one gets its own histogram row.
+    assert!(stdout.lines().any(|line| line.trim_start().starts_with("4 | #")));
+
+    let stdout = stdout_of(&run(random_number, &["--dist", "bernoulli:0.5", "--count", "6", "--separator", " ", "--seed", "5"]));
+    assert!(stdout.split_whitespace().all(|value| value == "0" || value == "1"));
+
+    let output = run(random_number, &["--dist", "normal:0,-1"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_random_number_rolls_dice() {
+    let random_number = env!("CARGO_BIN_EXE_random_number");
+    let output = run(random_number, &["roll", "4d6kh3", "+", "2", "--seed", "9"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "Rolling 4d6kh3 + 2");
+    // One die is dropped and shown in brackets.
+    assert!(lines[1].starts_with("4d6kh3: ") && lines[1].matches('(').count() == 1);
+    let kept: i64 = lines[1].rsplit(" = ").next().unwrap().parse().unwrap();
+    assert_eq!(lines[2], format!("Total: {}", kept + 2));
+    assert_eq!(stdout_of(&run(random_number, &["roll", "4d6kh3+2", "--seed", "9"])), stdout);
+
+    let output = run(random_number, &["roll", "3d6+*2"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("found '*' at column 5\n  3d6+*2\n      ^"));
+    assert_eq!(run(random_number, &["roll", "d6/0"]).status.code(), Some(1));
+}
+
+#[test]
+fn test_random_number_makes_passwords() {
+    let random_number = env!("CARGO_BIN_EXE_random_number");
+    let output = run(random_number, &["password", "--length", "12", "--classes", "lower,digits", "--count", "4"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert_eq!(stdout.lines().count(), 4);
+    for line in stdout.lines() {
+        let (password, entropy) = line.split_once('\t').unwrap();
+        assert_eq!(password.len(), 12);
+        assert!(password.chars().all(|letter| letter.is_ascii_lowercase() || letter.is_ascii_digit()));
+        assert!(password.chars().any(|letter| letter.is_ascii_digit()));
+        assert_eq!(entropy, "62.0 bits");
+    }
+
+    let stdout = stdout_of(&run(random_number, &["password", "--words", "4", "--separator", " ", "--seed", "3"]));
+    let (phrase, entropy) = stdout.trim_end().split_once('\t').unwrap();
+    assert_eq!(phrase.split(' ').count(), 4);
+    assert_eq!(entropy, "41.4 bits");
+    assert_eq!(stdout_of(&run(random_number, &["password", "--words", "4", "--separator", " ", "--seed", "3"])), stdout);
+
+    assert_eq!(run(random_number, &["password", "--classes", "kanji"]).status.code(), Some(2));
+}
+
+#[test]
+fn test_seed_replays_the_same_run() {
+    let random_number = env!("CARGO_BIN_EXE_random_number");
+    let first = stdout_of(&run(random_number, &["--seed", "42"]));
+    assert_eq!(stdout_of(&run(random_number, &["--seed", "42"])), first);
+    assert_ne!(stdout_of(&run(random_number, &["--seed", "43"])), first);
+
+    // RUST_PRACTICE_SEED does the same as `--seed`, for both binaries.
+    let guess_game = env!("CARGO_BIN_EXE_guess_game");
+    let moves = "rock\\npaper\\nscissors\\nrock\\npaper\\nscissors\\n";
+    let by_option = stdout_of(&run(guess_game, &["--first-to", "3", "--seed", "7", "--player", "tester", "--input", moves]));
+    let by_variable = Command::new(guess_game)
+        .args(["--first-to", "3", "--player", "tester", "--input", moves])
+        .env("RUST_PRACTICE_DATA_DIR", data_dir())
+        .env("RUST_PRACTICE_SEED", "7")
+        .output()
+        .unwrap();
+    assert_eq!(stdout_of(&by_variable), by_option);
+
+    let output = run(random_number, &["--seed", "lots"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_guess_game_number_mode() {
+    let guess_game = env!("CARGO_BIN_EXE_guess_game");
+    let output = run(guess_game, &["number", "--bot", "--seed", "3"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.ends_with("it never needs more than 7 for 1-100.\n"), "{}", stdout);
+
+    // With the same seed the secret is the same, so a player who knows it gets it at once.
+    let secret = stdout.split("The bot found ").nth(1).and_then(|rest| rest.split(' ').next()).unwrap();
+    let answers = format!("twelve\\n{}\\n", secret);
+    let stdout = stdout_of(&run(guess_game, &["number", "--seed", "3", "--hints", "--input", &answers]));
+    assert!(stdout.contains("'twelve' is not a whole number."), "{}", stdout);
+    assert!(stdout.contains(&format!("You found {} in 1 guess.", secret)), "{}", stdout);
+
+    let output = run(guess_game, &["number", "--difficulty", "impossible"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_tutorial_records_and_resets_progress() {
+    let tutorial = env!("CARGO_BIN_EXE_RustPractice");
+    assert!(run(tutorial, &["run", "loops", "--user", "progress_tester"]).status.success());
+
+    let output = run(tutorial, &["progress", "--user", "progress_tester"]);
+    assert!(output.status.success());
+    let stdout = stdout_of(&output);
+    assert!(stdout.starts_with("Progress for progress_tester: 1 of "));
+    assert!(stdout.contains("5. Control Flow  1/2 [#####-----]"));
+    assert!(stdout.contains("    [x] loops"));
+
+    let output = run(tutorial, &["reset", "--user", "progress_tester", "--yes"]);
+    assert!(output.status.success());
+    assert!(stdout_of(&output).contains("Forgot the progress of progress_tester."));
+    let stdout = stdout_of(&run(tutorial, &["progress", "--user", "progress_tester"]));
+    assert!(stdout.starts_with("Progress for progress_tester: 0 of "));
+}
+
+#[test]
+fn test_tutorial_quiz_records_the_score() {
+    let tutorial = env!("CARGO_BIN_EXE_RustPractice");
+    let output = run(tutorial, &["quiz"]);
+    assert!(stdout_of(&output).contains("string_ownership"));
+
+    // The order is random, so answer everything wrong: the score is known to be zero.
+    let wrong = "wrong\\n".repeat(5);
+    let output = run(tutorial, &["quiz", "string_ownership", "--input", &wrong, "--user", "quiz_tester"]);
+    assert!(output.status.success());
+    assert!(stdout_of(&output).contains("Score: 0/5 (0%)"));
+
+    let stdout = stdout_of(&run(tutorial, &["progress", "--user", "quiz_tester"]));
+    assert!(stdout.contains("string_ownership                   best 0/5, last 0/5 (1 attempt,"));
+
+    let output = run(tutorial, &["quiz", "loops"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_tutorial_exercise_check_reports_failures() {
+    let tutorial = env!("CARGO_BIN_EXE_RustPractice");
+    let output = run(tutorial, &["exercise", "list"]);
+    assert!(output.status.success());
+    assert!(stdout_of(&output).contains("calculate_length"));
+
+    // The stubs in exercises/ are unsolved, so checking one fails with the test output.
+    let output = run(tutorial, &["exercise", "check", "calculate_length"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = stdout_of(&output);
+    assert!(stdout.contains("FAIL  calculate_length: some tests failed"));
+    assert!(stdout.contains("0 of 1 exercises passed."));
+}
+
+#[test]
+fn test_tutorial_serve_answers_on_localhost() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+    use std::process::Stdio;
+
+    // Port 0 lets the system pick a free port; the first line printed tells us which.
+    let mut server = Command::new(env!("CARGO_BIN_EXE_RustPractice"))
+        .args(["serve", "--port", "0"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start the server");
+    // Keep reading from the same pipe: closing it would make the server's next `println!` fail.
+    let mut server_output = BufReader::new(server.stdout.take().unwrap());
+    let mut first_line = String::new();
+    server_output.read_line(&mut first_line).unwrap();
+    let address = first_line
+        .split("http://")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .expect("the server did not print its address")
+        .to_string();
+
+    let mut stream = TcpStream::connect(&address).unwrap();
+    stream.write_all(b"GET /loops.html HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    server.kill().unwrap();
+    server.wait().unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("<h1>Loops: loop, while, for</h1>"));
+    assert!(response.contains("<h2>Playground</h2>"));
+}
@@ -0,0 +1,115 @@
+// Golden Snapshot Tests
+// Runs every lesson into a buffer and compares the text with a saved copy of its expected
+// output (a "golden file") in `tests/golden/<lesson id>.txt`. If a lesson's output changes
+// on purpose, regenerate the files and review the diff before committing:
+//     UPDATE_GOLDEN=1 cargo test
+// Interactive lessons read their answers from `tests/golden/<lesson id>.input`;
+// an interactive lesson without such a file is skipped.
+
+use rust_practice::registry;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+fn golden_path(id: &str) -> PathBuf {
+    golden_dir().join(format!("{}.txt", id))
+}
+
+fn answers_path(id: &str) -> PathBuf {
+    golden_dir().join(format!("{}.input", id))
+}
+
+// Memory addresses printed with `{:p}` change on every run, so they are replaced
+// with a fixed placeholder before comparing.
+fn normalize(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("0x") {
+        normalized.push_str(&rest[..start]);
+        let digits = rest[start + 2..].chars().take_while(|c| c.is_ascii_hexdigit()).count();
+        if digits == 0 {
+            normalized.push_str("0x");
+        } else {
+            normalized.push_str("0x<address>");
+        }
+        rest = &rest[start + 2 + digits..];
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+// Describes the first line that differs, which is easier to read than two full outputs.
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line_number = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line_number += 1,
+            (None, None) => return String::from("only trailing whitespace differs"),
+            (e, a) => {
+                return format!(
+                    "line {}:\n    expected: {}\n    actual:   {}",
+                    line_number,
+                    e.unwrap_or("<end of output>"),
+                    a.unwrap_or("<end of output>")
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_lesson_output_matches_golden_files() {
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+    let mut failures = Vec::new();
+
+    for lesson in registry::all() {
+        let answers = match fs::read_to_string(answers_path(lesson.id())) {
+            Ok(answers) => answers,
+            Err(_) if lesson.is_interactive() => continue,
+            Err(_) => String::new(),
+        };
+        let mut output: Vec<u8> = Vec::new();
+        lesson.run(&mut answers.as_bytes(), &mut output).unwrap();
+        let actual = normalize(&String::from_utf8(output).unwrap());
+        let path = golden_path(lesson.id());
+
+        if update {
+            fs::create_dir_all(golden_dir()).unwrap();
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        match fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!("{}: {}", lesson.id(), first_difference(&expected, &actual))),
+            Err(error) => failures.push(format!("{}: cannot read {}: {}", lesson.id(), path.display(), error)),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "lesson output differs from the golden files (run `UPDATE_GOLDEN=1 cargo test` if the change is intended):\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn test_every_golden_file_belongs_to_a_lesson() {
+    for entry in fs::read_dir(golden_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        let id = path.file_stem().unwrap().to_string_lossy().to_string();
+        assert!(registry::find(&id).is_some(), "{} has no matching lesson", path.display());
+    }
+}
+
+#[test]
+fn test_normalize_replaces_addresses_only() {
+    assert_eq!(normalize("at 0x7ffd5a3c and 0x1f"), "at 0x<address> and 0x<address>");
+    assert_eq!(normalize("0x alone"), "0x alone");
+    assert_eq!(normalize("no addresses"), "no addresses");
+}